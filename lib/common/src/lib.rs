@@ -1,54 +1,201 @@
-use std::{env, num::ParseIntError};
+use std::env;
+use std::error::Error;
+use std::fmt;
 use std::fs;
-use std::path::PathBuf;
+use std::io::{self, Read};
+use std::path::{Path, PathBuf};
 
-pub fn get_input(input_file: &str) -> String {
-    let filename = &mut PathBuf::from(env::var("CARGO_MANIFEST_DIR").unwrap());
-    filename.push( "inputs/".to_owned() + input_file);
+// Where the puzzle input for a part is read from
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Input {
+    File(PathBuf),
+    Stdin,
+}
 
-    println!("Reading {}", filename.display());
+impl Input {
+    // A path given by the user, where - is stdin
+    pub fn from_arg(arg: &str) -> Input {
+        match arg {
+            "-" => Input::Stdin,
+            _ => Input::File(PathBuf::from(arg)),
+        }
+    }
 
-    let input = fs::read_to_string(filename).expect("Unable to open file");
+    pub fn read(&self) -> Result<String, InputError> {
+        let read = match self {
+            Input::File(path) => fs::read_to_string(path),
+            Input::Stdin => {
+                let mut text = String::new();
+                io::stdin().read_to_string(&mut text).map(|_| text)
+            }
+        };
 
-    input
+        read.map_err(|error| InputError::Read {
+            input: self.clone(),
+            error,
+        })
+    }
 }
 
-pub fn to_intcode(input: String) -> Result<Vec<i64>, ParseIntError> {
-    input
-        .split(",")
-        .map(|x| x.parse::<i64>())
-        .collect()
+impl fmt::Display for Input {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Input::File(path) => write!(f, "{}", path.display()),
+            Input::Stdin => write!(f, "stdin"),
+        }
+    }
 }
 
-#[macro_export]
-macro_rules! intcode_task {
-    ($input_file:expr, $func_name1:ident $(, $func_name2:ident)*) => {
-        fn main() {
-            let input = get_input(stringify!($input_file));
-            let program = to_intcode(input);
-
-            let program = program.expect("Invalid intcode program");
+#[derive(Debug)]
+pub enum InputError {
+    // None of the files a part could read are in the directory
+    Missing {
+        day: u32,
+        part: u32,
+        dir: PathBuf,
+        names: Vec<String>,
+    },
+    Read {
+        input: Input,
+        error: io::Error,
+    },
+}
 
-            println!("{} returned: {:?}", stringify!($func_name1), $func_name1(&program));
+impl fmt::Display for InputError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            InputError::Missing {
+                day,
+                part,
+                dir,
+                names,
+            } => write!(
+                f,
+                "No input for day {} part {} in {} (tried {})",
+                day,
+                part,
+                dir.display(),
+                names.join(", ")
+            ),
+            InputError::Read { input, error } => write!(f, "Unable to read {}: {}", input, error),
+        }
+    }
+}
 
-            $(
-                println!("{} returned: {:?}", stringify!($func_name2), $func_name2(&program));
-            )*
+impl Error for InputError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match self {
+            InputError::Read { error, .. } => Some(error),
+            _ => None,
         }
-    };
+    }
+}
+
+// The directory with the puzzle inputs: $AOC_INPUT_DIR if set, inputs/ in the crate when
+// run through cargo, and otherwise inputs/ in the working directory
+pub fn input_dir() -> PathBuf {
+    if let Some(dir) = env::var_os("AOC_INPUT_DIR") {
+        return PathBuf::from(dir);
+    }
+
+    match env::var_os("CARGO_MANIFEST_DIR") {
+        Some(dir) => Path::new(&dir).join("inputs"),
+        None => PathBuf::from("inputs"),
+    }
+}
+
+// The files a part reads, in order of preference. A part with an input of its own has it
+// in DAY_PART.txt, otherwise the parts share DAY.txt, or DAY_1.txt for some older days.
+pub fn input_names(day: u32, part: u32) -> Vec<String> {
+    let mut names = vec![format!("{}_{}.txt", day, part), format!("{}.txt", day)];
+
+    if part != 1 {
+        names.push(format!("{}_1.txt", day));
+    }
+
+    names
+}
+
+// The input of a part in a directory
+pub fn find_input(dir: &Path, day: u32, part: u32) -> Result<Input, InputError> {
+    let names = input_names(day, part);
+
+    let found = names
+        .iter()
+        .map(|name| dir.join(name))
+        .find(|path| path.is_file());
+
+    match found {
+        Some(path) => Ok(Input::File(path)),
+        None => Err(InputError::Missing {
+            day,
+            part,
+            dir: dir.to_path_buf(),
+            names,
+        }),
+    }
+}
+
+// The input of a part, either given by the user or found in the input directory
+pub fn resolve_input(day: u32, part: u32, arg: Option<&str>) -> Result<Input, InputError> {
+    match arg {
+        Some(arg) => Ok(Input::from_arg(arg)),
+        None => find_input(&input_dir(), day, part),
+    }
 }
 
-#[macro_export]
-macro_rules! task {
-    ($input_file:expr, $func_name1:ident $(, $func_name2:ident)*) => {
-        fn main() {
-            let input = get_input(stringify!($input_file));
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::process;
+
+    #[test]
+    fn find_inputs() {
+        let dir = env::temp_dir().join(format!("common-inputs-{}", process::id()));
+        fs::create_dir_all(&dir).unwrap();
+
+        for name in &["7_1.txt", "7_2.txt", "8_1.txt", "14.txt"] {
+            fs::write(dir.join(name), name).unwrap();
+        }
+
+        let read = |day, part| find_input(&dir, day, part).and_then(|input| input.read());
 
-            println!("{} returned: {:?}", stringify!($func_name1), $func_name1(&input));
+        assert_eq!(read(7, 1).unwrap(), "7_1.txt");
+        assert_eq!(read(7, 2).unwrap(), "7_2.txt");
+        assert_eq!(read(8, 2).unwrap(), "8_1.txt");
+        assert_eq!(read(14, 2).unwrap(), "14.txt");
 
-            $(
-                println!("{} returned: {:?}", stringify!($func_name2), $func_name2(&input));
-            )*
+        match find_input(&dir, 15, 1) {
+            Err(InputError::Missing { names, .. }) => {
+                assert_eq!(names, vec!["15_1.txt", "15.txt"])
+            }
+            other => panic!("Expected a missing input, got {:?}", other),
         }
-    };
-}
\ No newline at end of file
+
+        fs::remove_dir_all(&dir).unwrap();
+
+        match read(7, 1) {
+            Err(InputError::Missing { .. }) => (),
+            other => panic!("Expected a missing input, got {:?}", other),
+        }
+    }
+
+    #[test]
+    fn arguments() {
+        assert_eq!(resolve_input(1, 1, Some("-")).unwrap(), Input::Stdin);
+        assert_eq!(
+            resolve_input(1, 1, Some("mine.txt")).unwrap(),
+            Input::File(PathBuf::from("mine.txt"))
+        );
+
+        let missing = Input::from_arg("/nonexistent/input.txt");
+
+        match missing.read() {
+            Err(e @ InputError::Read { .. }) => {
+                let message = e.to_string();
+                assert!(message.starts_with("Unable to read /nonexistent/input.txt"));
+            }
+            other => panic!("Expected a read error, got {:?}", other),
+        }
+    }
+}
@@ -0,0 +1,284 @@
+use std::ops::{Index, IndexMut};
+
+use crate::{GridError, Vec2i, Vec2u};
+
+// A rectangle of tiles stored row by row
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Grid<T> {
+    width: usize,
+    height: usize,
+    cells: Vec<T>,
+}
+
+impl<T: Clone> Grid<T> {
+    pub fn new(width: usize, height: usize, fill: T) -> Grid<T> {
+        Grid {
+            width,
+            height,
+            cells: vec![fill; width * height],
+        }
+    }
+}
+
+impl<T> Grid<T> {
+    // Every tile from its position
+    pub fn from_fn<F>(width: usize, height: usize, mut tile: F) -> Grid<T>
+    where
+        F: FnMut(Vec2i) -> T,
+    {
+        let cells = (0..height)
+            .flat_map(|y| (0..width).map(move |x| Vec2i::new(x as isize, y as isize)))
+            .map(&mut tile)
+            .collect();
+
+        Grid {
+            width,
+            height,
+            cells,
+        }
+    }
+
+    // One row per line, mapping every character to a tile. A trailing newline is fine,
+    // but every line must be as long as the first one.
+    pub fn parse<F>(text: &str, tile: F) -> Result<Grid<T>, GridError>
+    where
+        F: FnMut(char) -> Option<T>,
+    {
+        Grid::parse_lines(text, None, tile)
+    }
+
+    // Like parse, but lines shorter than the longest one are padded with a character
+    pub fn parse_padded<F>(text: &str, padding: char, tile: F) -> Result<Grid<T>, GridError>
+    where
+        F: FnMut(char) -> Option<T>,
+    {
+        Grid::parse_lines(text, Some(padding), tile)
+    }
+
+    fn parse_lines<F>(text: &str, padding: Option<char>, mut tile: F) -> Result<Grid<T>, GridError>
+    where
+        F: FnMut(char) -> Option<T>,
+    {
+        let lines = text.lines().collect::<Vec<_>>();
+        let line_width = |line: &str| line.chars().count();
+
+        let width = match padding {
+            Some(_) => lines.iter().map(|l| line_width(l)).max().unwrap_or(0),
+            None => lines.first().map(|l| line_width(l)).unwrap_or(0),
+        };
+
+        let mut cells = Vec::with_capacity(width * lines.len());
+
+        for (y, line) in lines.iter().enumerate() {
+            let found = line_width(line);
+
+            if padding.is_none() && found != width {
+                return Err(GridError::Ragged {
+                    line: y,
+                    expected: width,
+                    found,
+                });
+            }
+
+            let padded = line.chars().chain(padding.into_iter().cycle()).take(width);
+
+            for (x, c) in padded.enumerate() {
+                let t = tile(c).ok_or(GridError::UnknownTile {
+                    position: Vec2u { x, y },
+                    tile: c,
+                })?;
+
+                cells.push(t);
+            }
+        }
+
+        Ok(Grid {
+            width,
+            height: lines.len(),
+            cells,
+        })
+    }
+
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    pub fn height(&self) -> usize {
+        self.height
+    }
+
+    pub fn size(&self) -> Vec2u {
+        Vec2u {
+            x: self.width,
+            y: self.height,
+        }
+    }
+
+    pub fn contains(&self, position: Vec2i) -> bool {
+        self.index_of(position).is_some()
+    }
+
+    // None outside of the grid
+    pub fn get(&self, position: Vec2i) -> Option<&T> {
+        self.index_of(position).map(|i| &self.cells[i])
+    }
+
+    pub fn get_mut(&mut self, position: Vec2i) -> Option<&mut T> {
+        self.index_of(position).map(move |i| &mut self.cells[i])
+    }
+
+    pub fn row(&self, y: usize) -> &[T] {
+        &self.cells[y * self.width..(y + 1) * self.width]
+    }
+
+    pub fn column(&self, x: usize) -> impl Iterator<Item = &T> {
+        self.cells.iter().skip(x).step_by(self.width.max(1))
+    }
+
+    pub fn positions(&self) -> impl Iterator<Item = Vec2i> {
+        let width = self.width;
+
+        (0..self.cells.len()).map(move |i| Vec2i {
+            x: (i % width) as isize,
+            y: (i / width) as isize,
+        })
+    }
+
+    // Every tile with its position, row by row
+    pub fn iter(&self) -> impl Iterator<Item = (Vec2i, &T)> {
+        self.positions().zip(self.cells.iter())
+    }
+
+    pub fn find<P>(&self, mut predicate: P) -> Option<Vec2i>
+    where
+        P: FnMut(&T) -> bool,
+    {
+        self.iter().find(|(_, t)| predicate(t)).map(|(p, _)| p)
+    }
+
+    // The neighbours that are inside the grid
+    pub fn neighbours4(&self, position: Vec2i) -> impl Iterator<Item = Vec2i> + '_ {
+        position.neighbours4().filter(move |&p| self.contains(p))
+    }
+
+    pub fn neighbours8(&self, position: Vec2i) -> impl Iterator<Item = Vec2i> + '_ {
+        position.neighbours8().filter(move |&p| self.contains(p))
+    }
+
+    pub fn map<U, F>(&self, f: F) -> Grid<U>
+    where
+        F: FnMut(&T) -> U,
+    {
+        Grid {
+            width: self.width,
+            height: self.height,
+            cells: self.cells.iter().map(f).collect(),
+        }
+    }
+
+    // One line per row, ending with a newline
+    pub fn render<F>(&self, mut tile: F) -> String
+    where
+        F: FnMut(&T) -> char,
+    {
+        let mut text = String::with_capacity((self.width + 1) * self.height);
+
+        for y in 0..self.height {
+            text.extend(self.row(y).iter().map(&mut tile));
+            text.push('\n');
+        }
+
+        text
+    }
+
+    fn index_of(&self, position: Vec2i) -> Option<usize> {
+        let Vec2u { x, y } = position.unsigned()?;
+
+        if x < self.width && y < self.height {
+            Some(y * self.width + x)
+        } else {
+            None
+        }
+    }
+}
+
+impl<T> Index<Vec2i> for Grid<T> {
+    type Output = T;
+
+    fn index(&self, position: Vec2i) -> &T {
+        self.get(position)
+            .unwrap_or_else(|| panic!("{:?} is outside of the grid", position))
+    }
+}
+
+impl<T> IndexMut<Vec2i> for Grid<T> {
+    fn index_mut(&mut self, position: Vec2i) -> &mut T {
+        self.get_mut(position)
+            .unwrap_or_else(|| panic!("{:?} is outside of the grid", position))
+    }
+}
+
+impl<T> Index<Vec2u> for Grid<T> {
+    type Output = T;
+
+    fn index(&self, position: Vec2u) -> &T {
+        &self[position.signed()]
+    }
+}
+
+impl<T> IndexMut<Vec2u> for Grid<T> {
+    fn index_mut(&mut self, position: Vec2u) -> &mut T {
+        &mut self[position.signed()]
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn wall(c: char) -> Option<bool> {
+        match c {
+            '#' => Some(true),
+            '.' => Some(false),
+            _ => None,
+        }
+    }
+
+    #[test]
+    fn parse_and_render() {
+        let text = "#..\n.#.\n";
+        let grid = Grid::parse(text, wall).unwrap();
+
+        assert_eq!(grid.size(), Vec2u::new(3, 2));
+        assert!(grid[Vec2i::new(1, 1)]);
+        assert_eq!(grid.get(Vec2i::new(-1, 0)), None);
+        assert_eq!(grid.get(Vec2i::new(3, 0)), None);
+        assert_eq!(grid.column(0).collect::<Vec<_>>(), vec![&true, &false]);
+        assert_eq!(grid.render(|&w| if w { '#' } else { '.' }), text);
+        assert_eq!(grid.find(|&w| w), Some(Vec2i::new(0, 0)));
+        assert_eq!(grid.neighbours4(Vec2i::new(0, 0)).count(), 2);
+        assert_eq!(grid.neighbours8(Vec2i::new(1, 0)).count(), 5);
+    }
+
+    #[test]
+    fn parse_errors() {
+        assert_eq!(
+            Grid::parse("#.\n#\n", wall),
+            Err(GridError::Ragged {
+                line: 1,
+                expected: 2,
+                found: 1
+            })
+        );
+        assert_eq!(
+            Grid::parse("#.\n#x\n", wall),
+            Err(GridError::UnknownTile {
+                position: Vec2u::new(1, 1),
+                tile: 'x'
+            })
+        );
+
+        let padded = Grid::parse_padded("#.#\n#\n", '.', wall).unwrap();
+        assert_eq!(padded.row(1), &[true, false, false]);
+    }
+}
@@ -0,0 +1,52 @@
+use std::fmt;
+
+mod dense;
+mod ocr;
+mod sparse;
+mod vec2;
+
+pub use dense::Grid;
+pub use ocr::{read_letters, OcrError, UnknownGlyph};
+pub use sparse::SparseGrid;
+pub use vec2::{Vec2i, Vec2u, DIRECTIONS4, DIRECTIONS8};
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum GridError {
+    // A line of a different length than the first one
+    Ragged {
+        line: usize,
+        expected: usize,
+        found: usize,
+    },
+    UnknownTile {
+        position: Vec2u,
+        tile: char,
+    },
+}
+
+impl fmt::Display for GridError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            GridError::Ragged {
+                line,
+                expected,
+                found,
+            } => write!(
+                f,
+                "line {} is {} characters long, expected {}",
+                line + 1,
+                found,
+                expected
+            ),
+            GridError::UnknownTile { position, tile } => write!(
+                f,
+                "unknown tile '{}' at line {}, column {}",
+                tile,
+                position.y + 1,
+                position.x + 1
+            ),
+        }
+    }
+}
+
+impl std::error::Error for GridError {}
@@ -0,0 +1,241 @@
+use std::fmt;
+use std::ops::Range;
+
+use crate::Grid;
+
+// The letters of the font some puzzles draw their answers in. Most are 4 pixels wide and
+// all are 6 high, with a blank column between letters.
+const FONT: &[(char, [&str; 6])] = &[
+    ('A', [".##.", "#..#", "#..#", "####", "#..#", "#..#"]),
+    ('B', ["###.", "#..#", "###.", "#..#", "#..#", "###."]),
+    ('C', [".##.", "#..#", "#...", "#...", "#..#", ".##."]),
+    ('E', ["####", "#...", "###.", "#...", "#...", "####"]),
+    ('F', ["####", "#...", "###.", "#...", "#...", "#..."]),
+    ('G', [".##.", "#..#", "#...", "#.##", "#..#", ".###"]),
+    ('H', ["#..#", "#..#", "####", "#..#", "#..#", "#..#"]),
+    ('I', [".###", "..#.", "..#.", "..#.", "..#.", ".###"]),
+    ('J', ["..##", "...#", "...#", "...#", "#..#", ".##."]),
+    ('K', ["#..#", "#.#.", "##..", "#.#.", "#.#.", "#..#"]),
+    ('L', ["#...", "#...", "#...", "#...", "#...", "####"]),
+    ('O', [".##.", "#..#", "#..#", "#..#", "#..#", ".##."]),
+    ('P', ["###.", "#..#", "#..#", "###.", "#...", "#..."]),
+    ('R', ["###.", "#..#", "#..#", "###.", "#.#.", "#..#"]),
+    ('S', [".###", "#...", "#...", ".##.", "...#", "###."]),
+    ('U', ["#..#", "#..#", "#..#", "#..#", "#..#", ".##."]),
+    ('Y', ["#...#", "#...#", ".#.#.", "..#..", "..#..", "..#.."]),
+    ('Z', ["####", "...#", "..#.", ".#..", "#...", "####"]),
+];
+
+// A glyph that isn't a letter of the font, with its pixels as #s and .s
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct UnknownGlyph {
+    pub column: usize,
+    pub bitmap: String,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum OcrError {
+    Blank,
+    // The text has a ? for every glyph that couldn't be read
+    UnknownGlyphs {
+        text: String,
+        glyphs: Vec<UnknownGlyph>,
+    },
+}
+
+impl fmt::Display for OcrError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            OcrError::Blank => write!(f, "the picture is blank"),
+            OcrError::UnknownGlyphs { text, glyphs } => {
+                write!(f, "unknown glyphs in {}", text)?;
+
+                for glyph in glyphs {
+                    write!(f, "\nat column {}:\n{}", glyph.column + 1, glyph.bitmap)?;
+                }
+
+                Ok(())
+            }
+        }
+    }
+}
+
+impl std::error::Error for OcrError {}
+
+// The rows of a letter without the blank columns on either side, like the glyphs are cut
+fn trimmed(rows: &[&str]) -> String {
+    let lit = |x: usize| rows.iter().any(|row| row.as_bytes()[x] == b'#');
+    let width = rows[0].len();
+    let start = (0..width).find(|&x| lit(x)).unwrap();
+    let end = (0..width).rfind(|&x| lit(x)).unwrap() + 1;
+
+    let rows: Vec<_> = rows.iter().map(|row| &row[start..end]).collect();
+    rows.join("\n")
+}
+
+// The letters that exactly cover the columns, trying every letter that fits at the start.
+// A Y fills all of its cell, so it touches the letter after it without a blank column.
+fn letters(pixels: &Grid<bool>, columns: Range<usize>, rows: Range<usize>) -> Option<String> {
+    if columns.is_empty() {
+        return Some(String::new());
+    }
+
+    FONT.iter().find_map(|(letter, glyph)| {
+        let glyph = trimmed(glyph);
+        let end = columns.start + glyph.find('\n')?;
+
+        if end > columns.end || bitmap(pixels, columns.start..end, rows.clone()) != glyph {
+            return None;
+        }
+
+        let rest = letters(pixels, end..columns.end, rows.clone())?;
+
+        Some(format!("{}{}", letter, rest))
+    })
+}
+
+fn bitmap(pixels: &Grid<bool>, columns: Range<usize>, rows: Range<usize>) -> String {
+    let rows: Vec<String> = rows
+        .map(|y| {
+            pixels.row(y)[columns.clone()]
+                .iter()
+                .map(|&lit| if lit { '#' } else { '.' })
+                .collect()
+        })
+        .collect();
+
+    rows.join("\n")
+}
+
+// Reads the letters drawn with lit pixels. The letters may be anywhere in the picture, and
+// are told apart by the blank columns between them, or by the shapes of letters that touch.
+pub fn read_letters(pixels: &Grid<bool>) -> Result<String, OcrError> {
+    let lit_row = |y: usize| pixels.row(y).iter().any(|&lit| lit);
+    let lit_column = |x: usize| pixels.column(x).any(|&lit| lit);
+
+    let top = (0..pixels.height())
+        .find(|&y| lit_row(y))
+        .ok_or(OcrError::Blank)?;
+    let bottom = (0..pixels.height()).rfind(|&y| lit_row(y)).unwrap() + 1;
+
+    let mut text = String::new();
+    let mut glyphs = Vec::new();
+    let mut x = 0;
+
+    while x < pixels.width() {
+        if !lit_column(x) {
+            x += 1;
+            continue;
+        }
+
+        let start = x;
+
+        while x < pixels.width() && lit_column(x) {
+            x += 1;
+        }
+
+        match letters(pixels, start..x, top..bottom) {
+            Some(letters) => text.push_str(&letters),
+            None => {
+                text.push('?');
+                glyphs.push(UnknownGlyph {
+                    column: start,
+                    bitmap: bitmap(pixels, start..x, top..bottom),
+                });
+            }
+        }
+    }
+
+    if glyphs.is_empty() {
+        Ok(text)
+    } else {
+        Err(OcrError::UnknownGlyphs { text, glyphs })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn picture(text: &str) -> Grid<bool> {
+        Grid::parse(text, |c| Some(c == '#')).unwrap()
+    }
+
+    #[test]
+    fn read() {
+        let hull = picture(
+            "\
+..........................
+.###..####.#..#.####..##..
+.#..#.#....#.#..#....#..#.
+.#..#.###..##...###..#....
+.###..#....#.#..#....#....
+.#....#....#.#..#....#..#.
+.#....#....#..#.####..##..
+..........................
+",
+        );
+
+        assert_eq!(read_letters(&hull), Ok("PFKEC".to_string()));
+
+        let font = FONT.iter().map(|(_, rows)| rows.to_vec()).fold(
+            vec![String::new(); 6],
+            |mut picture, rows| {
+                for (line, row) in picture.iter_mut().zip(rows) {
+                    line.push_str(row);
+                    line.push('.');
+                }
+
+                picture
+            },
+        );
+
+        assert_eq!(
+            read_letters(&picture(&font.join("\n"))),
+            Ok("ABCEFGHIJKLOPRSUYZ".to_string())
+        );
+    }
+
+    #[test]
+    fn touching_letters() {
+        let image = picture(
+            "\
+#...##..#.###.
+#...##..#.#..#
+.#.#.####.###.
+..#..#..#.#..#
+..#..#..#.#..#
+..#..#..#.###.
+",
+        );
+
+        assert_eq!(read_letters(&image), Ok("YHB".to_string()));
+    }
+
+    #[test]
+    fn unknown_glyphs() {
+        let image = picture(
+            "\
+####.###
+#....#.#
+###..#.#
+#....#.#
+#....#.#
+####.###
+",
+        );
+
+        assert_eq!(
+            read_letters(&image),
+            Err(OcrError::UnknownGlyphs {
+                text: "E?".to_string(),
+                glyphs: vec![UnknownGlyph {
+                    column: 5,
+                    bitmap: "###\n#.#\n#.#\n#.#\n#.#\n###".to_string(),
+                }],
+            })
+        );
+
+        assert_eq!(read_letters(&picture("...\n...\n")), Err(OcrError::Blank));
+    }
+}
@@ -0,0 +1,145 @@
+use std::collections::HashMap;
+use std::iter::FromIterator;
+
+use crate::{Grid, Vec2i};
+
+// Tiles at arbitrary positions, for maps that are discovered as they are explored
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SparseGrid<T> {
+    cells: HashMap<Vec2i, T>,
+}
+
+impl<T> Default for SparseGrid<T> {
+    fn default() -> SparseGrid<T> {
+        SparseGrid::new()
+    }
+}
+
+impl<T> SparseGrid<T> {
+    pub fn new() -> SparseGrid<T> {
+        SparseGrid {
+            cells: HashMap::new(),
+        }
+    }
+
+    pub fn insert(&mut self, position: Vec2i, tile: T) -> Option<T> {
+        self.cells.insert(position, tile)
+    }
+
+    pub fn remove(&mut self, position: Vec2i) -> Option<T> {
+        self.cells.remove(&position)
+    }
+
+    pub fn get(&self, position: Vec2i) -> Option<&T> {
+        self.cells.get(&position)
+    }
+
+    pub fn get_mut(&mut self, position: Vec2i) -> Option<&mut T> {
+        self.cells.get_mut(&position)
+    }
+
+    pub fn contains(&self, position: Vec2i) -> bool {
+        self.cells.contains_key(&position)
+    }
+
+    pub fn len(&self) -> usize {
+        self.cells.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.cells.is_empty()
+    }
+
+    // In no particular order
+    pub fn iter(&self) -> impl Iterator<Item = (Vec2i, &T)> {
+        self.cells.iter().map(|(&p, t)| (p, t))
+    }
+
+    pub fn positions(&self) -> impl Iterator<Item = Vec2i> + '_ {
+        self.cells.keys().copied()
+    }
+
+    // The smallest and largest corner of the rectangle around every tile
+    pub fn bounds(&self) -> Option<(Vec2i, Vec2i)> {
+        let mut positions = self.positions();
+        let first = positions.next()?;
+
+        Some(positions.fold((first, first), |(min, max), p| {
+            (
+                Vec2i::new(min.x.min(p.x), min.y.min(p.y)),
+                Vec2i::new(max.x.max(p.x), max.y.max(p.y)),
+            )
+        }))
+    }
+
+    // The rectangle around every tile, one line per row ending with a newline. Positions
+    // without a tile are passed as None.
+    pub fn render<F>(&self, mut tile: F) -> String
+    where
+        F: FnMut(Option<&T>) -> char,
+    {
+        let mut text = String::new();
+
+        if let Some((min, max)) = self.bounds() {
+            for y in min.y..=max.y {
+                text.extend((min.x..=max.x).map(|x| tile(self.get(Vec2i { x, y }))));
+                text.push('\n');
+            }
+        }
+
+        text
+    }
+
+    // The rectangle around every tile as a grid, with its smallest corner at the origin.
+    // Positions without a tile are passed as None.
+    pub fn to_dense<U, F>(&self, mut tile: F) -> Grid<U>
+    where
+        F: FnMut(Option<&T>) -> U,
+    {
+        match self.bounds() {
+            Some((min, max)) => {
+                let size = max - min;
+
+                Grid::from_fn(size.x as usize + 1, size.y as usize + 1, |p| {
+                    tile(self.get(min + p))
+                })
+            }
+            None => Grid::from_fn(0, 0, |_| tile(None)),
+        }
+    }
+}
+
+impl<T> FromIterator<(Vec2i, T)> for SparseGrid<T> {
+    fn from_iter<I: IntoIterator<Item = (Vec2i, T)>>(iter: I) -> SparseGrid<T> {
+        SparseGrid {
+            cells: iter.into_iter().collect(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Vec2u;
+
+    #[test]
+    fn bounds_and_render() {
+        let mut grid = SparseGrid::new();
+
+        assert_eq!(grid.bounds(), None);
+        assert_eq!(grid.render(|_| '#'), "");
+
+        grid.insert(Vec2i::new(-1, 2), 'a');
+        grid.insert(Vec2i::new(1, 3), 'b');
+
+        assert_eq!(grid.bounds(), Some((Vec2i::new(-1, 2), Vec2i::new(1, 3))));
+        assert_eq!(grid.render(|t| *t.unwrap_or(&' ')), "a  \n  b\n");
+
+        let dense = grid.to_dense(|t| t.is_some());
+        assert_eq!(dense.size(), Vec2u::new(3, 2));
+        assert_eq!(dense.render(|&t| if t { '#' } else { '.' }), "#..\n..#\n");
+
+        assert_eq!(grid.remove(Vec2i::new(1, 3)), Some('b'));
+        assert_eq!(grid.len(), 1);
+    }
+}
@@ -0,0 +1,243 @@
+use std::ops;
+
+// Points grow to the right and downwards, as the rows of a text map
+#[derive(Debug, Default, Hash, PartialEq, Eq, PartialOrd, Ord, Copy, Clone)]
+pub struct Vec2i {
+    pub x: isize,
+    pub y: isize,
+}
+
+#[derive(Debug, Default, Hash, PartialEq, Eq, PartialOrd, Ord, Copy, Clone)]
+pub struct Vec2u {
+    pub x: usize,
+    pub y: usize,
+}
+
+// North, east, south and west, in clockwise order
+pub const DIRECTIONS4: [Vec2i; 4] = [Vec2i::NORTH, Vec2i::EAST, Vec2i::SOUTH, Vec2i::WEST];
+
+// Clockwise from north, diagonals included
+pub const DIRECTIONS8: [Vec2i; 8] = [
+    Vec2i::NORTH,
+    Vec2i { x: 1, y: -1 },
+    Vec2i::EAST,
+    Vec2i { x: 1, y: 1 },
+    Vec2i::SOUTH,
+    Vec2i { x: -1, y: 1 },
+    Vec2i::WEST,
+    Vec2i { x: -1, y: -1 },
+];
+
+impl Vec2i {
+    pub const ZERO: Vec2i = Vec2i { x: 0, y: 0 };
+    pub const NORTH: Vec2i = Vec2i { x: 0, y: -1 };
+    pub const EAST: Vec2i = Vec2i { x: 1, y: 0 };
+    pub const SOUTH: Vec2i = Vec2i { x: 0, y: 1 };
+    pub const WEST: Vec2i = Vec2i { x: -1, y: 0 };
+
+    pub fn new(x: isize, y: isize) -> Vec2i {
+        Vec2i { x, y }
+    }
+
+    pub fn manhattan(self, other: Vec2i) -> usize {
+        ((self.x - other.x).abs() + (self.y - other.y).abs()) as usize
+    }
+
+    // A quarter turn clockwise, as seen on the map
+    pub fn turn_right(self) -> Vec2i {
+        Vec2i {
+            x: -self.y,
+            y: self.x,
+        }
+    }
+
+    pub fn turn_left(self) -> Vec2i {
+        Vec2i {
+            x: self.y,
+            y: -self.x,
+        }
+    }
+
+    pub fn neighbours4(self) -> impl Iterator<Item = Vec2i> {
+        DIRECTIONS4.iter().map(move |&d| self + d)
+    }
+
+    pub fn neighbours8(self) -> impl Iterator<Item = Vec2i> {
+        DIRECTIONS8.iter().map(move |&d| self + d)
+    }
+
+    // None if either coordinate is negative
+    pub fn unsigned(self) -> Option<Vec2u> {
+        if self.x < 0 || self.y < 0 {
+            return None;
+        }
+
+        Some(Vec2u {
+            x: self.x as usize,
+            y: self.y as usize,
+        })
+    }
+}
+
+impl Vec2u {
+    pub fn new(x: usize, y: usize) -> Vec2u {
+        Vec2u { x, y }
+    }
+
+    pub fn manhattan(self, other: Vec2u) -> usize {
+        self.signed().manhattan(other.signed())
+    }
+
+    pub fn signed(self) -> Vec2i {
+        Vec2i {
+            x: self.x as isize,
+            y: self.y as isize,
+        }
+    }
+
+    // Only the neighbours that don't go below zero
+    pub fn neighbours4(self) -> impl Iterator<Item = Vec2u> {
+        self.signed().neighbours4().filter_map(Vec2i::unsigned)
+    }
+
+    pub fn neighbours8(self) -> impl Iterator<Item = Vec2u> {
+        self.signed().neighbours8().filter_map(Vec2i::unsigned)
+    }
+}
+
+impl From<Vec2u> for Vec2i {
+    fn from(v: Vec2u) -> Vec2i {
+        v.signed()
+    }
+}
+
+impl ops::Add for Vec2i {
+    type Output = Vec2i;
+
+    fn add(self, r: Vec2i) -> Vec2i {
+        Vec2i {
+            x: self.x + r.x,
+            y: self.y + r.y,
+        }
+    }
+}
+
+impl ops::Sub for Vec2i {
+    type Output = Vec2i;
+
+    fn sub(self, r: Vec2i) -> Vec2i {
+        Vec2i {
+            x: self.x - r.x,
+            y: self.y - r.y,
+        }
+    }
+}
+
+impl ops::Mul<isize> for Vec2i {
+    type Output = Vec2i;
+
+    fn mul(self, r: isize) -> Vec2i {
+        Vec2i {
+            x: self.x * r,
+            y: self.y * r,
+        }
+    }
+}
+
+impl ops::Neg for Vec2i {
+    type Output = Vec2i;
+
+    fn neg(self) -> Vec2i {
+        Vec2i {
+            x: -self.x,
+            y: -self.y,
+        }
+    }
+}
+
+impl ops::AddAssign for Vec2i {
+    fn add_assign(&mut self, r: Vec2i) {
+        *self = *self + r;
+    }
+}
+
+impl ops::SubAssign for Vec2i {
+    fn sub_assign(&mut self, r: Vec2i) {
+        *self = *self - r;
+    }
+}
+
+impl ops::Add for Vec2u {
+    type Output = Vec2u;
+
+    fn add(self, r: Vec2u) -> Vec2u {
+        Vec2u {
+            x: self.x + r.x,
+            y: self.y + r.y,
+        }
+    }
+}
+
+// Panics when going below zero, like any other unsigned subtraction
+impl ops::Sub for Vec2u {
+    type Output = Vec2u;
+
+    fn sub(self, r: Vec2u) -> Vec2u {
+        Vec2u {
+            x: self.x - r.x,
+            y: self.y - r.y,
+        }
+    }
+}
+
+impl ops::Mul<usize> for Vec2u {
+    type Output = Vec2u;
+
+    fn mul(self, r: usize) -> Vec2u {
+        Vec2u {
+            x: self.x * r,
+            y: self.y * r,
+        }
+    }
+}
+
+impl ops::AddAssign for Vec2u {
+    fn add_assign(&mut self, r: Vec2u) {
+        *self = *self + r;
+    }
+}
+
+impl ops::SubAssign for Vec2u {
+    fn sub_assign(&mut self, r: Vec2u) {
+        *self = *self - r;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn arithmetic() {
+        let a = Vec2i::new(3, -2);
+
+        assert_eq!(a + Vec2i::EAST * 2, Vec2i::new(5, -2));
+        assert_eq!(-a, Vec2i::new(-3, 2));
+        assert_eq!(a.manhattan(Vec2i::ZERO), 5);
+        assert_eq!(Vec2i::NORTH.turn_right(), Vec2i::EAST);
+        assert_eq!(Vec2i::NORTH.turn_left(), Vec2i::WEST);
+        assert_eq!(a.unsigned(), None);
+        assert_eq!(Vec2u::new(1, 2) + Vec2u::new(1, 1), Vec2u::new(2, 3));
+    }
+
+    #[test]
+    fn neighbours() {
+        assert_eq!(Vec2i::ZERO.neighbours4().count(), 4);
+        assert_eq!(Vec2i::ZERO.neighbours8().count(), 8);
+        assert_eq!(
+            Vec2u::new(0, 1).neighbours4().collect::<Vec<_>>(),
+            vec![Vec2u::new(0, 0), Vec2u::new(1, 1), Vec2u::new(0, 2)]
+        );
+        assert_eq!(Vec2u::new(0, 0).neighbours8().count(), 3);
+    }
+}
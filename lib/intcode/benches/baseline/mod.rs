@@ -0,0 +1,360 @@
+// The VM as it was before instructions were decoded with integer arithmetic: the modes and
+// the opcode are parsed from the instruction word as a string, and every step allocates a
+// Box<dyn Instruction>. Memory is a HashMap, like it was then. Only used as the baseline of
+// the dispatch benchmark.
+
+use std::collections::{HashMap, VecDeque};
+
+#[derive(Debug, Copy, Clone)]
+enum Mode {
+    Immediate,
+    Position,
+    Relative,
+}
+
+impl From<u32> for Mode {
+    fn from(m: u32) -> Mode {
+        match m {
+            0 => Mode::Position,
+            1 => Mode::Immediate,
+            2 => Mode::Relative,
+            _ => panic!("Unknown mode"),
+        }
+    }
+}
+
+impl Mode {
+    fn get_next_3(vm: &IntcodeVM) -> [Mode; 3] {
+        let mut modes = [Mode::Position; 3];
+        for (i, c) in vm.memory[&vm.instruction_ptr]
+            .to_string()
+            .chars()
+            .rev()
+            .skip(2)
+            .enumerate()
+        {
+            if i > 2 {
+                panic!("Unsupported number of operands");
+            }
+
+            modes[i] = Mode::from(c.to_digit(10).unwrap());
+        }
+
+        modes
+    }
+
+    fn get_value(&self, vm: &IntcodeVM, address: usize) -> i64 {
+        let program = &vm.memory;
+
+        match self {
+            Mode::Immediate => program[&address],
+            Mode::Position => {
+                let t = program[&address] as usize;
+                *program.get(&t).unwrap_or(&0)
+            }
+            Mode::Relative => {
+                let t = (program[&address] + vm.relative_base) as usize;
+                *program.get(&t).unwrap_or(&0)
+            }
+        }
+    }
+
+    fn get_addr(&self, vm: &IntcodeVM, address: usize) -> usize {
+        match self {
+            Mode::Immediate => panic!("Invalid mode!"),
+            Mode::Position => vm.memory[&address] as usize,
+            Mode::Relative => (vm.memory[&address] + vm.relative_base) as usize,
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum VMStatus {
+    Ok,
+    EmptyInputBuffer,
+    Halted,
+}
+
+trait Instruction {
+    fn execute(
+        &self,
+        vm: &mut IntcodeVM,
+        input_buffer: &mut VecDeque<i64>,
+        output_buffer: &mut VecDeque<i64>,
+    ) -> VMStatus;
+    fn get_len(&self) -> usize;
+}
+
+// Add, Mult, LessThan and Equals only differ in how they combine their operands
+struct Arithmetic {
+    lhs: i64,
+    rhs: i64,
+    dest: usize,
+    combine: fn(i64, i64) -> i64,
+}
+
+impl Arithmetic {
+    fn new(vm: &IntcodeVM, combine: fn(i64, i64) -> i64) -> Self {
+        let ip = vm.instruction_ptr;
+
+        let modes = Mode::get_next_3(vm);
+        let lhs = modes[0].get_value(vm, ip + 1);
+        let rhs = modes[1].get_value(vm, ip + 2);
+        let dest = modes[2].get_addr(vm, ip + 3);
+
+        Arithmetic {
+            lhs,
+            rhs,
+            dest,
+            combine,
+        }
+    }
+}
+
+impl Instruction for Arithmetic {
+    fn execute(
+        &self,
+        vm: &mut IntcodeVM,
+        _input_buffer: &mut VecDeque<i64>,
+        _output_buffer: &mut VecDeque<i64>,
+    ) -> VMStatus {
+        vm.memory
+            .insert(self.dest, (self.combine)(self.lhs, self.rhs));
+
+        if self.dest != vm.instruction_ptr {
+            vm.instruction_ptr += self.get_len();
+        }
+
+        VMStatus::Ok
+    }
+
+    fn get_len(&self) -> usize {
+        4
+    }
+}
+
+struct Input {
+    dest: usize,
+}
+
+impl Input {
+    fn new(vm: &IntcodeVM) -> Self {
+        let modes = Mode::get_next_3(vm);
+        let dest = modes[0].get_addr(vm, vm.instruction_ptr + 1);
+
+        Input { dest }
+    }
+}
+
+impl Instruction for Input {
+    fn execute(
+        &self,
+        vm: &mut IntcodeVM,
+        input_buffer: &mut VecDeque<i64>,
+        _output_buffer: &mut VecDeque<i64>,
+    ) -> VMStatus {
+        match input_buffer.pop_front() {
+            None => VMStatus::EmptyInputBuffer,
+            Some(value) => {
+                vm.memory.insert(self.dest, value);
+
+                if self.dest != vm.instruction_ptr {
+                    vm.instruction_ptr += self.get_len();
+                }
+
+                VMStatus::Ok
+            }
+        }
+    }
+
+    fn get_len(&self) -> usize {
+        2
+    }
+}
+
+struct Output {
+    val: i64,
+}
+
+impl Output {
+    fn new(vm: &IntcodeVM) -> Self {
+        let modes = Mode::get_next_3(vm);
+        let val = modes[0].get_value(vm, vm.instruction_ptr + 1);
+
+        Output { val }
+    }
+}
+
+impl Instruction for Output {
+    fn execute(
+        &self,
+        vm: &mut IntcodeVM,
+        _input_buffer: &mut VecDeque<i64>,
+        output_buffer: &mut VecDeque<i64>,
+    ) -> VMStatus {
+        vm.instruction_ptr += self.get_len();
+
+        output_buffer.push_back(self.val);
+
+        VMStatus::Ok
+    }
+
+    fn get_len(&self) -> usize {
+        2
+    }
+}
+
+// JumpIfTrue and JumpIfFalse, jumping when the operand is nonzero or zero
+struct Jump {
+    operand: i64,
+    dest: usize,
+    if_true: bool,
+}
+
+impl Jump {
+    fn new(vm: &IntcodeVM, if_true: bool) -> Self {
+        let ip = vm.instruction_ptr;
+
+        let modes = Mode::get_next_3(vm);
+        let operand = modes[0].get_value(vm, ip + 1);
+        let dest = modes[1].get_value(vm, ip + 2) as usize;
+
+        Jump {
+            operand,
+            dest,
+            if_true,
+        }
+    }
+}
+
+impl Instruction for Jump {
+    fn execute(
+        &self,
+        vm: &mut IntcodeVM,
+        _input_buffer: &mut VecDeque<i64>,
+        _output_buffer: &mut VecDeque<i64>,
+    ) -> VMStatus {
+        if (self.operand != 0) == self.if_true {
+            vm.instruction_ptr = self.dest;
+        } else {
+            vm.instruction_ptr += self.get_len();
+        }
+
+        VMStatus::Ok
+    }
+
+    fn get_len(&self) -> usize {
+        3
+    }
+}
+
+struct UpdateRelativeBase {
+    offset: i64,
+}
+
+impl UpdateRelativeBase {
+    fn new(vm: &IntcodeVM) -> Self {
+        let modes = Mode::get_next_3(vm);
+        let offset = modes[0].get_value(vm, vm.instruction_ptr + 1);
+
+        UpdateRelativeBase { offset }
+    }
+}
+
+impl Instruction for UpdateRelativeBase {
+    fn execute(
+        &self,
+        vm: &mut IntcodeVM,
+        _input_buffer: &mut VecDeque<i64>,
+        _output_buffer: &mut VecDeque<i64>,
+    ) -> VMStatus {
+        vm.instruction_ptr += self.get_len();
+        vm.relative_base += self.offset;
+
+        VMStatus::Ok
+    }
+
+    fn get_len(&self) -> usize {
+        2
+    }
+}
+
+struct Halt {}
+
+impl Instruction for Halt {
+    fn execute(
+        &self,
+        _vm: &mut IntcodeVM,
+        _input_buffer: &mut VecDeque<i64>,
+        _output_buffer: &mut VecDeque<i64>,
+    ) -> VMStatus {
+        VMStatus::Halted
+    }
+
+    fn get_len(&self) -> usize {
+        1
+    }
+}
+
+impl dyn Instruction {
+    fn next(vm: &IntcodeVM) -> Box<dyn Instruction> {
+        let opcode = vm.memory[&vm.instruction_ptr]
+            .to_string()
+            .chars()
+            .rev()
+            .take(2)
+            .collect::<String>()
+            .chars()
+            .rev()
+            .collect::<String>()
+            .parse::<isize>()
+            .expect("Invalid opcode");
+
+        match opcode {
+            1 => Box::new(Arithmetic::new(vm, |a, b| a + b)),
+            2 => Box::new(Arithmetic::new(vm, |a, b| a * b)),
+            3 => Box::new(Input::new(vm)),
+            4 => Box::new(Output::new(vm)),
+            5 => Box::new(Jump::new(vm, true)),
+            6 => Box::new(Jump::new(vm, false)),
+            7 => Box::new(Arithmetic::new(vm, |a, b| (a < b) as i64)),
+            8 => Box::new(Arithmetic::new(vm, |a, b| (a == b) as i64)),
+            9 => Box::new(UpdateRelativeBase::new(vm)),
+            99 => Box::new(Halt {}),
+            _ => panic!("Unknown opcode: {}", opcode),
+        }
+    }
+}
+
+pub struct IntcodeVM {
+    instruction_ptr: usize,
+    relative_base: i64,
+    memory: HashMap<usize, i64>,
+}
+
+impl IntcodeVM {
+    pub fn new(program: &[i64]) -> IntcodeVM {
+        IntcodeVM {
+            instruction_ptr: 0,
+            relative_base: 0,
+            memory: program.iter().cloned().enumerate().collect(),
+        }
+    }
+}
+
+pub fn run(
+    vm: &mut IntcodeVM,
+    input_buffer: &mut VecDeque<i64>,
+    output_buffer: &mut VecDeque<i64>,
+) -> VMStatus {
+    loop {
+        let instr = <dyn Instruction>::next(vm);
+        let status = instr.execute(vm, input_buffer, output_buffer);
+
+        if let VMStatus::Ok = status {
+            continue;
+        }
+
+        return status;
+    }
+}
@@ -0,0 +1,153 @@
+// Compares the instruction dispatch strategies of IntcodeVM on the day 9 BOOST program, and
+// the VM from before they existed. Run with `cargo bench -p intcode --bench dispatch`.
+
+mod baseline;
+
+use std::collections::VecDeque;
+use std::fs;
+use std::path::PathBuf;
+use std::time::{Duration, Instant};
+
+use intcode::*;
+
+const WARM_UP: Duration = Duration::from_secs(1);
+const SAMPLES: usize = 20;
+
+fn load_program() -> Vec<i64> {
+    let filename = &mut PathBuf::from(env!("CARGO_MANIFEST_DIR"));
+    filename.push("../../inputs/9_1.txt");
+
+    let input = fs::read_to_string(filename).expect("Unable to open file");
+
+    input
+        .trim()
+        .split(',')
+        .map(|x| x.parse::<i64>().unwrap())
+        .collect()
+}
+
+#[derive(Debug, Clone, Copy)]
+enum Strategy {
+    // A boxed instruction parsed from a string on every step
+    Boxed,
+    Dispatch(Dispatch),
+}
+
+impl Strategy {
+    fn name(&self) -> String {
+        match self {
+            Strategy::Boxed => "boxed".to_string(),
+            Strategy::Dispatch(dispatch) => format!("{:?}", dispatch).to_lowercase(),
+        }
+    }
+}
+
+fn boost(program: &[i64], strategy: Strategy) -> i64 {
+    let mut input_buffer: VecDeque<i64> = vec![2].into_iter().collect();
+    let mut output_buffer = VecDeque::new();
+
+    let dispatch = match strategy {
+        Strategy::Boxed => {
+            let mut vm = baseline::IntcodeVM::new(program);
+
+            match baseline::run(&mut vm, &mut input_buffer, &mut output_buffer) {
+                baseline::VMStatus::Halted => return output_buffer.pop_back().unwrap(),
+                status => panic!("BOOST did not halt: {:?}", status),
+            }
+        }
+        Strategy::Dispatch(dispatch) => dispatch,
+    };
+
+    let mut vm = IntcodeVM::new(program);
+    vm.set_dispatch(dispatch);
+
+    match run(&mut vm, &mut input_buffer, &mut output_buffer) {
+        Ok(VMStatus::Halted) => output_buffer.pop_back().unwrap(),
+        status => panic!("BOOST did not halt: {:?}", status),
+    }
+}
+
+struct Estimate {
+    mean: f64,
+    std_dev: f64,
+    min: f64,
+    max: f64,
+}
+
+fn bench(program: &[i64], strategy: Strategy) -> Estimate {
+    let start = Instant::now();
+    let mut iterations = 0u32;
+
+    while start.elapsed() < WARM_UP {
+        boost(program, strategy);
+        iterations += 1;
+    }
+
+    // Size the samples so that each one takes roughly as long as a tenth of the warm up
+    let per_sample = std::cmp::max(1, iterations / 10);
+    let mut samples = Vec::new();
+
+    for _ in 0..SAMPLES {
+        let start = Instant::now();
+
+        for _ in 0..per_sample {
+            boost(program, strategy);
+        }
+
+        samples.push(start.elapsed().as_secs_f64() / per_sample as f64);
+    }
+
+    let mean = samples.iter().sum::<f64>() / samples.len() as f64;
+    let variance =
+        samples.iter().map(|s| (s - mean) * (s - mean)).sum::<f64>() / (samples.len() - 1) as f64;
+
+    Estimate {
+        mean,
+        std_dev: variance.sqrt(),
+        min: samples.iter().cloned().fold(f64::MAX, f64::min),
+        max: samples.iter().cloned().fold(f64::MIN, f64::max),
+    }
+}
+
+fn format_time(seconds: f64) -> String {
+    if seconds >= 1.0 {
+        format!("{:.3} s", seconds)
+    } else if seconds >= 1e-3 {
+        format!("{:.3} ms", seconds * 1e3)
+    } else {
+        format!("{:.3} us", seconds * 1e6)
+    }
+}
+
+fn main() {
+    let program = load_program();
+    let strategies = [
+        Strategy::Boxed,
+        Strategy::Dispatch(Dispatch::Decode),
+        Strategy::Dispatch(Dispatch::Cached),
+    ];
+    let expected = boost(&program, Strategy::Boxed);
+
+    let mut baseline = None;
+
+    for strategy in strategies.iter() {
+        assert_eq!(boost(&program, *strategy), expected);
+
+        let estimate = bench(&program, *strategy);
+        let name = format!("boost/{}", strategy.name());
+
+        println!(
+            "{:<16} time: [{} {} {}] std dev: {}",
+            name,
+            format_time(estimate.min),
+            format_time(estimate.mean),
+            format_time(estimate.max),
+            format_time(estimate.std_dev)
+        );
+
+        match baseline {
+            None => baseline = Some(estimate.mean),
+            Some(b) => println!("{:<16} speedup: {:.2}x", "", b / estimate.mean),
+        }
+    }
+}
@@ -0,0 +1,329 @@
+use std::fmt;
+use std::thread;
+
+use crate::{IntcodeVM, Link, Network, NetworkError, Outcome, Schedule};
+
+// Instructions each amplifier runs before the next one gets a turn
+const QUANTUM: u64 = 10_000;
+
+// Every ordering of a set of items, each one a single swap away from the last
+// https://en.wikipedia.org/wiki/Heap%27s_algorithm
+#[derive(Debug, Clone)]
+pub struct Permutations<T> {
+    items: Vec<T>,
+    c: Vec<usize>,
+    i: usize,
+    first: bool,
+}
+
+impl<T: Clone> Permutations<T> {
+    pub fn new(items: Vec<T>) -> Permutations<T> {
+        Permutations {
+            c: vec![0; items.len()],
+            items,
+            i: 0,
+            first: true,
+        }
+    }
+}
+
+impl<T: Clone> Iterator for Permutations<T> {
+    type Item = Vec<T>;
+
+    fn next(&mut self) -> Option<Vec<T>> {
+        if self.first {
+            self.first = false;
+            return Some(self.items.clone());
+        }
+
+        while self.i < self.items.len() {
+            let i = self.i;
+
+            if self.c[i] < i {
+                if i % 2 == 0 {
+                    self.items.swap(0, i);
+                } else {
+                    self.items.swap(self.c[i], i);
+                }
+
+                self.c[i] += 1;
+                self.i = 0;
+
+                return Some(self.items.clone());
+            }
+
+            self.c[i] = 0;
+            self.i += 1;
+        }
+
+        None
+    }
+}
+
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub enum ChainMode {
+    // The signal goes through every amplifier once
+    Linear,
+    // The last amplifier feeds back into the first until they all halt
+    Feedback,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum AmplifierError {
+    PhaseCount { expected: usize, found: usize },
+    Network(NetworkError),
+    // The chain stopped without every amplifier halting, or without any output
+    NoSignal(Outcome),
+}
+
+impl fmt::Display for AmplifierError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            AmplifierError::PhaseCount { expected, found } => {
+                write!(f, "expected {} phase settings, found {}", expected, found)
+            }
+            AmplifierError::Network(e) => write!(f, "{}", e),
+            AmplifierError::NoSignal(outcome) => write!(f, "no thruster signal: {:?}", outcome),
+        }
+    }
+}
+
+impl std::error::Error for AmplifierError {}
+
+impl From<NetworkError> for AmplifierError {
+    fn from(e: NetworkError) -> AmplifierError {
+        AmplifierError::Network(e)
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct PhaseSetting {
+    pub phases: Vec<i64>,
+    pub signal: i64,
+}
+
+// A chain of amplifiers running the same program, as in day 7
+#[derive(Debug, Clone)]
+pub struct AmplifierChain<'a> {
+    program: &'a [i64],
+    length: usize,
+    mode: ChainMode,
+}
+
+impl<'a> AmplifierChain<'a> {
+    pub fn new(program: &'a [i64], length: usize, mode: ChainMode) -> AmplifierChain<'a> {
+        AmplifierChain {
+            program,
+            length,
+            mode,
+        }
+    }
+
+    // The thruster signal from sending 0 into the first amplifier with one phase setting
+    // per amplifier
+    pub fn signal(&self, phases: &[i64]) -> Result<i64, AmplifierError> {
+        if phases.len() != self.length || self.length == 0 {
+            return Err(AmplifierError::PhaseCount {
+                expected: self.length,
+                found: phases.len(),
+            });
+        }
+
+        let mut network = Network::new();
+        let last = self.length - 1;
+
+        for (amplifier, &phase) in phases.iter().enumerate() {
+            network.add_node(IntcodeVM::new(self.program));
+            network.send(amplifier, &[phase]);
+
+            if amplifier < last {
+                network.connect(amplifier, Link::Direct(amplifier + 1));
+            }
+        }
+
+        if self.mode == ChainMode::Feedback {
+            network.connect(last, Link::Direct(0));
+        }
+
+        network.send(0, &[0]);
+
+        let outcome = network.run(Schedule::RoundRobin { quantum: QUANTUM })?;
+
+        let signal = match self.mode {
+            ChainMode::Linear => network.output(last).back().copied(),
+            ChainMode::Feedback => network.last_packet(last).map(|packet| packet[0]),
+        };
+
+        match (outcome, signal) {
+            (Outcome::Halted, Some(signal)) => Ok(signal),
+            (outcome, _) => Err(AmplifierError::NoSignal(outcome)),
+        }
+    }
+
+    // Tries every ordering of length of the phase values, spread over a number of threads,
+    // and returns the one giving the highest signal. Ties go to the ordering found first.
+    pub fn search(
+        &self,
+        values: &[i64],
+        threads: usize,
+    ) -> Result<Option<PhaseSetting>, AmplifierError> {
+        if values.len() < self.length {
+            return Err(AmplifierError::PhaseCount {
+                expected: self.length,
+                found: values.len(),
+            });
+        }
+
+        let threads = threads.max(1);
+
+        let results = thread::scope(|scope| {
+            let workers = (0..threads)
+                .map(|worker| {
+                    scope.spawn(move || {
+                        let mut best: Option<(usize, PhaseSetting)> = None;
+
+                        let orderings = self.orderings(values);
+
+                        for (index, phases) in orderings.enumerate() {
+                            if index % threads != worker {
+                                continue;
+                            }
+
+                            let signal = self.signal(&phases)?;
+
+                            if best.as_ref().map_or(true, |(_, b)| signal > b.signal) {
+                                best = Some((index, PhaseSetting { phases, signal }));
+                            }
+                        }
+
+                        Ok(best)
+                    })
+                })
+                .collect::<Vec<_>>();
+
+            workers
+                .into_iter()
+                .map(|worker| worker.join().unwrap())
+                .collect::<Result<Vec<_>, AmplifierError>>()
+        })?;
+
+        let best = results
+            .into_iter()
+            .flatten()
+            .max_by(|(a_index, a), (b_index, b)| {
+                a.signal.cmp(&b.signal).then(b_index.cmp(a_index))
+            });
+
+        Ok(best.map(|(_, setting)| setting))
+    }
+
+    // Every permutation of every choice of length values
+    fn orderings<'v>(&self, values: &'v [i64]) -> impl Iterator<Item = Vec<i64>> + 'v {
+        let length = self.length;
+
+        choices(values.len(), length).flat_map(move |chosen| {
+            let chosen = chosen.iter().map(|&i| values[i]).collect();
+            Permutations::new(chosen)
+        })
+    }
+}
+
+// Every way to pick k of n indices, in increasing order
+fn choices(n: usize, k: usize) -> impl Iterator<Item = Vec<usize>> {
+    let mut next = if k <= n {
+        Some((0..k).collect::<Vec<_>>())
+    } else {
+        None
+    };
+
+    std::iter::from_fn(move || {
+        let current = next.take()?;
+        let mut following = current.clone();
+
+        // Advance the last index that can still move right, and reset the ones after it
+        if let Some(i) = (0..k).rev().find(|&i| following[i] < n - k + i) {
+            following[i] += 1;
+
+            for j in i + 1..k {
+                following[j] = following[j - 1] + 1;
+            }
+
+            next = Some(following);
+        }
+
+        Some(current)
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn heaps_algorithm() {
+        let permutations = Permutations::new(vec![1, 2, 3]).collect::<Vec<_>>();
+
+        assert_eq!(
+            permutations,
+            vec![
+                vec![1, 2, 3],
+                vec![2, 1, 3],
+                vec![3, 1, 2],
+                vec![1, 3, 2],
+                vec![2, 3, 1],
+                vec![3, 2, 1],
+            ]
+        );
+
+        assert_eq!(Permutations::new(vec![0; 5]).count(), 120);
+        assert_eq!(choices(5, 3).count(), 10);
+        assert_eq!(choices(3, 0).collect::<Vec<_>>(), vec![Vec::<usize>::new()]);
+    }
+
+    // The examples from day 7
+    #[test]
+    fn linear() {
+        let program = [
+            3, 15, 3, 16, 1002, 16, 10, 16, 1, 16, 15, 15, 4, 15, 99, 0, 0,
+        ];
+        let chain = AmplifierChain::new(&program, 5, ChainMode::Linear);
+
+        assert_eq!(chain.signal(&[4, 3, 2, 1, 0]), Ok(43210));
+        assert_eq!(
+            chain.search(&[0, 1, 2, 3, 4], 3),
+            Ok(Some(PhaseSetting {
+                phases: vec![4, 3, 2, 1, 0],
+                signal: 43210
+            }))
+        );
+        assert_eq!(
+            chain.signal(&[1, 2]),
+            Err(AmplifierError::PhaseCount {
+                expected: 5,
+                found: 2
+            })
+        );
+    }
+
+    #[test]
+    fn feedback() {
+        let program = [
+            3, 26, 1001, 26, -4, 26, 3, 27, 1002, 27, 2, 27, 1, 27, 26, 27, 4, 27, 1001, 28, -1,
+            28, 1005, 28, 6, 99, 0, 0, 5,
+        ];
+        let chain = AmplifierChain::new(&program, 5, ChainMode::Feedback);
+
+        assert_eq!(chain.signal(&[9, 8, 7, 6, 5]), Ok(139629729));
+        assert_eq!(
+            chain.search(&[5, 6, 7, 8, 9], 4).unwrap().unwrap().phases,
+            vec![9, 8, 7, 6, 5]
+        );
+
+        // Three of the five values
+        let chain = AmplifierChain::new(&program, 3, ChainMode::Feedback);
+        let best = chain.search(&[5, 6, 7, 8, 9], 2).unwrap().unwrap();
+
+        assert_eq!(best.phases.len(), 3);
+        assert_eq!(chain.signal(&best.phases), Ok(best.signal));
+    }
+}
@@ -0,0 +1,505 @@
+use std::collections::VecDeque;
+use std::fmt;
+use std::fs::{self, File};
+use std::io::{self, BufRead, BufReader, BufWriter, Write};
+use std::path::Path;
+
+use crate::{
+    encode_ascii, run, AsciiWriter, IntcodeVM, Memory, OutputDevice, VMStatus, VecMemory, VmError,
+};
+
+pub const TRANSCRIPT_VERSION: u32 = 1;
+
+const MAGIC: &str = "intcode-transcript";
+
+// Lines kept by History
+const HISTORY_LIMIT: usize = 1000;
+
+// Output as text, with values outside of ASCII written as numbers on a line of their own
+pub fn render_ascii(words: &[i64]) -> String {
+    let mut writer = AsciiWriter::new(Vec::new());
+
+    for &word in words {
+        writer.output(word);
+    }
+
+    String::from_utf8_lossy(&writer.into_inner()).into_owned()
+}
+
+// Previously entered lines, recalled with shell style event expansion: !! for the last
+// line, !N for line N, !-N for the Nth last line and !text for the last line starting with
+// text. Lines can't be edited or recalled with the arrow keys.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct History {
+    lines: Vec<String>,
+}
+
+impl History {
+    pub fn new() -> History {
+        History::default()
+    }
+
+    pub fn lines(&self) -> &[String] {
+        &self.lines
+    }
+
+    // Adds a line, unless it's empty or repeats the last one
+    pub fn push(&mut self, line: &str) {
+        if line.trim().is_empty() || self.lines.last().map(|l| l.as_str()) == Some(line) {
+            return;
+        }
+
+        self.lines.push(line.to_string());
+
+        if self.lines.len() > HISTORY_LIMIT {
+            self.lines.remove(0);
+        }
+    }
+
+    // The line with any history reference replaced
+    pub fn expand(&self, line: &str) -> Result<String, String> {
+        let reference = match line.strip_prefix('!') {
+            Some(reference) if !reference.is_empty() => reference,
+            _ => return Ok(line.to_string()),
+        };
+
+        let found = match reference {
+            "!" => self.lines.last(),
+            _ => match reference.parse::<i64>() {
+                Ok(n) if n > 0 => self.lines.get(n as usize - 1),
+                Ok(n) if n < 0 => self
+                    .lines
+                    .len()
+                    .checked_sub(n.unsigned_abs() as usize)
+                    .map(|i| &self.lines[i]),
+                Ok(_) => None,
+                Err(_) => self.lines.iter().rev().find(|l| l.starts_with(reference)),
+            },
+        };
+
+        found
+            .cloned()
+            .ok_or_else(|| format!("!{}: event not found", reference))
+    }
+
+    pub fn load_file<P: AsRef<Path>>(path: P) -> io::Result<History> {
+        let mut history = History::new();
+
+        for line in fs::read_to_string(path)?.lines() {
+            history.push(line);
+        }
+
+        Ok(history)
+    }
+
+    pub fn save_file<P: AsRef<Path>>(&self, path: P) -> io::Result<()> {
+        let mut writer = BufWriter::new(File::create(path)?);
+
+        for line in self.lines.iter() {
+            writeln!(writer, "{}", line)?;
+        }
+
+        writer.flush()
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum Entry {
+    Output(String),
+    Input(String),
+    // How the session ended, the VM status or the fault
+    Exit(String),
+}
+
+#[derive(Debug)]
+pub enum TranscriptError {
+    Io(io::Error),
+    NotATranscript,
+    UnsupportedVersion(String),
+    Malformed { line: usize },
+}
+
+impl fmt::Display for TranscriptError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            TranscriptError::Io(e) => write!(f, "{}", e),
+            TranscriptError::NotATranscript => write!(f, "not an intcode transcript"),
+            TranscriptError::UnsupportedVersion(v) => {
+                write!(f, "unsupported transcript version {}", v)
+            }
+            TranscriptError::Malformed { line } => {
+                write!(f, "malformed transcript at line {}", line)
+            }
+        }
+    }
+}
+
+impl std::error::Error for TranscriptError {}
+
+impl From<io::Error> for TranscriptError {
+    fn from(e: io::Error) -> TranscriptError {
+        TranscriptError::Io(e)
+    }
+}
+
+// A recorded session, saved as text with one line per entry:
+//
+//     intcode-transcript 1
+//     < Command?
+//     > north
+//     # Halted
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct Transcript {
+    pub entries: Vec<Entry>,
+}
+
+impl Transcript {
+    pub fn new() -> Transcript {
+        Transcript::default()
+    }
+
+    pub fn inputs(&self) -> impl Iterator<Item = &str> {
+        self.entries.iter().filter_map(|entry| match entry {
+            Entry::Input(line) => Some(line.as_str()),
+            _ => None,
+        })
+    }
+
+    pub fn save<W: Write>(&self, writer: &mut W) -> io::Result<()> {
+        writeln!(writer, "{} {}", MAGIC, TRANSCRIPT_VERSION)?;
+
+        for entry in self.entries.iter() {
+            match entry {
+                Entry::Output(line) => writeln!(writer, "< {}", line)?,
+                Entry::Input(line) => writeln!(writer, "> {}", line)?,
+                Entry::Exit(status) => writeln!(writer, "# {}", status)?,
+            }
+        }
+
+        Ok(())
+    }
+
+    pub fn load<R: BufRead>(reader: R) -> Result<Transcript, TranscriptError> {
+        let mut lines = reader.lines();
+
+        let header = lines.next().ok_or(TranscriptError::NotATranscript)??;
+        let mut header = header.split_whitespace();
+
+        if header.next() != Some(MAGIC) {
+            return Err(TranscriptError::NotATranscript);
+        }
+
+        match header.next() {
+            Some(version) if version == TRANSCRIPT_VERSION.to_string() => (),
+            version => {
+                let version = version.unwrap_or("").to_string();
+                return Err(TranscriptError::UnsupportedVersion(version));
+            }
+        }
+
+        let mut transcript = Transcript::new();
+
+        for (i, line) in lines.enumerate() {
+            let line = line?;
+
+            // Editors tend to strip the space after a marker on empty lines
+            let (marker, text) = match line.char_indices().nth(1) {
+                Some((1, ' ')) => (&line[..1], &line[2..]),
+                _ => (line.as_str(), ""),
+            };
+
+            let entry = match marker {
+                "<" => Entry::Output(text.to_string()),
+                ">" => Entry::Input(text.to_string()),
+                "#" => Entry::Exit(text.to_string()),
+                _ => return Err(TranscriptError::Malformed { line: i + 2 }),
+            };
+
+            transcript.entries.push(entry);
+        }
+
+        Ok(transcript)
+    }
+
+    pub fn save_file<P: AsRef<Path>>(&self, path: P) -> io::Result<()> {
+        let mut writer = BufWriter::new(File::create(path)?);
+        self.save(&mut writer)?;
+        writer.flush()
+    }
+
+    pub fn load_file<P: AsRef<Path>>(path: P) -> Result<Transcript, TranscriptError> {
+        Transcript::load(BufReader::new(File::open(path)?))
+    }
+}
+
+// Where a replayed session first differs from its transcript
+#[derive(Debug, Clone, PartialEq)]
+pub struct Mismatch {
+    pub entry: usize,
+    pub expected: Option<Entry>,
+    pub found: Option<Entry>,
+}
+
+impl fmt::Display for Mismatch {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "replay differs at entry {}: expected {:?}, found {:?}",
+            self.entry + 1,
+            self.expected,
+            self.found
+        )
+    }
+}
+
+impl std::error::Error for Mismatch {}
+
+// Runs an ASCII program a line of input at a time, recording a transcript as it goes
+#[derive(Debug, Clone)]
+pub struct AsciiSession<M = VecMemory> {
+    vm: IntcodeVM<M>,
+    input: VecDeque<i64>,
+    transcript: Transcript,
+}
+
+impl<M: Memory> AsciiSession<M> {
+    pub fn new(vm: IntcodeVM<M>) -> AsciiSession<M> {
+        AsciiSession {
+            vm,
+            input: VecDeque::new(),
+            transcript: Transcript::new(),
+        }
+    }
+
+    pub fn vm(&self) -> &IntcodeVM<M> {
+        &self.vm
+    }
+
+    pub fn transcript(&self) -> &Transcript {
+        &self.transcript
+    }
+
+    pub fn into_transcript(self) -> Transcript {
+        self.transcript
+    }
+
+    // Runs until the program halts or wants more input, returning its output as text
+    pub fn run(&mut self) -> Result<(VMStatus, String), VmError> {
+        let mut output = Vec::new();
+        let result = run(&mut self.vm, &mut self.input, &mut output);
+        let text = render_ascii(&output);
+
+        for line in text.split_inclusive('\n') {
+            let line = line.strip_suffix('\n').unwrap_or(line);
+            self.transcript
+                .entries
+                .push(Entry::Output(line.to_string()));
+        }
+
+        match &result {
+            Ok(VMStatus::EmptyInputBuffer) => (),
+            Ok(status) => self.finish(&format!("{:?}", status)),
+            Err(e) => self.finish(&e.to_string()),
+        }
+
+        result.map(|status| (status, text))
+    }
+
+    // Sends a line of input, followed by a newline
+    pub fn send(&mut self, line: &str) {
+        self.input.extend(encode_ascii(line));
+        self.input.push_back(10);
+        self.transcript.entries.push(Entry::Input(line.to_string()));
+    }
+
+    fn finish(&mut self, exit: &str) {
+        self.transcript.entries.push(Entry::Exit(exit.to_string()));
+    }
+
+    // Runs the program with input read line by line from reader, until it halts or the
+    // reader runs out. Lines starting with ! recall history, :history lists it and :quit
+    // stops the session.
+    pub fn interact<R: BufRead, W: Write>(
+        &mut self,
+        reader: R,
+        writer: &mut W,
+        history: &mut History,
+    ) -> io::Result<VMStatus> {
+        let mut lines = reader.lines();
+
+        loop {
+            let status = match self.run() {
+                Ok((status, text)) => {
+                    write!(writer, "{}", text)?;
+                    status
+                }
+                Err(e) => {
+                    writeln!(writer, "Intcode program failed: {}", e)?;
+                    return Ok(VMStatus::Faulted);
+                }
+            };
+
+            writer.flush()?;
+
+            if status != VMStatus::EmptyInputBuffer {
+                return Ok(status);
+            }
+
+            loop {
+                let line = match lines.next() {
+                    Some(line) => line?,
+                    None => ":quit".to_string(),
+                };
+
+                match line.trim() {
+                    ":quit" => {
+                        self.finish(&format!("{:?}", status));
+                        return Ok(status);
+                    }
+                    ":history" => {
+                        for (i, line) in history.lines().iter().enumerate() {
+                            writeln!(writer, "{:>5}  {}", i + 1, line)?;
+                        }
+                        continue;
+                    }
+                    _ => (),
+                }
+
+                match history.expand(&line) {
+                    Ok(expanded) => {
+                        if expanded != line {
+                            writeln!(writer, "{}", expanded)?;
+                        }
+
+                        history.push(&expanded);
+                        self.send(&expanded);
+                        break;
+                    }
+                    Err(e) => writeln!(writer, "{}", e)?,
+                }
+            }
+        }
+    }
+}
+
+// Runs a program with the inputs of a transcript, one line each time it waits for input,
+// and checks that it produces the same transcript
+pub fn replay<M: Memory>(
+    vm: IntcodeVM<M>,
+    transcript: &Transcript,
+) -> Result<Transcript, Mismatch> {
+    let mut session = AsciiSession::new(vm);
+    let mut inputs = transcript.inputs();
+
+    while let Ok((VMStatus::EmptyInputBuffer, _)) = session.run() {
+        match inputs.next() {
+            Some(line) => session.send(line),
+            None => {
+                session.finish(&format!("{:?}", VMStatus::EmptyInputBuffer));
+                break;
+            }
+        }
+    }
+
+    let expected = &transcript.entries;
+    let found = session.into_transcript();
+
+    let entry = (0..expected.len().max(found.entries.len()))
+        .find(|&i| expected.get(i) != found.entries.get(i));
+
+    match entry {
+        Some(entry) => Err(Mismatch {
+            entry,
+            expected: expected.get(entry).cloned(),
+            found: found.entries.get(entry).cloned(),
+        }),
+        None => Ok(found),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::assemble;
+
+    // Asks for a name and greets it, then outputs the length of the name plus 1000
+    fn greeter() -> IntcodeVM {
+        let program = assemble(
+            r#"
+                    OUT #63
+                    OUT #10
+            loop:   IN [c]
+                    EQ [c], #10, [done]
+                    JT [done], #end
+                    ADD [count], #1, [count]
+                    OUT [c]
+                    JT #1, #loop
+            end:    OUT #10
+                    ADD [count], #1000, [count]
+                    OUT [count]
+                    HLT
+            c:      data 0
+            done:   data 0
+            count:  data 0
+            "#,
+        )
+        .unwrap();
+
+        IntcodeVM::new(&program)
+    }
+
+    #[test]
+    fn history_expansion() {
+        let mut history = History::new();
+        history.push("north");
+        history.push("take mug");
+        history.push("take mug");
+        history.push("");
+        history.push("south");
+
+        assert_eq!(history.lines().len(), 3);
+        assert_eq!(history.expand("!!"), Ok("south".to_string()));
+        assert_eq!(history.expand("!1"), Ok("north".to_string()));
+        assert_eq!(history.expand("!-2"), Ok("take mug".to_string()));
+        assert_eq!(history.expand("!ta"), Ok("take mug".to_string()));
+        assert_eq!(history.expand("east"), Ok("east".to_string()));
+        assert!(history.expand("!9").is_err());
+        assert!(history.expand("!-4").is_err());
+        assert!(history.expand("!-9223372036854775808").is_err());
+    }
+
+    #[test]
+    fn interact_and_replay() {
+        let mut session = AsciiSession::new(greeter());
+        let mut history = History::new();
+        history.push("bob");
+
+        let mut output = Vec::new();
+        let status = session
+            .interact("!b\n".as_bytes(), &mut output, &mut history)
+            .unwrap();
+
+        assert_eq!(status, VMStatus::Halted);
+        assert_eq!(String::from_utf8(output).unwrap(), "?\nbob\nbob\n1003\n");
+
+        let mut saved = Vec::new();
+        session.transcript().save(&mut saved).unwrap();
+
+        let text = String::from_utf8(saved).unwrap();
+        assert_eq!(
+            text,
+            "intcode-transcript 1\n< ?\n> bob\n< bob\n< 1003\n# Halted\n"
+        );
+
+        let transcript = Transcript::load(text.as_bytes()).unwrap();
+        assert_eq!(&transcript, session.transcript());
+        assert!(replay(greeter(), &transcript).is_ok());
+
+        let mut changed = transcript.clone();
+        changed.entries[1] = Entry::Input("alice".to_string());
+
+        let mismatch = replay(greeter(), &changed).unwrap_err();
+        assert_eq!(mismatch.entry, 2);
+        assert_eq!(mismatch.found, Some(Entry::Output("alice".to_string())));
+    }
+}
@@ -0,0 +1,484 @@
+use std::collections::HashMap;
+use std::fmt;
+
+use crate::{Mode, Op};
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum AsmErrorKind {
+    UnknownMnemonic(String),
+    OperandCount { expected: usize, found: usize },
+    InvalidOperand(String),
+    InvalidLabel(String),
+    InvalidString(String),
+    ImmediateWrite(String),
+    DuplicateLabel(String),
+    UndefinedLabel(String),
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct AsmError {
+    pub line: usize,
+    pub kind: AsmErrorKind,
+}
+
+impl fmt::Display for AsmError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "line {}: ", self.line)?;
+
+        match &self.kind {
+            AsmErrorKind::UnknownMnemonic(m) => write!(f, "unknown mnemonic '{}'", m),
+            AsmErrorKind::OperandCount { expected, found } => {
+                write!(f, "expected {} operands, found {}", expected, found)
+            }
+            AsmErrorKind::InvalidOperand(o) => write!(f, "invalid operand '{}'", o),
+            AsmErrorKind::InvalidLabel(l) => write!(f, "invalid label '{}'", l),
+            AsmErrorKind::InvalidString(s) => write!(f, "invalid string {}", s),
+            AsmErrorKind::ImmediateWrite(o) => {
+                write!(f, "operand '{}' is written to and can't be immediate", o)
+            }
+            AsmErrorKind::DuplicateLabel(l) => write!(f, "label '{}' is already defined", l),
+            AsmErrorKind::UndefinedLabel(l) => write!(f, "undefined label '{}'", l),
+        }
+    }
+}
+
+impl std::error::Error for AsmError {}
+
+// What a source line assembles to, before labels are resolved
+enum Item<'a> {
+    Instruction(&'a str, Vec<&'a str>),
+    Data(Vec<&'a str>),
+    Ascii(Vec<i64>),
+}
+
+impl<'a> Item<'a> {
+    fn len(&self) -> usize {
+        match self {
+            Item::Instruction(_, operands) => operands.len() + 1,
+            Item::Data(values) => values.len(),
+            Item::Ascii(text) => text.len(),
+        }
+    }
+}
+
+// Builds the op for a mnemonic from the operand modes, which have already been counted
+fn build(mnemonic: &str, modes: &[Mode]) -> Op {
+    let mode = |i: usize| modes[i];
+
+    match mnemonic {
+        "ADD" => Op::Add(mode(0), mode(1), mode(2)),
+        "MUL" => Op::Mult(mode(0), mode(1), mode(2)),
+        "IN" => Op::Input(mode(0)),
+        "OUT" => Op::Output(mode(0)),
+        "JT" => Op::JumpIfTrue(mode(0), mode(1)),
+        "JF" => Op::JumpIfFalse(mode(0), mode(1)),
+        "LT" => Op::LessThan(mode(0), mode(1), mode(2)),
+        "EQ" => Op::Equals(mode(0), mode(1), mode(2)),
+        "ARB" => Op::UpdateRelativeBase(mode(0)),
+        "HLT" => Op::Halt,
+        _ => unreachable!(),
+    }
+}
+
+fn operand_count(mnemonic: &str) -> Option<usize> {
+    match mnemonic {
+        "ADD" | "MUL" | "LT" | "EQ" => Some(3),
+        "JT" | "JF" => Some(2),
+        "IN" | "OUT" | "ARB" => Some(1),
+        "HLT" => Some(0),
+        _ => None,
+    }
+}
+
+fn is_label(name: &str) -> bool {
+    let mut chars = name.chars();
+
+    match chars.next() {
+        Some(c) if c.is_ascii_alphabetic() || c == '_' => (),
+        _ => return false,
+    }
+
+    chars.all(|c| c.is_ascii_alphanumeric() || c == '_') && name != "rb"
+}
+
+// Strips a trailing comment, ignoring semicolons inside string literals
+fn strip_comment(line: &str) -> &str {
+    let mut in_string = false;
+    let mut escaped = false;
+
+    for (i, c) in line.char_indices() {
+        match c {
+            _ if escaped => escaped = false,
+            '\\' if in_string => escaped = true,
+            '"' => in_string = !in_string,
+            ';' if !in_string => return &line[..i],
+            _ => (),
+        }
+    }
+
+    line
+}
+
+fn parse_string(text: &str) -> Option<Vec<i64>> {
+    if text.len() < 2 || !text.starts_with('"') || !text.ends_with('"') {
+        return None;
+    }
+
+    let mut words = Vec::new();
+    let mut chars = text[1..text.len() - 1].chars();
+
+    while let Some(c) = chars.next() {
+        let c = match c {
+            '"' => return None,
+            '\\' => match chars.next()? {
+                'n' => '\n',
+                't' => '\t',
+                '\\' => '\\',
+                '"' => '"',
+                _ => return None,
+            },
+            c => c,
+        };
+
+        words.push(c as i64);
+    }
+
+    Some(words)
+}
+
+// A sum of numbers and labels, e.g. `-3`, `loop` or `buffer+2`
+fn evaluate(expr: &str, labels: &HashMap<&str, usize>) -> Result<i64, AsmErrorKind> {
+    let invalid = || AsmErrorKind::InvalidOperand(expr.to_string());
+    let mut total: i64 = 0;
+    let mut sign = 1;
+    let mut rest = expr.trim();
+
+    if rest.is_empty() {
+        return Err(invalid());
+    }
+
+    if let Some(r) = rest.strip_prefix('-') {
+        sign = -1;
+        rest = r.trim_start();
+    }
+
+    loop {
+        let end = rest.find(['+', '-']).unwrap_or(rest.len());
+        let term = rest[..end].trim();
+
+        let value = if is_label(term) {
+            match labels.get(term) {
+                Some(&address) => address as i64,
+                None => return Err(AsmErrorKind::UndefinedLabel(term.to_string())),
+            }
+        } else {
+            term.parse::<i64>().map_err(|_| invalid())?
+        };
+
+        total = value
+            .checked_mul(sign)
+            .and_then(|v| total.checked_add(v))
+            .ok_or_else(invalid)?;
+
+        if end == rest.len() {
+            return Ok(total);
+        }
+
+        sign = if rest[end..].starts_with('-') { -1 } else { 1 };
+        rest = &rest[end + 1..];
+    }
+}
+
+fn operand(text: &str, labels: &HashMap<&str, usize>) -> Result<(Mode, i64), AsmErrorKind> {
+    let invalid = || AsmErrorKind::InvalidOperand(text.to_string());
+
+    if let Some(inner) = text.strip_prefix('[') {
+        let inner = inner.strip_suffix(']').ok_or_else(invalid)?;
+        Ok((Mode::Position, evaluate(inner, labels)?))
+    } else if let Some(expr) = text.strip_prefix('#') {
+        Ok((Mode::Immediate, evaluate(expr, labels)?))
+    } else if let Some(offset) = text.strip_prefix("rb") {
+        let offset = offset.trim_start();
+
+        if offset.is_empty() {
+            Ok((Mode::Relative, 0))
+        } else if offset.starts_with('+') || offset.starts_with('-') {
+            // Evaluate as 0+offset so that the sign applies to the whole first term
+            Ok((Mode::Relative, evaluate(&format!("0{}", offset), labels)?))
+        } else {
+            Err(invalid())
+        }
+    } else {
+        Err(invalid())
+    }
+}
+
+fn split_operands(text: &str) -> Vec<&str> {
+    if text.trim().is_empty() {
+        Vec::new()
+    } else {
+        text.split(',').map(|o| o.trim()).collect()
+    }
+}
+
+fn parse_line<'a>(
+    number: usize,
+    text: &'a str,
+    labels: &mut HashMap<&'a str, usize>,
+    address: usize,
+) -> Result<Option<Item<'a>>, AsmError> {
+    let error = |kind| AsmError { line: number, kind };
+    let mut text = strip_comment(text).trim();
+
+    // Any number of labels can precede the instruction
+    while let Some(colon) = text.find(':') {
+        let label = text[..colon].trim();
+
+        if label.contains('"') {
+            break;
+        }
+
+        if !is_label(label) {
+            return Err(error(AsmErrorKind::InvalidLabel(label.to_string())));
+        }
+
+        if labels.insert(label, address).is_some() {
+            return Err(error(AsmErrorKind::DuplicateLabel(label.to_string())));
+        }
+
+        text = text[colon + 1..].trim_start();
+    }
+
+    if text.is_empty() {
+        return Ok(None);
+    }
+
+    let (mnemonic, rest) = match text.find(char::is_whitespace) {
+        Some(i) => (&text[..i], text[i..].trim()),
+        None => (text, ""),
+    };
+
+    let item = match mnemonic.to_ascii_lowercase().as_str() {
+        "data" => Item::Data(split_operands(rest)),
+        "ascii" => match parse_string(rest) {
+            Some(words) => Item::Ascii(words),
+            None => return Err(error(AsmErrorKind::InvalidString(rest.to_string()))),
+        },
+        _ => {
+            let upper = mnemonic.to_ascii_uppercase();
+            let operands = split_operands(rest);
+
+            let expected = match operand_count(&upper) {
+                Some(n) => n,
+                None => {
+                    return Err(error(AsmErrorKind::UnknownMnemonic(mnemonic.to_string())));
+                }
+            };
+
+            if operands.len() != expected {
+                return Err(error(AsmErrorKind::OperandCount {
+                    expected,
+                    found: operands.len(),
+                }));
+            }
+
+            Item::Instruction(mnemonic, operands)
+        }
+    };
+
+    Ok(Some(item))
+}
+
+// Assembles the mnemonics printed by the disassembler into a program for IntcodeVM.
+//
+//     ; Prints the numbers 3 down to 1
+//             ADD #3, #0, [count]
+//     loop:   OUT [count]
+//             ADD [count], #-1, [count]
+//             JT [count], #loop
+//             HLT
+//     count:  data 0
+//
+// Operands are `[x]` for position, `#x` for immediate and `rb+x` for relative mode, where
+// x is a number, a label or a sum of them. `data` emits comma separated values verbatim
+// and `ascii` emits the characters of a string literal. Comments start with `;`.
+pub fn assemble(source: &str) -> Result<Vec<i64>, AsmError> {
+    let mut labels = HashMap::new();
+    let mut items = Vec::new();
+    let mut address = 0;
+
+    for (i, text) in source.lines().enumerate() {
+        if let Some(item) = parse_line(i + 1, text, &mut labels, address)? {
+            address += item.len();
+            items.push((i + 1, item));
+        }
+    }
+
+    let mut program = Vec::with_capacity(address);
+
+    for (number, item) in items {
+        let error = |kind| AsmError { line: number, kind };
+
+        match item {
+            Item::Instruction(mnemonic, operands) => {
+                let mut modes = Vec::new();
+                let mut values = Vec::new();
+
+                for text in operands.iter() {
+                    let (mode, value) = operand(text, &labels).map_err(error)?;
+                    modes.push(mode);
+                    values.push(value);
+                }
+
+                let op = build(&mnemonic.to_ascii_uppercase(), &modes);
+
+                // Parameters that are written to can't be immediate
+                let write_target = match op {
+                    Op::Add(..) | Op::Mult(..) | Op::LessThan(..) | Op::Equals(..) => Some(2),
+                    Op::Input(_) => Some(0),
+                    _ => None,
+                };
+
+                if let Some(i) = write_target {
+                    if modes[i] == Mode::Immediate {
+                        let operand = operands[i].to_string();
+                        return Err(error(AsmErrorKind::ImmediateWrite(operand)));
+                    }
+                }
+
+                program.push(op.encode());
+                program.extend(values);
+            }
+            Item::Data(values) => {
+                for value in values {
+                    program.push(evaluate(value, &labels).map_err(error)?);
+                }
+            }
+            Item::Ascii(words) => program.extend(words),
+        }
+    }
+
+    Ok(program)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{disassemble, run, IntcodeVM, VMStatus};
+    use std::collections::VecDeque;
+
+    #[test]
+    fn countdown() {
+        let program = assemble(
+            "
+            ; Prints the numbers 3 down to 1
+                    ADD #3, #0, [count]
+            loop:   OUT [count]
+                    ADD [count], #-1, [count]
+                    JT [count], #loop
+                    HLT
+            count:  data 0
+            ",
+        )
+        .unwrap();
+
+        assert_eq!(
+            program,
+            vec![1101, 3, 0, 14, 4, 14, 1001, 14, -1, 14, 1005, 14, 4, 99, 0]
+        );
+
+        let mut vm = IntcodeVM::new(&program);
+        let mut output = VecDeque::new();
+
+        assert_eq!(
+            run(&mut vm, &mut VecDeque::new(), &mut output),
+            Ok(VMStatus::Halted)
+        );
+        assert_eq!(output, vec![3, 2, 1]);
+    }
+
+    #[test]
+    fn relative_operands_and_directives() {
+        let program = assemble(
+            "
+            start:  ARB #buffer         ; Point rb at the string
+                    OUT rb+1
+                    out rb-1            ; Mnemonics are case insensitive
+                    hlt
+                    data 7, -8, start+2
+            buffer: ascii \"a;\\\"\\n\"
+            ",
+        )
+        .unwrap();
+
+        assert_eq!(
+            program,
+            vec![109, 10, 204, 1, 204, -1, 99, 7, -8, 2, 97, 59, 34, 10]
+        );
+    }
+
+    #[test]
+    fn errors() {
+        let error = |source: &str| assemble(source).unwrap_err();
+
+        assert_eq!(
+            error("HLT\nJMP #1"),
+            AsmError {
+                line: 2,
+                kind: AsmErrorKind::UnknownMnemonic("JMP".to_string())
+            }
+        );
+        assert_eq!(
+            error("ADD #1, #2").kind,
+            AsmErrorKind::OperandCount {
+                expected: 3,
+                found: 2
+            }
+        );
+        assert_eq!(
+            error("\n\nIN #1").to_string(),
+            "line 3: operand '#1' is written to and can't be immediate"
+        );
+        assert_eq!(
+            error("JT #1, #end").kind,
+            AsmErrorKind::UndefinedLabel("end".to_string())
+        );
+        assert_eq!(
+            error("a: HLT\na: HLT").kind,
+            AsmErrorKind::DuplicateLabel("a".to_string())
+        );
+        assert_eq!(
+            error("OUT 5").kind,
+            AsmErrorKind::InvalidOperand("5".to_string())
+        );
+        assert_eq!(
+            error("ascii \"unterminated").kind,
+            AsmErrorKind::InvalidString("\"unterminated".to_string())
+        );
+    }
+
+    #[test]
+    fn disassembly_round_trip() {
+        let program = assemble(
+            "
+                    ARB #stack
+                    ADD #ret, #0, rb+0  ; Return address
+                    JT #1, #print
+            ret:    HLT
+            print:  OUT [msg]
+                    JT #1, rb+0
+            msg:    ascii \"Hi\"
+            stack:  data 0
+            ",
+        )
+        .unwrap();
+
+        let source = disassemble(&program)
+            .iter()
+            .map(|line| line.source())
+            .collect::<Vec<_>>()
+            .join("\n");
+
+        assert_eq!(assemble(&source), Ok(program));
+    }
+}
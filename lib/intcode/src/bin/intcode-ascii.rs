@@ -0,0 +1,90 @@
+// Runs an ASCII intcode program interactively, or replays a recorded session.
+// Usage: intcode-ascii [--record TRANSCRIPT] [--history FILE] [--replay TRANSCRIPT] PROGRAM
+//
+// Input lines are sent as ASCII followed by a newline. `!!`, `!N`, `!-N` and `!text` recall
+// earlier lines, `:history` lists them and `:quit` exits. There is no line editing, for
+// that run it under a wrapper like rlwrap. Output values outside of ASCII are printed as
+// numbers. With --record the session is saved as a transcript, which --replay runs again
+// without interaction, failing if the output differs.
+
+use std::env;
+use std::fs;
+use std::io::{self, Write};
+use std::process;
+
+use intcode::*;
+
+fn fail(message: String) -> ! {
+    eprintln!("{}", message);
+    process::exit(1);
+}
+
+fn main() {
+    let mut record = None;
+    let mut history_file = None;
+    let mut replay_file = None;
+    let mut path = None;
+    let mut args = env::args().skip(1);
+
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--record" => record = args.next(),
+            "--history" => history_file = args.next(),
+            "--replay" => replay_file = args.next(),
+            _ => path = Some(arg),
+        }
+    }
+
+    let path = path.unwrap_or_else(|| {
+        fail(
+            "Usage: intcode-ascii [--record TRANSCRIPT] [--history FILE] [--replay TRANSCRIPT] PROGRAM"
+                .to_string(),
+        )
+    });
+
+    let text = fs::read_to_string(&path)
+        .unwrap_or_else(|e| fail(format!("Unable to open {}: {}", path, e)));
+    let program =
+        parse_program(&text).unwrap_or_else(|e| fail(format!("Invalid intcode program: {}", e)));
+
+    if let Some(replay_file) = replay_file {
+        let transcript = Transcript::load_file(&replay_file)
+            .unwrap_or_else(|e| fail(format!("Unable to load {}: {}", replay_file, e)));
+
+        match replay(IntcodeVM::new(&program), &transcript) {
+            Ok(replayed) => println!("{} entries match", replayed.entries.len()),
+            Err(mismatch) => fail(mismatch.to_string()),
+        }
+
+        return;
+    }
+
+    let mut history = match &history_file {
+        Some(file) => History::load_file(file).unwrap_or_default(),
+        None => History::new(),
+    };
+
+    let mut session = AsciiSession::new(IntcodeVM::new(&program));
+    let stdin = io::stdin();
+    let stdout = io::stdout();
+
+    let status = session
+        .interact(stdin.lock(), &mut stdout.lock(), &mut history)
+        .unwrap_or_else(|e| fail(e.to_string()));
+
+    io::stdout().flush().unwrap();
+    eprintln!("{:?}", status);
+
+    if let Some(file) = history_file {
+        if let Err(e) = history.save_file(&file) {
+            eprintln!("Unable to save {}: {}", file, e);
+        }
+    }
+
+    if let Some(file) = record {
+        match session.transcript().save_file(&file) {
+            Ok(()) => eprintln!("Saved {}", file),
+            Err(e) => fail(format!("Unable to save {}: {}", file, e)),
+        }
+    }
+}
@@ -0,0 +1,205 @@
+// Interactive step debugger for intcode programs.
+// Usage: intcode-dbg FILE, then type `help` for the list of commands.
+
+use std::env;
+use std::fs;
+use std::io::{self, BufRead, Write};
+use std::process;
+
+use intcode::*;
+
+const HELP: &str = "\
+s, step [N]         execute N instructions (default 1)
+c, continue         run until a breakpoint, watchpoint, input request or halt
+b, break ADDR       set a breakpoint
+d, delete ADDR      remove a breakpoint or watchpoint
+w, watch ADDR       stop when ADDR is written to
+l, list [N]         show the next N instructions (default 10)
+x ADDR [N]          show N memory words from ADDR (default 8)
+set ADDR VALUE      write VALUE to memory
+rb [VALUE]          show or set the relative base
+in WORDS...         queue input words
+ascii TEXT          queue TEXT followed by a newline as input
+i, info             show registers, breakpoints and watchpoints
+q, quit             exit";
+
+fn parse<T: std::str::FromStr>(arg: Option<&str>) -> Result<T, String> {
+    let arg = arg.ok_or("missing argument")?;
+    arg.parse().map_err(|_| format!("invalid number '{}'", arg))
+}
+
+fn print_output(dbg: &mut Debugger) {
+    let output = dbg.take_output();
+
+    if output.is_empty() {
+        return;
+    }
+
+    if output.iter().all(|&c| c == 10 || (32..127).contains(&c)) {
+        print!("{}", decode_ascii(&output));
+
+        if output.last() != Some(&10) {
+            println!();
+        }
+    } else {
+        println!("output: {:?}", output);
+    }
+}
+
+fn report(dbg: &mut Debugger, stop: Result<Stop, VmError>) {
+    print_output(dbg);
+
+    match stop {
+        Ok(Stop::Step) => (),
+        Ok(Stop::Breakpoint(address)) => println!("breakpoint at {}", address),
+        Ok(Stop::Watchpoint {
+            address,
+            ip,
+            old,
+            new,
+        }) => println!(
+            "watchpoint {}: {} -> {} (written at {})",
+            address, old, new, ip
+        ),
+        Ok(Stop::NeedInput) => println!("waiting for input"),
+        Ok(Stop::Halted) => println!("halted"),
+        Err(e) => println!("fault: {}", e),
+    }
+
+    println!("{}", dbg.current_instruction());
+}
+
+fn execute(dbg: &mut Debugger, line: &str) -> Result<bool, String> {
+    let mut args = line.split_whitespace();
+    let command = match args.next() {
+        Some(command) => command,
+        None => return Ok(true),
+    };
+
+    match command {
+        "s" | "step" => {
+            let count = args.next().map_or(Ok(1), |n| parse(Some(n)))?;
+            let mut stop = Ok(Stop::Step);
+
+            for _ in 0..count {
+                stop = dbg.step();
+
+                if stop != Ok(Stop::Step) {
+                    break;
+                }
+            }
+
+            report(dbg, stop);
+        }
+        "c" | "continue" => {
+            let stop = dbg.cont();
+            report(dbg, stop);
+        }
+        "b" | "break" => dbg.add_breakpoint(parse(args.next())?),
+        "w" | "watch" => dbg.add_watchpoint(parse(args.next())?),
+        "d" | "delete" => {
+            let address = parse(args.next())?;
+
+            if !dbg.remove_breakpoint(address) && !dbg.remove_watchpoint(address) {
+                return Err(format!("nothing set at {}", address));
+            }
+        }
+        "l" | "list" => {
+            let count = args.next().map_or(Ok(10), |n| parse(Some(n)))?;
+            let mut address = dbg.vm().instruction_ptr();
+
+            for _ in 0..count {
+                let line = instruction_at(dbg.vm().memory(), address);
+                println!("{}", line);
+                address += line.words().len();
+            }
+        }
+        "x" => {
+            let address: usize = parse(args.next())?;
+            let count = args.next().map_or(Ok(8), |n| parse(Some(n)))?;
+            let words = (address..address + count)
+                .map(|a| dbg.vm().memory().read(a).to_string())
+                .collect::<Vec<_>>();
+
+            println!("{:>6}: {}", address, words.join(" "));
+        }
+        "set" => {
+            let address = parse(args.next())?;
+            let value = parse(args.next())?;
+
+            dbg.vm_mut()
+                .write(address, value)
+                .map_err(|e| e.to_string())?;
+        }
+        "rb" => match args.next() {
+            Some(value) => dbg.vm_mut().set_relative_base(parse(Some(value))?),
+            None => println!("rb = {}", dbg.vm().relative_base()),
+        },
+        "in" => {
+            let words = args
+                .map(|w| parse(Some(w)))
+                .collect::<Result<Vec<i64>, _>>()?;
+
+            dbg.feed(&words);
+        }
+        "ascii" => {
+            let text = line.trim_start()[command.len()..].trim();
+            dbg.feed(&encode_ascii(&format!("{}\n", text)));
+        }
+        "i" | "info" => {
+            println!(
+                "ip = {}, rb = {}, status = {:?}, pending input = {}",
+                dbg.vm().instruction_ptr(),
+                dbg.vm().relative_base(),
+                dbg.vm().status(),
+                dbg.input().len()
+            );
+            println!("breakpoints: {:?}", dbg.breakpoints());
+            println!("watchpoints: {:?}", dbg.watchpoints());
+        }
+        "h" | "help" => println!("{}", HELP),
+        "q" | "quit" => return Ok(false),
+        _ => return Err(format!("unknown command '{}', try help", command)),
+    }
+
+    Ok(true)
+}
+
+fn main() {
+    let path = env::args().nth(1).unwrap_or_else(|| {
+        eprintln!("Usage: intcode-dbg FILE");
+        process::exit(1);
+    });
+
+    let input = fs::read_to_string(&path).unwrap_or_else(|e| {
+        eprintln!("Unable to open {}: {}", path, e);
+        process::exit(1);
+    });
+
+    let program = parse_program(&input).unwrap_or_else(|e| {
+        eprintln!("Invalid intcode program: {}", e);
+        process::exit(1);
+    });
+
+    let mut dbg = Debugger::new(IntcodeVM::new(&program));
+    let stdin = io::stdin();
+
+    println!("{}", dbg.current_instruction());
+
+    loop {
+        print!("(dbg) ");
+        io::stdout().flush().unwrap();
+
+        let mut line = String::new();
+
+        if stdin.lock().read_line(&mut line).unwrap() == 0 {
+            break;
+        }
+
+        match execute(&mut dbg, &line) {
+            Ok(true) => (),
+            Ok(false) => break,
+            Err(e) => println!("error: {}", e),
+        }
+    }
+}
@@ -0,0 +1,34 @@
+// Prints an annotated listing of an intcode program.
+// Usage: intcode-dis [FILE], reading the program from stdin when no file is given.
+
+use std::env;
+use std::fs;
+use std::io::{self, Read};
+use std::process;
+
+use intcode::*;
+
+fn main() {
+    let input = match env::args().nth(1) {
+        Some(path) => fs::read_to_string(&path).unwrap_or_else(|e| {
+            eprintln!("Unable to open {}: {}", path, e);
+            process::exit(1);
+        }),
+        None => {
+            let mut input = String::new();
+            io::stdin()
+                .read_to_string(&mut input)
+                .expect("Unable to read stdin");
+            input
+        }
+    };
+
+    let program = parse_program(&input).unwrap_or_else(|e| {
+        eprintln!("Invalid intcode program: {}", e);
+        process::exit(1);
+    });
+
+    for line in disassemble(&program) {
+        println!("{}", line);
+    }
+}
@@ -0,0 +1,122 @@
+// Runs an intcode program interactively, with checkpoints.
+// Usage: intcode-run [--numeric] (FILE | --resume SNAPSHOT)
+//
+// Each line read while the program waits for input is sent as ASCII followed by a newline,
+// or with --numeric as comma or space separated numbers. Lines starting with `!` are
+// commands: `!save PATH` writes a snapshot that --resume continues from, `!quit` exits.
+
+use std::collections::VecDeque;
+use std::env;
+use std::fs;
+use std::io::{self, BufRead, Write};
+use std::process;
+
+use intcode::*;
+
+fn fail(message: String) -> ! {
+    eprintln!("{}", message);
+    process::exit(1);
+}
+
+fn print_output(output: &mut VecDeque<i64>) {
+    let words = output.drain(..).collect::<Vec<_>>();
+
+    if words.iter().all(|&c| c == 10 || (32..127).contains(&c)) {
+        print!("{}", decode_ascii(&words));
+    } else {
+        for word in words {
+            println!("{}", word);
+        }
+    }
+
+    io::stdout().flush().unwrap();
+}
+
+fn parse_numbers(line: &str) -> Result<Vec<i64>, String> {
+    line.split(|c: char| c == ',' || c.is_whitespace())
+        .filter(|w| !w.is_empty())
+        .map(|w| w.parse().map_err(|_| format!("invalid number '{}'", w)))
+        .collect()
+}
+
+fn main() {
+    let mut numeric = false;
+    let mut resume = None;
+    let mut path = None;
+    let mut args = env::args().skip(1);
+
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--numeric" => numeric = true,
+            "--resume" => resume = args.next(),
+            _ => path = Some(arg),
+        }
+    }
+
+    let (mut vm, mut input, mut output) = match (resume, path) {
+        (Some(snapshot), None) => {
+            let snapshot = Snapshot::load_file(&snapshot)
+                .unwrap_or_else(|e| fail(format!("Unable to load {}: {}", snapshot, e)));
+            let vm = snapshot.restore().unwrap_or_else(|e| fail(e.to_string()));
+
+            (vm, snapshot.input, snapshot.output)
+        }
+        (None, Some(path)) => {
+            let text = fs::read_to_string(&path)
+                .unwrap_or_else(|e| fail(format!("Unable to open {}: {}", path, e)));
+            let program = parse_program(&text)
+                .unwrap_or_else(|e| fail(format!("Invalid intcode program: {}", e)));
+
+            (IntcodeVM::new(&program), VecDeque::new(), VecDeque::new())
+        }
+        _ => fail("Usage: intcode-run [--numeric] (FILE | --resume SNAPSHOT)".to_string()),
+    };
+
+    let stdin = io::stdin();
+    let mut lines = stdin.lock().lines();
+
+    loop {
+        let status = run(&mut vm, &mut input, &mut output);
+        print_output(&mut output);
+
+        match status {
+            Ok(VMStatus::EmptyInputBuffer) => (),
+            Ok(status) => {
+                println!("{:?}", status);
+                return;
+            }
+            Err(e) => fail(format!("Intcode program failed: {}", e)),
+        }
+
+        // Read until the program has something to consume
+        while input.is_empty() {
+            let line = match lines.next() {
+                Some(line) => line.unwrap(),
+                None => return,
+            };
+
+            if let Some(command) = line.strip_prefix('!') {
+                let mut words = command.split_whitespace();
+
+                match (words.next(), words.next()) {
+                    (Some("save"), Some(path)) => {
+                        match Snapshot::capture(&vm, &input, &output).save_file(path) {
+                            Ok(()) => eprintln!("Saved {}", path),
+                            Err(e) => eprintln!("Unable to save {}: {}", path, e),
+                        }
+                    }
+                    (Some("quit"), None) => return,
+                    _ => eprintln!("Commands are !save PATH and !quit"),
+                }
+            } else if numeric {
+                match parse_numbers(&line) {
+                    Ok(words) => input.extend(words),
+                    Err(e) => eprintln!("{}", e),
+                }
+            } else {
+                input.extend(encode_ascii(&line));
+                input.push_back(10);
+            }
+        }
+    }
+}
@@ -0,0 +1,229 @@
+use std::collections::{BTreeSet, VecDeque};
+
+use crate::{
+    decode, instruction_at, step, IntcodeVM, Line, Memory, Op, VMStatus, VecMemory, VmError,
+};
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum Stop {
+    // A single instruction was executed
+    Step,
+    Breakpoint(usize),
+    Watchpoint {
+        address: usize,
+        ip: usize,
+        old: i64,
+        new: i64,
+    },
+    NeedInput,
+    Halted,
+}
+
+// Wraps a VM and its I/O buffers with breakpoints and watchpoints. Instructions are executed
+// through the regular step function, so programs behave exactly as they do under run.
+#[derive(Debug, Clone)]
+pub struct Debugger<M = VecMemory> {
+    vm: IntcodeVM<M>,
+    input: VecDeque<i64>,
+    output: VecDeque<i64>,
+    breakpoints: BTreeSet<usize>,
+    watchpoints: BTreeSet<usize>,
+}
+
+impl<M: Memory> Debugger<M> {
+    pub fn new(vm: IntcodeVM<M>) -> Debugger<M> {
+        Debugger {
+            vm,
+            input: VecDeque::new(),
+            output: VecDeque::new(),
+            breakpoints: BTreeSet::new(),
+            watchpoints: BTreeSet::new(),
+        }
+    }
+
+    pub fn vm(&self) -> &IntcodeVM<M> {
+        &self.vm
+    }
+
+    pub fn vm_mut(&mut self) -> &mut IntcodeVM<M> {
+        &mut self.vm
+    }
+
+    pub fn into_vm(self) -> IntcodeVM<M> {
+        self.vm
+    }
+
+    pub fn feed(&mut self, words: &[i64]) {
+        self.input.extend(words);
+    }
+
+    pub fn input(&self) -> &VecDeque<i64> {
+        &self.input
+    }
+
+    // Output produced since the last call
+    pub fn take_output(&mut self) -> Vec<i64> {
+        self.output.drain(..).collect()
+    }
+
+    pub fn breakpoints(&self) -> &BTreeSet<usize> {
+        &self.breakpoints
+    }
+
+    pub fn add_breakpoint(&mut self, address: usize) {
+        self.breakpoints.insert(address);
+    }
+
+    pub fn remove_breakpoint(&mut self, address: usize) -> bool {
+        self.breakpoints.remove(&address)
+    }
+
+    pub fn watchpoints(&self) -> &BTreeSet<usize> {
+        &self.watchpoints
+    }
+
+    pub fn add_watchpoint(&mut self, address: usize) {
+        self.watchpoints.insert(address);
+    }
+
+    pub fn remove_watchpoint(&mut self, address: usize) -> bool {
+        self.watchpoints.remove(&address)
+    }
+
+    pub fn current_instruction(&self) -> Line {
+        instruction_at(self.vm.memory(), self.vm.instruction_ptr())
+    }
+
+    // The address the next instruction writes to, if it writes at all
+    fn write_target(&self) -> Option<usize> {
+        let ip = self.vm.instruction_ptr;
+
+        match decode(self.vm.memory.read(ip), ip).ok()? {
+            Op::Add(.., c) | Op::Mult(.., c) | Op::LessThan(.., c) | Op::Equals(.., c) => {
+                self.vm.get_addr(c, ip + 3).ok()
+            }
+            Op::Input(a) => self.vm.get_addr(a, ip + 1).ok(),
+            _ => None,
+        }
+    }
+
+    pub fn step(&mut self) -> Result<Stop, VmError> {
+        let ip = self.vm.instruction_ptr;
+        let watched = self
+            .write_target()
+            .filter(|address| self.watchpoints.contains(address))
+            .map(|address| (address, self.vm.memory.read(address)));
+
+        match step(&mut self.vm, &mut self.input, &mut self.output)? {
+            VMStatus::Ok => (),
+            VMStatus::EmptyInputBuffer => return Ok(Stop::NeedInput),
+            _ => return Ok(Stop::Halted),
+        }
+
+        match watched {
+            Some((address, old)) => Ok(Stop::Watchpoint {
+                address,
+                ip,
+                old,
+                new: self.vm.memory.read(address),
+            }),
+            None => Ok(Stop::Step),
+        }
+    }
+
+    // Runs until a breakpoint or watchpoint is hit, input is needed or the VM halts. Always
+    // executes at least one instruction so that continuing from a breakpoint makes progress.
+    pub fn cont(&mut self) -> Result<Stop, VmError> {
+        loop {
+            match self.step()? {
+                Stop::Step => (),
+                stop => return Ok(stop),
+            }
+
+            let ip = self.vm.instruction_ptr;
+
+            if self.breakpoints.contains(&ip) {
+                return Ok(Stop::Breakpoint(ip));
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::assemble;
+
+    fn debugger(source: &str) -> Debugger {
+        Debugger::new(IntcodeVM::new(&assemble(source).unwrap()))
+    }
+
+    const COUNTDOWN: &str = "
+                ADD #3, #0, [count]
+        loop:   OUT [count]
+                ADD [count], #-1, [count]
+                JT [count], #loop
+                HLT
+        count:  data 0
+    ";
+
+    #[test]
+    fn breakpoints() {
+        let mut dbg = debugger(COUNTDOWN);
+        dbg.add_breakpoint(4);
+
+        assert_eq!(dbg.cont(), Ok(Stop::Breakpoint(4)));
+        assert_eq!(dbg.current_instruction().source(), "OUT [14]");
+        assert_eq!(dbg.cont(), Ok(Stop::Breakpoint(4)));
+        assert_eq!(dbg.take_output(), vec![3]);
+
+        dbg.remove_breakpoint(4);
+
+        assert_eq!(dbg.cont(), Ok(Stop::Halted));
+        assert_eq!(dbg.take_output(), vec![2, 1]);
+    }
+
+    #[test]
+    fn watchpoints() {
+        let mut dbg = debugger(COUNTDOWN);
+        dbg.add_watchpoint(14);
+
+        assert_eq!(
+            dbg.cont(),
+            Ok(Stop::Watchpoint {
+                address: 14,
+                ip: 0,
+                old: 0,
+                new: 3
+            })
+        );
+        assert_eq!(
+            dbg.cont(),
+            Ok(Stop::Watchpoint {
+                address: 14,
+                ip: 6,
+                old: 3,
+                new: 2
+            })
+        );
+    }
+
+    #[test]
+    fn input_and_relative_base() {
+        let mut dbg = debugger("IN rb+5\nOUT rb+5\nHLT");
+
+        assert_eq!(dbg.step(), Ok(Stop::NeedInput));
+        assert_eq!(dbg.vm().instruction_ptr(), 0);
+
+        dbg.feed(&[42]);
+        dbg.vm_mut().set_relative_base(10);
+
+        assert_eq!(dbg.step(), Ok(Stop::Step));
+        assert_eq!(dbg.vm().memory().read(15), 42);
+
+        dbg.vm_mut().write(15, 7).unwrap();
+
+        assert_eq!(dbg.cont(), Ok(Stop::Halted));
+        assert_eq!(dbg.take_output(), vec![7]);
+    }
+}
@@ -0,0 +1,216 @@
+use crate::VmError;
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+pub enum Mode {
+    Position,
+    Immediate,
+    Relative,
+}
+
+impl Mode {
+    pub fn digit(self) -> i64 {
+        match self {
+            Mode::Position => 0,
+            Mode::Immediate => 1,
+            Mode::Relative => 2,
+        }
+    }
+
+    fn from_digit(digit: i64, address: usize) -> Result<Mode, VmError> {
+        match digit {
+            0 => Ok(Mode::Position),
+            1 => Ok(Mode::Immediate),
+            2 => Ok(Mode::Relative),
+            _ => Err(VmError::InvalidMode { digit, address }),
+        }
+    }
+}
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum Op {
+    Add(Mode, Mode, Mode),
+    Mult(Mode, Mode, Mode),
+    Input(Mode),
+    Output(Mode),
+    JumpIfTrue(Mode, Mode),
+    JumpIfFalse(Mode, Mode),
+    LessThan(Mode, Mode, Mode),
+    Equals(Mode, Mode, Mode),
+    UpdateRelativeBase(Mode),
+    Halt,
+}
+
+impl Op {
+    pub fn get_len(&self) -> usize {
+        match self {
+            Op::Add(..) | Op::Mult(..) | Op::LessThan(..) | Op::Equals(..) => 4,
+            Op::JumpIfTrue(..) | Op::JumpIfFalse(..) => 3,
+            Op::Input(_) | Op::Output(_) | Op::UpdateRelativeBase(_) => 2,
+            Op::Halt => 1,
+        }
+    }
+
+    pub fn mnemonic(&self) -> &'static str {
+        match self {
+            Op::Add(..) => "ADD",
+            Op::Mult(..) => "MUL",
+            Op::Input(_) => "IN",
+            Op::Output(_) => "OUT",
+            Op::JumpIfTrue(..) => "JT",
+            Op::JumpIfFalse(..) => "JF",
+            Op::LessThan(..) => "LT",
+            Op::Equals(..) => "EQ",
+            Op::UpdateRelativeBase(_) => "ARB",
+            Op::Halt => "HLT",
+        }
+    }
+
+    pub fn opcode(&self) -> i64 {
+        match self {
+            Op::Add(..) => 1,
+            Op::Mult(..) => 2,
+            Op::Input(_) => 3,
+            Op::Output(_) => 4,
+            Op::JumpIfTrue(..) => 5,
+            Op::JumpIfFalse(..) => 6,
+            Op::LessThan(..) => 7,
+            Op::Equals(..) => 8,
+            Op::UpdateRelativeBase(_) => 9,
+            Op::Halt => 99,
+        }
+    }
+
+    // The instruction word, the inverse of decode
+    pub fn encode(&self) -> i64 {
+        self.modes()
+            .iter()
+            .zip([100, 1000, 10000].iter())
+            .map(|(mode, factor)| mode.digit() * factor)
+            .sum::<i64>()
+            + self.opcode()
+    }
+
+    pub fn modes(&self) -> Vec<Mode> {
+        match *self {
+            Op::Add(a, b, c) | Op::Mult(a, b, c) | Op::LessThan(a, b, c) | Op::Equals(a, b, c) => {
+                vec![a, b, c]
+            }
+            Op::JumpIfTrue(a, b) | Op::JumpIfFalse(a, b) => vec![a, b],
+            Op::Input(a) | Op::Output(a) | Op::UpdateRelativeBase(a) => vec![a],
+            Op::Halt => vec![],
+        }
+    }
+}
+
+fn build(opcode: i64, modes: [Mode; 3], address: usize) -> Result<Op, VmError> {
+    let [a, b, c] = modes;
+
+    let op = match opcode {
+        1 => Op::Add(a, b, c),
+        2 => Op::Mult(a, b, c),
+        3 => Op::Input(a),
+        4 => Op::Output(a),
+        5 => Op::JumpIfTrue(a, b),
+        6 => Op::JumpIfFalse(a, b),
+        7 => Op::LessThan(a, b, c),
+        8 => Op::Equals(a, b, c),
+        9 => Op::UpdateRelativeBase(a),
+        99 => Op::Halt,
+        _ => return Err(VmError::UnknownOpcode { opcode, address }),
+    };
+
+    // Parameters that are written to can't be immediate
+    let write_target = match op {
+        Op::Add(..) | Op::Mult(..) | Op::LessThan(..) | Op::Equals(..) => Some((c, 3)),
+        Op::Input(_) => Some((a, 1)),
+        _ => None,
+    };
+
+    if let Some((Mode::Immediate, offset)) = write_target {
+        return Err(VmError::ImmediateWrite {
+            address: address + offset,
+        });
+    }
+
+    Ok(op)
+}
+
+pub fn decode(word: i64, address: usize) -> Result<Op, VmError> {
+    if word < 0 {
+        return Err(VmError::UnknownOpcode {
+            opcode: word,
+            address,
+        });
+    }
+
+    let modes = [
+        Mode::from_digit(word / 100 % 10, address)?,
+        Mode::from_digit(word / 1000 % 10, address)?,
+        Mode::from_digit(word / 10000 % 10, address)?,
+    ];
+
+    if word >= 100_000 {
+        return Err(VmError::InvalidMode {
+            digit: word / 100_000 % 10,
+            address,
+        });
+    }
+
+    build(word % 100, modes, address)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn decode_modes() {
+        assert_eq!(
+            decode(1002, 0),
+            Ok(Op::Mult(Mode::Position, Mode::Immediate, Mode::Position))
+        );
+        assert_eq!(
+            decode(21107, 0),
+            Ok(Op::LessThan(
+                Mode::Immediate,
+                Mode::Immediate,
+                Mode::Relative
+            ))
+        );
+        assert_eq!(decode(204, 0), Ok(Op::Output(Mode::Relative)));
+        assert_eq!(decode(99, 0), Ok(Op::Halt));
+    }
+
+    #[test]
+    fn decode_errors() {
+        assert_eq!(
+            decode(42, 3),
+            Err(VmError::UnknownOpcode {
+                opcode: 42,
+                address: 3
+            })
+        );
+        assert_eq!(
+            decode(301, 3),
+            Err(VmError::InvalidMode {
+                digit: 3,
+                address: 3
+            })
+        );
+        assert_eq!(
+            decode(101101, 3),
+            Err(VmError::InvalidMode {
+                digit: 1,
+                address: 3
+            })
+        );
+        assert_eq!(decode(103, 3), Err(VmError::ImmediateWrite { address: 4 }));
+    }
+
+    #[test]
+    fn encode_inverts_decode() {
+        for word in [1002, 21107, 204, 99, 1105, 22201].iter() {
+            assert_eq!(decode(*word, 0).unwrap().encode(), *word);
+        }
+    }
+}
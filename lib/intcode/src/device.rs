@@ -0,0 +1,291 @@
+use std::collections::VecDeque;
+use std::io::{self, BufRead, Write};
+
+// Where the VM reads from on opcode 3
+pub trait InputDevice {
+    // The next input word, or None to make the VM wait with VMStatus::EmptyInputBuffer
+    fn input(&mut self) -> Option<i64>;
+}
+
+// Where the VM writes to on opcode 4
+pub trait OutputDevice {
+    fn output(&mut self, value: i64);
+}
+
+impl InputDevice for VecDeque<i64> {
+    fn input(&mut self) -> Option<i64> {
+        self.pop_front()
+    }
+}
+
+impl OutputDevice for VecDeque<i64> {
+    fn output(&mut self, value: i64) {
+        self.push_back(value);
+    }
+}
+
+impl OutputDevice for Vec<i64> {
+    fn output(&mut self, value: i64) {
+        self.push(value);
+    }
+}
+
+impl<D: InputDevice + ?Sized> InputDevice for &mut D {
+    fn input(&mut self) -> Option<i64> {
+        (**self).input()
+    }
+}
+
+impl<D: OutputDevice + ?Sized> OutputDevice for &mut D {
+    fn output(&mut self, value: i64) {
+        (**self).output(value)
+    }
+}
+
+// Input produced by a closure
+pub struct InputFn<F>(pub F);
+
+impl<F: FnMut() -> Option<i64>> InputDevice for InputFn<F> {
+    fn input(&mut self) -> Option<i64> {
+        (self.0)()
+    }
+}
+
+// Output consumed by a closure
+pub struct OutputFn<F>(pub F);
+
+impl<F: FnMut(i64)> OutputDevice for OutputFn<F> {
+    fn output(&mut self, value: i64) {
+        (self.0)(value)
+    }
+}
+
+// Input taken from an iterator until it runs out
+pub struct IterInput<I>(pub I);
+
+impl<I: Iterator<Item = i64>> InputDevice for IterInput<I> {
+    fn input(&mut self) -> Option<i64> {
+        self.0.next()
+    }
+}
+
+// Feeds lines of text as ASCII, each followed by a newline. The VM waits once the reader
+// is exhausted, and on read errors, which are kept for error().
+pub struct AsciiReader<R> {
+    reader: R,
+    line: VecDeque<i64>,
+    error: Option<io::Error>,
+}
+
+impl<R: BufRead> AsciiReader<R> {
+    pub fn new(reader: R) -> AsciiReader<R> {
+        AsciiReader {
+            reader,
+            line: VecDeque::new(),
+            error: None,
+        }
+    }
+
+    pub fn error(&self) -> Option<&io::Error> {
+        self.error.as_ref()
+    }
+}
+
+impl<R: BufRead> InputDevice for AsciiReader<R> {
+    fn input(&mut self) -> Option<i64> {
+        if self.line.is_empty() && self.error.is_none() {
+            let mut line = String::new();
+
+            match self.reader.read_line(&mut line) {
+                Ok(0) => return None,
+                Ok(_) => {
+                    let line = line.trim_end_matches(['\n', '\r']);
+                    self.line.extend(line.chars().map(|c| c as i64));
+                    self.line.push_back(10);
+                }
+                Err(e) => self.error = Some(e),
+            }
+        }
+
+        self.line.pop_front()
+    }
+}
+
+// Writes output as text. Values outside of ASCII, like the puzzle answers that ASCII
+// programs finish with, are written as numbers on a line of their own.
+pub struct AsciiWriter<W> {
+    writer: W,
+    error: Option<io::Error>,
+}
+
+impl<W: Write> AsciiWriter<W> {
+    pub fn new(writer: W) -> AsciiWriter<W> {
+        AsciiWriter {
+            writer,
+            error: None,
+        }
+    }
+
+    // The first write error, output after it is dropped
+    pub fn error(&self) -> Option<&io::Error> {
+        self.error.as_ref()
+    }
+
+    pub fn into_inner(self) -> W {
+        self.writer
+    }
+}
+
+impl<W: Write> OutputDevice for AsciiWriter<W> {
+    fn output(&mut self, value: i64) {
+        if self.error.is_some() {
+            return;
+        }
+
+        let result = match value {
+            0..=127 => self.writer.write_all(&[value as u8]),
+            _ => writeln!(self.writer, "{}", value),
+        };
+
+        // Flush on newlines so that interactive programs show their prompts
+        let result = result.and_then(|_| match value {
+            10 => self.writer.flush(),
+            _ => Ok(()),
+        });
+
+        if let Err(e) = result {
+            self.error = Some(e);
+        }
+    }
+}
+
+// Passes output on to two devices
+pub struct Tee<A, B>(pub A, pub B);
+
+impl<A: OutputDevice, B: OutputDevice> OutputDevice for Tee<A, B> {
+    fn output(&mut self, value: i64) {
+        self.0.output(value);
+        self.1.output(value);
+    }
+}
+
+// Wraps an input or output device and records every word that passes through it
+pub struct Recorder<D> {
+    device: D,
+    words: Vec<i64>,
+}
+
+impl<D> Recorder<D> {
+    pub fn new(device: D) -> Recorder<D> {
+        Recorder {
+            device,
+            words: Vec::new(),
+        }
+    }
+
+    pub fn words(&self) -> &[i64] {
+        &self.words
+    }
+
+    pub fn into_inner(self) -> D {
+        self.device
+    }
+}
+
+impl<D: InputDevice> InputDevice for Recorder<D> {
+    fn input(&mut self) -> Option<i64> {
+        let value = self.device.input();
+        self.words.extend(value);
+        value
+    }
+}
+
+impl<D: OutputDevice> OutputDevice for Recorder<D> {
+    fn output(&mut self, value: i64) {
+        self.words.push(value);
+        self.device.output(value);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{assemble, run, IntcodeVM, VMStatus};
+
+    // Echoes its input until it reads a 0
+    fn echo() -> IntcodeVM {
+        let program = assemble(
+            "
+            loop:   IN [value]
+                    JF [value], #end
+                    OUT [value]
+                    JT #1, #loop
+            end:    OUT #1000
+                    HLT
+            value:  data 0
+            ",
+        )
+        .unwrap();
+
+        IntcodeVM::new(&program)
+    }
+
+    #[test]
+    fn closures_and_iterators() {
+        let mut vm = echo();
+        let mut count = 0;
+        let mut input = InputFn(|| {
+            count += 1;
+            Some(if count < 4 { count * 10 } else { 0 })
+        });
+        let mut sum = 0;
+
+        assert_eq!(
+            run(&mut vm, &mut input, &mut OutputFn(|v| sum += v)),
+            Ok(VMStatus::Halted)
+        );
+        assert_eq!(sum, 1060);
+
+        let mut vm = echo();
+        let mut output = Vec::new();
+
+        assert_eq!(
+            run(&mut vm, &mut IterInput(vec![1, 2].into_iter()), &mut output),
+            Ok(VMStatus::EmptyInputBuffer)
+        );
+        assert_eq!(output, vec![1, 2]);
+    }
+
+    #[test]
+    fn ascii_devices() {
+        let mut vm = echo();
+        let mut input = AsciiReader::new("hi\r\nyou\n".as_bytes());
+        let mut output = Recorder::new(AsciiWriter::new(Vec::new()));
+
+        assert_eq!(
+            run(&mut vm, &mut input, &mut output),
+            Ok(VMStatus::EmptyInputBuffer)
+        );
+        assert_eq!(output.words().len(), 7);
+
+        let mut input = IterInput(Some(0).into_iter());
+
+        assert_eq!(run(&mut vm, &mut input, &mut output), Ok(VMStatus::Halted));
+
+        let text = String::from_utf8(output.into_inner().into_inner()).unwrap();
+        assert_eq!(text, "hi\nyou\n1000\n");
+    }
+
+    #[test]
+    fn tee() {
+        let mut vm = echo();
+        let mut input: VecDeque<i64> = vec![5, 6, 0].into_iter().collect();
+        let mut first = VecDeque::new();
+        let mut second = Vec::new();
+
+        run(&mut vm, &mut input, &mut Tee(&mut first, &mut second)).unwrap();
+
+        assert_eq!(first, vec![5, 6, 1000]);
+        assert_eq!(second, vec![5, 6, 1000]);
+    }
+}
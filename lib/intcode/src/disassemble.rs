@@ -0,0 +1,512 @@
+use std::collections::{BTreeSet, HashSet, VecDeque};
+use std::fmt;
+
+use crate::{decode, Memory, Mode, Op};
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+pub struct Operand {
+    pub mode: Mode,
+    pub value: i64,
+}
+
+impl fmt::Display for Operand {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self.mode {
+            Mode::Position => write!(f, "[{}]", self.value),
+            Mode::Immediate => write!(f, "#{}", self.value),
+            Mode::Relative if self.value < 0 => write!(f, "rb{}", self.value),
+            Mode::Relative => write!(f, "rb+{}", self.value),
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum Line {
+    Instruction {
+        address: usize,
+        words: Vec<i64>,
+        op: Op,
+        operands: Vec<Operand>,
+    },
+    Data {
+        address: usize,
+        words: Vec<i64>,
+    },
+}
+
+impl Line {
+    pub fn address(&self) -> usize {
+        match self {
+            Line::Instruction { address, .. } | Line::Data { address, .. } => *address,
+        }
+    }
+
+    pub fn words(&self) -> &[i64] {
+        match self {
+            Line::Instruction { words, .. } | Line::Data { words, .. } => words,
+        }
+    }
+
+    // The line as assembler source, without the address and raw words
+    pub fn source(&self) -> String {
+        match self {
+            Line::Instruction { op, operands, .. } if operands.is_empty() => {
+                op.mnemonic().to_string()
+            }
+            Line::Instruction { op, operands, .. } => {
+                let operands = operands
+                    .iter()
+                    .map(|o| o.to_string())
+                    .collect::<Vec<_>>()
+                    .join(", ");
+
+                format!("{:<4}{}", op.mnemonic(), operands)
+            }
+            Line::Data { words, .. } => {
+                let words = words
+                    .iter()
+                    .map(|w| w.to_string())
+                    .collect::<Vec<_>>()
+                    .join(", ");
+
+                format!("data {}", words)
+            }
+        }
+    }
+}
+
+impl fmt::Display for Line {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let raw = self
+            .words()
+            .iter()
+            .map(|w| w.to_string())
+            .collect::<Vec<_>>()
+            .join(" ");
+
+        write!(f, "{:>6}: {:<36} ", self.address(), raw)?;
+
+        match self {
+            Line::Instruction { .. } => write!(f, "{}", self.source()),
+            Line::Data { words, .. } => {
+                let text = words
+                    .iter()
+                    .map(|&w| match w {
+                        32..=126 => w as u8 as char,
+                        _ => '.',
+                    })
+                    .collect::<String>();
+
+                write!(f, "DATA  ; {}", text)
+            }
+        }
+    }
+}
+
+const DATA_WORDS_PER_LINE: usize = 8;
+
+fn decode_at(program: &[i64], address: usize) -> Option<Op> {
+    let op = decode(*program.get(address)?, address).ok()?;
+
+    if address + op.get_len() > program.len() {
+        return None;
+    }
+
+    Some(op)
+}
+
+fn operands(program: &[i64], address: usize, op: Op) -> Vec<Operand> {
+    op.modes()
+        .into_iter()
+        .enumerate()
+        .map(|(i, mode)| Operand {
+            mode,
+            value: program[address + 1 + i],
+        })
+        .collect()
+}
+
+// The value an ADD or MUL of two immediates stores, if it could be an address in the program
+fn constant(program: &[i64], op: Op, operands: &[Operand]) -> Option<usize> {
+    let value = match (op, operands) {
+        (Op::Add(Mode::Immediate, Mode::Immediate, _), [a, b, _]) => a.value.checked_add(b.value),
+        (Op::Mult(Mode::Immediate, Mode::Immediate, _), [a, b, _]) => a.value.checked_mul(b.value),
+        _ => None,
+    }?;
+
+    if value >= 0 && (value as usize) < program.len() {
+        Some(value as usize)
+    } else {
+        None
+    }
+}
+
+// The constants stored so far and where, which rb+ slots move with the relative base. An
+// address written with something else has None, as its word in the program is gone.
+type Slots = Vec<(Operand, Option<usize>)>;
+
+// How far below the relative base slots are kept track of. Each call deeper into a
+// recursive function stores another return address further down, and forgetting the ones
+// far down keeps the number of different slots from growing with every call.
+const FRAME_WINDOW: i64 = 32;
+
+// The constant at an operand, stored there or for an address the program starts with
+fn read(program: &[i64], slots: &Slots, operand: Operand) -> Option<usize> {
+    match slots.iter().find(|(o, _)| *o == operand) {
+        Some(&(_, value)) => value,
+        None if operand.mode == Mode::Position => program
+            .get(operand.value as usize)
+            .filter(|&&v| v >= 0 && (v as usize) < program.len())
+            .map(|&v| v as usize),
+        None => None,
+    }
+}
+
+// Updates the slots for an instruction that writes to memory or moves the relative base.
+// A copy made with an ADD of 0 or a MUL by 1 keeps the constant it copies.
+fn store(program: &[i64], op: Op, operands: &[Operand], slots: &mut Slots) {
+    match op {
+        Op::UpdateRelativeBase(Mode::Immediate) => {
+            for (slot, _) in slots.iter_mut().filter(|(o, _)| o.mode == Mode::Relative) {
+                slot.value -= operands[0].value;
+            }
+
+            slots.retain(|(o, _)| o.mode != Mode::Relative || o.value >= -FRAME_WINDOW);
+        }
+        Op::UpdateRelativeBase(_) => slots.retain(|(o, _)| o.mode != Mode::Relative),
+        Op::Add(..) | Op::Mult(..) | Op::LessThan(..) | Op::Equals(..) | Op::Input(_) => {
+            let identity = match op {
+                Op::Add(..) => Some(0),
+                Op::Mult(..) => Some(1),
+                _ => None,
+            };
+            let is_identity = |o: Operand| o.mode == Mode::Immediate && Some(o.value) == identity;
+
+            let copied = match operands[..] {
+                [a, b, _] if is_identity(a) => read(program, slots, b),
+                [a, b, _] if is_identity(b) => read(program, slots, a),
+                _ => None,
+            };
+
+            let written = *operands.last().unwrap();
+            let value = copied.or_else(|| constant(program, op, operands));
+            slots.retain(|(o, _)| *o != written);
+
+            if value.is_some() || written.mode == Mode::Position {
+                slots.push((written, value));
+            }
+        }
+        _ => (),
+    }
+}
+
+// Where control flow goes after an instruction. A jump is followed when its target is a
+// constant in memory: an immediate value, or a constant that was stored where it reads.
+fn successors(program: &[i64], address: usize, op: Op, slots: &Slots) -> Vec<usize> {
+    let operand = |i: usize| program[address + 1 + i];
+    let next = address + op.get_len();
+
+    match op {
+        Op::Halt => vec![],
+        Op::JumpIfTrue(a, b) | Op::JumpIfFalse(a, b) => {
+            let jump_if = matches!(op, Op::JumpIfTrue(..));
+            let mut successors = Vec::new();
+
+            // A constant condition makes the jump unconditional, or a no-op
+            let taken = match a {
+                Mode::Immediate => Some((operand(0) != 0) == jump_if),
+                _ => None,
+            };
+
+            if taken != Some(true) {
+                successors.push(next);
+            }
+
+            // An immediate target is the word in the instruction, which the program can
+            // write another target to
+            let target = match b {
+                Mode::Immediate => Operand {
+                    mode: Mode::Position,
+                    value: (address + 2) as i64,
+                },
+                mode => Operand {
+                    mode,
+                    value: operand(1),
+                },
+            };
+
+            let return_address = Operand {
+                mode: Mode::Relative,
+                value: 0,
+            };
+
+            // A call to a function that isn't known returns to the address the caller
+            // stored at rb+0
+            match read(program, slots, target) {
+                Some(target) if taken != Some(false) => successors.push(target),
+                None if taken == Some(true) => {
+                    successors.extend(read(program, slots, return_address))
+                }
+                _ => (),
+            }
+
+            successors
+        }
+        _ => vec![next],
+    }
+}
+
+// Addresses control flow can reach from address 0 and the given entry points, following
+// jumps to immediate targets
+fn reachable(program: &[i64], entry_points: &BTreeSet<usize>) -> BTreeSet<usize> {
+    let mut code = BTreeSet::new();
+    let mut queue = vec![0];
+    queue.extend(entry_points);
+
+    while let Some(address) = queue.pop() {
+        if code.contains(&address) {
+            continue;
+        }
+
+        if let Some(op) = decode_at(program, address) {
+            code.insert(address);
+            queue.extend(successors(program, address, op, &Slots::new()));
+        }
+    }
+
+    code
+}
+
+// The most states looked at when following the constants stored from one address
+const FLOW_LIMIT: usize = 2000;
+
+// The targets of the computed jumps that read back a constant stored from the given
+// address on, following calls into functions and their returns back to the caller
+fn computed_targets(program: &[i64], start: usize) -> BTreeSet<usize> {
+    let mut targets = BTreeSet::new();
+    let mut seen = HashSet::new();
+    let mut queue = VecDeque::new();
+
+    queue.push_back((start, Slots::new()));
+
+    while let Some((address, mut slots)) = queue.pop_front() {
+        if seen.len() >= FLOW_LIMIT || !seen.insert((address, slots.clone())) {
+            continue;
+        }
+
+        let op = match decode_at(program, address) {
+            Some(op) => op,
+            None => continue,
+        };
+
+        let operands = operands(program, address, op);
+        let next = successors(program, address, op, &slots);
+
+        if let Op::JumpIfTrue(..) | Op::JumpIfFalse(..) = op {
+            let fixed = successors(program, address, op, &Slots::new());
+            targets.extend(next.iter().filter(|a| !fixed.contains(a)));
+        }
+
+        store(program, op, &operands, &mut slots);
+
+        for address in next {
+            queue.push_back((address, slots.clone()));
+        }
+    }
+
+    targets
+}
+
+// The code of a program. Jumps are followed when their target is an immediate value, or a
+// constant the program stored with an ADD or MUL of two immediates and the jump reads back,
+// like the return address a function returns to. Constants are followed from the start of
+// each run of straight line code that stores one. Constants no jump reads, like pointers
+// into data, don't make code of what they point to.
+fn find_code(program: &[i64]) -> BTreeSet<usize> {
+    let mut entry_points = BTreeSet::new();
+    let mut followed = BTreeSet::new();
+
+    loop {
+        let code = reachable(program, &entry_points);
+        let mut found = false;
+
+        for &address in code.iter() {
+            let op = decode_at(program, address).unwrap();
+
+            if constant(program, op, &operands(program, address, op)).is_none() {
+                continue;
+            }
+
+            // Back to the first instruction that isn't only reached from the one before it
+            let mut start = address;
+
+            while let Some(&previous) = code.range(..start).next_back() {
+                let op = decode_at(program, previous).unwrap();
+                let jumps = matches!(op, Op::JumpIfTrue(..) | Op::JumpIfFalse(..) | Op::Halt);
+
+                if previous + op.get_len() != start || jumps {
+                    break;
+                }
+
+                start = previous;
+            }
+
+            if followed.insert(start) {
+                for target in computed_targets(program, start) {
+                    found |= entry_points.insert(target);
+                }
+            }
+        }
+
+        if !found {
+            return code;
+        }
+    }
+}
+
+// Decodes the single instruction at an address, or a data word if it isn't one
+pub fn instruction_at<M: Memory>(memory: &M, address: usize) -> Line {
+    let word = memory.read(address);
+
+    match decode(word, address) {
+        Ok(op) => {
+            let words = (address..address + op.get_len())
+                .map(|a| memory.read(a))
+                .collect::<Vec<_>>();
+
+            let operands = op
+                .modes()
+                .into_iter()
+                .zip(words[1..].iter())
+                .map(|(mode, &value)| Operand { mode, value })
+                .collect();
+
+            Line::Instruction {
+                address,
+                words,
+                op,
+                operands,
+            }
+        }
+        Err(_) => Line::Data {
+            address,
+            words: vec![word],
+        },
+    }
+}
+
+pub fn disassemble(program: &[i64]) -> Vec<Line> {
+    let code = find_code(program);
+    let mut lines = Vec::new();
+    let mut address = 0;
+
+    while address < program.len() {
+        if code.contains(&address) {
+            let op = decode_at(program, address).unwrap();
+            let len = op.get_len();
+
+            lines.push(Line::Instruction {
+                address,
+                words: program[address..address + len].to_vec(),
+                op,
+                operands: operands(program, address, op),
+            });
+
+            address += len;
+        } else {
+            let start = address;
+
+            while address < program.len()
+                && !code.contains(&address)
+                && address - start < DATA_WORDS_PER_LINE
+            {
+                address += 1;
+            }
+
+            lines.push(Line::Data {
+                address: start,
+                words: program[start..address].to_vec(),
+            });
+        }
+    }
+
+    lines
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn operand_sigils() {
+        let lines = disassemble(&[21101, 3, 4, -2, 204, 5, 99]);
+
+        assert_eq!(
+            lines[0].to_string().trim_end(),
+            format!("{:>6}: {:<36} ADD #3, #4, rb-2", 0, "21101 3 4 -2")
+        );
+        assert_eq!(
+            lines[1].to_string().trim_end(),
+            format!("{:>6}: {:<36} OUT rb+5", 4, "204 5")
+        );
+        assert_eq!(lines[2].to_string(), format!("{:>6}: {:<36} HLT", 6, "99"));
+    }
+
+    #[test]
+    fn data_after_unconditional_jump() {
+        // Jumps over a two word string to the halt at address 5
+        let program = [1105, 1, 5, 72, 105, 99];
+        let lines = disassemble(&program);
+
+        assert_eq!(lines.len(), 3);
+        assert_eq!(
+            lines[1],
+            Line::Data {
+                address: 3,
+                words: vec![72, 105]
+            }
+        );
+        assert!(lines[1].to_string().ends_with("DATA  ; Hi"));
+        assert_eq!(lines[2].address(), 5);
+    }
+
+    #[test]
+    fn data_pointers_are_data() {
+        // Passes a pointer to the words at 12, which would decode as OUT #42, to the
+        // function at 14 along with return address 11
+        let program = [
+            21101, 0, 12, 1, 21101, 0, 11, 0, 1105, 1, 14, 99, 104, 42, 2106, 0, 0,
+        ];
+        let lines = disassemble(&program);
+        let addresses = lines.iter().map(|l| l.address()).collect::<Vec<_>>();
+
+        assert_eq!(addresses, vec![0, 4, 8, 11, 12, 14]);
+        assert_eq!(
+            lines[4],
+            Line::Data {
+                address: 12,
+                words: vec![104, 42]
+            }
+        );
+    }
+
+    #[test]
+    fn return_addresses_are_code() {
+        // Stores return address 8 and jumps to the function at 10, which jumps back
+        let program = [21101, 0, 8, 0, 1105, 1, 10, 0, 104, 1, 2106, 0, 0, 99];
+        let lines = disassemble(&program);
+        let addresses = lines.iter().map(|l| l.address()).collect::<Vec<_>>();
+
+        assert_eq!(addresses, vec![0, 4, 7, 8, 10, 13]);
+
+        match &lines[3] {
+            Line::Instruction { op, .. } => assert_eq!(op.mnemonic(), "OUT"),
+            _ => panic!("Expected an instruction at address 8"),
+        }
+
+        match &lines[2] {
+            Line::Data { words, .. } => assert_eq!(words, &vec![0]),
+            _ => panic!("Expected data at address 7"),
+        }
+    }
+}
@@ -1,563 +1,730 @@
-use std::collections::HashMap;
-use std::collections::VecDeque;
+use std::fmt;
+use std::num::ParseIntError;
+
+mod amplifier;
+mod ascii;
+mod assemble;
+mod debugger;
+mod decode;
+mod device;
+mod disassemble;
+mod legacy;
+mod memory;
+mod network;
+mod scheduler;
+mod snapshot;
+pub mod springscript;
+mod trace;
+
+pub use amplifier::{AmplifierChain, AmplifierError, ChainMode, Permutations, PhaseSetting};
+pub use ascii::{
+    render_ascii, replay, AsciiSession, Entry, History, Mismatch, Transcript, TranscriptError,
+    TRANSCRIPT_VERSION,
+};
+pub use assemble::{assemble, AsmError, AsmErrorKind};
+pub use debugger::{Debugger, Stop};
+pub use decode::{decode, Mode, Op};
+pub use device::*;
+pub use disassemble::{disassemble, instruction_at, Line, Operand};
+pub use legacy::LegacyInterpreter;
+pub use memory::*;
+pub use network::{
+    Control, Interceptor, Link, Nat, Network, NetworkError, NodeId, Outcome, Schedule,
+};
+pub use scheduler::{Process, RoundRobin};
+pub use snapshot::{Snapshot, SnapshotError, SNAPSHOT_VERSION};
+pub use trace::{run_traced, JsonTracer, Limiter, Profiler, TraceEvent, Tracer};
 
-#[derive(Debug, Copy, Clone)]
-enum Mode {
-    Immediate,
-    Position,
-    Relative,
+#[derive(Debug, Clone, PartialEq)]
+pub enum VmError {
+    UnknownOpcode { opcode: i64, address: usize },
+    InvalidMode { digit: i64, address: usize },
+    ImmediateWrite { address: usize },
+    NegativeAddress { target: i64, address: usize },
+    Overflow { address: usize },
+    MemoryLimit { address: usize, limit: usize },
 }
 
-impl From<u32> for Mode {
-    fn from(m: u32) -> Mode {
-        match m {
-            0 => Mode::Position,
-            1 => Mode::Immediate,
-            2 => Mode::Relative,
-            _ => panic!("Unknown mode"),
+impl fmt::Display for VmError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            VmError::UnknownOpcode { opcode, address } => {
+                write!(f, "unknown opcode {} at address {}", opcode, address)
+            }
+            VmError::InvalidMode { digit, address } => {
+                write!(f, "invalid parameter mode {} at address {}", digit, address)
+            }
+            VmError::ImmediateWrite { address } => {
+                write!(
+                    f,
+                    "write to immediate mode parameter at address {}",
+                    address
+                )
+            }
+            VmError::NegativeAddress { target, address } => {
+                write!(
+                    f,
+                    "negative address {} referenced at address {}",
+                    target, address
+                )
+            }
+            VmError::Overflow { address } => {
+                write!(f, "arithmetic overflow at address {}", address)
+            }
+            VmError::MemoryLimit { address, limit } => write!(
+                f,
+                "write to address {} exceeds the memory limit of {} words",
+                address, limit
+            ),
         }
     }
 }
 
-impl Mode {
-    fn get_next_3(vm: &IntcodeVM) -> [Mode; 3] {
-        let mut modes = [Mode::Position; 3];
-        for (i, c) in vm.memory[&vm.instruction_ptr]
-            .to_string()
-            .chars()
-            .rev()
-            .skip(2)
-            .enumerate()
-        {
-            if i > 2 {
-                panic!("Unsupported number of operands");
-            }
-
-            modes[i] = Mode::from(c.to_digit(10).unwrap());
-        }
+impl std::error::Error for VmError {}
 
-        modes
-    }
+#[derive(Debug, Clone, PartialEq)]
+pub enum VMStatus {
+    Ok,
+    EmptyInputBuffer,
+    Halted,
+    Faulted,
+    // run_for or run_until stopped the VM, it can be resumed with any of the run functions
+    StepLimitReached,
+}
 
-    fn get_value(
+pub trait Engine {
+    // Executes a single instruction
+    fn step<M: Memory, I: InputDevice + ?Sized, O: OutputDevice + ?Sized>(
         &self,
-        program: &HashMap<usize, i64>,
-        instruction_ptr: usize,
-        relative_base: i64,
-    ) -> i64 {
-        match self {
-            Mode::Immediate => program[&instruction_ptr],
-            Mode::Position => {
-                let t = program[&instruction_ptr] as usize;
-                *program.get(&t).unwrap_or(&0)
-            }
-            Mode::Relative => {
-                let t = (program[&instruction_ptr] + relative_base) as usize;
-                *program.get(&t).unwrap_or(&0)
-            }
-        }
-    }
+        vm: &mut IntcodeVM<M>,
+        input: &mut I,
+        output: &mut O,
+    ) -> Result<VMStatus, VmError>;
+}
+
+// The default engine, executes instructions decoded according to the VM's Dispatch setting
+#[derive(Debug, Copy, Clone)]
+pub struct Interpreter;
 
-    fn get_addr(
+impl Engine for Interpreter {
+    fn step<M: Memory, I: InputDevice + ?Sized, O: OutputDevice + ?Sized>(
         &self,
-        program: &HashMap<usize, i64>,
-        instruction_ptr: usize,
-        relative_base: i64,
-    ) -> usize {
-        match self {
-            Mode::Immediate => panic!("Invalid mode!"),
-            Mode::Position => program[&instruction_ptr] as usize,
-            Mode::Relative => (program[&instruction_ptr] + relative_base) as usize,
-        }
+        vm: &mut IntcodeVM<M>,
+        input: &mut I,
+        output: &mut O,
+    ) -> Result<VMStatus, VmError> {
+        vm.execute(input, output)
     }
 }
 
-trait Instruction {
-    fn execute(
-        &self,
-        vm: &mut IntcodeVM,
-        input_buffer: &mut VecDeque<i64>,
-        output_buffer: &mut VecDeque<i64>,
-    ) -> VMStatus;
-    fn get_len(&self) -> usize;
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub enum EngineKind {
+    Interpreter,
+    Legacy,
 }
 
-struct Add {
-    lhs: i64,
-    rhs: i64,
-    dest: usize,
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub enum Dispatch {
+    Decode,
+    // Decode each address once and reuse the result until the address is written to
+    Cached,
 }
 
-impl Add {
-    fn new(vm: &IntcodeVM) -> Self {
-        let p = &vm.memory;
-        let ip = vm.instruction_ptr;
+// Addresses past this are never cached, programs are a few thousand words
+const DECODE_CACHE_LIMIT: usize = 1 << 16;
 
-        let modes = Mode::get_next_3(vm);
-        let lhs = modes[0].get_value(p, ip + 1, vm.relative_base);
-        let rhs = modes[1].get_value(p, ip + 2, vm.relative_base);
-        let dest = modes[2].get_addr(p, ip + 3, vm.relative_base);
+#[derive(Debug, Clone)]
+pub struct IntcodeVM<M = VecMemory> {
+    instruction_ptr: usize,
+    relative_base: i64,
+    status: VMStatus,
+    fault: Option<VmError>,
+    steps: u64,
+    engine: EngineKind,
+    dispatch: Dispatch,
+    decode_cache: Vec<Option<Op>>,
+    memory: M,
+}
 
-        Add { lhs, rhs, dest }
+impl IntcodeVM {
+    pub fn new(program: &[i64]) -> IntcodeVM {
+        IntcodeVM::with_memory(VecMemory::new(program))
     }
 }
 
-impl Instruction for Add {
-    fn execute(
-        &self,
-        vm: &mut IntcodeVM,
-        _input_buffer: &mut VecDeque<i64>,
-        _output_buffer: &mut VecDeque<i64>,
-    ) -> VMStatus {
-        vm.memory.insert(self.dest, self.lhs + self.rhs);
-
-        if self.dest != vm.instruction_ptr {
-            vm.instruction_ptr += self.get_len();
+impl<M: Memory> IntcodeVM<M> {
+    pub fn with_memory(memory: M) -> IntcodeVM<M> {
+        IntcodeVM {
+            instruction_ptr: 0,
+            relative_base: 0,
+            status: VMStatus::Ok,
+            fault: None,
+            steps: 0,
+            engine: EngineKind::Interpreter,
+            dispatch: Dispatch::Decode,
+            decode_cache: Vec::new(),
+            memory,
         }
+    }
 
-        VMStatus::Ok
+    pub fn instruction_ptr(&self) -> usize {
+        self.instruction_ptr
     }
 
-    fn get_len(&self) -> usize {
-        4
+    pub fn relative_base(&self) -> i64 {
+        self.relative_base
     }
-}
 
-struct Mult {
-    lhs: i64,
-    rhs: i64,
-    dest: usize,
-}
+    pub fn set_relative_base(&mut self, relative_base: i64) {
+        self.relative_base = relative_base;
+    }
+
+    pub fn status(&self) -> &VMStatus {
+        &self.status
+    }
 
-impl Mult {
-    fn new(vm: &IntcodeVM) -> Self {
-        let p = &vm.memory;
-        let ip = vm.instruction_ptr;
+    // The error that put the VM into the Faulted state, if any
+    pub fn fault(&self) -> Option<&VmError> {
+        self.fault.as_ref()
+    }
 
-        let modes = Mode::get_next_3(vm);
-        let lhs = modes[0].get_value(p, ip + 1, vm.relative_base);
-        let rhs = modes[1].get_value(p, ip + 2, vm.relative_base);
-        let dest = modes[2].get_addr(p, ip + 3, vm.relative_base);
+    // Instructions executed so far, not counting halts or waits for input
+    pub fn steps(&self) -> u64 {
+        self.steps
+    }
 
-        Mult { lhs, rhs, dest }
+    pub fn memory(&self) -> &M {
+        &self.memory
     }
-}
 
-impl Instruction for Mult {
-    fn execute(
-        &self,
-        vm: &mut IntcodeVM,
-        _input_buffer: &mut VecDeque<i64>,
-        _output_buffer: &mut VecDeque<i64>,
-    ) -> VMStatus {
-        vm.memory.insert(self.dest, self.lhs * self.rhs);
-
-        if self.dest != vm.instruction_ptr {
-            vm.instruction_ptr += self.get_len();
-        }
+    pub fn set_engine(&mut self, engine: EngineKind) {
+        self.engine = engine;
+    }
 
-        VMStatus::Ok
+    pub fn engine(&self) -> EngineKind {
+        self.engine
     }
 
-    fn get_len(&self) -> usize {
-        4
+    // Only affects the Interpreter engine
+    pub fn set_dispatch(&mut self, dispatch: Dispatch) {
+        self.dispatch = dispatch;
+        self.decode_cache.clear();
     }
-}
 
-struct LessThan {
-    lhs: i64,
-    rhs: i64,
-    dest: usize,
-}
+    pub fn write(&mut self, address: usize, value: i64) -> Result<(), VmError> {
+        if let Some(entry) = self.decode_cache.get_mut(address) {
+            *entry = None;
+        }
+
+        self.memory.write(address, value)
+    }
+
+    fn fetch(&mut self) -> Result<Op, VmError> {
+        let ip = self.instruction_ptr;
+
+        match self.dispatch {
+            Dispatch::Decode => decode(self.memory.read(ip), ip),
+            Dispatch::Cached => {
+                if let Some(Some(op)) = self.decode_cache.get(ip) {
+                    return Ok(*op);
+                }
 
-impl LessThan {
-    fn new(vm: &IntcodeVM) -> Self {
-        let p = &vm.memory;
-        let ip = vm.instruction_ptr;
+                let op = decode(self.memory.read(ip), ip)?;
 
-        let modes = Mode::get_next_3(vm);
-        let lhs = modes[0].get_value(p, ip + 1, vm.relative_base);
-        let rhs = modes[1].get_value(p, ip + 2, vm.relative_base);
-        let dest = modes[2].get_addr(p, ip + 3, vm.relative_base);
+                // The cache is dense, so code far out in sparse memory runs uncached rather
+                // than allocating an entry for every address below it
+                if ip >= DECODE_CACHE_LIMIT {
+                    return Ok(op);
+                }
 
-        LessThan { lhs, rhs, dest }
+                if ip >= self.decode_cache.len() {
+                    self.decode_cache.resize(ip + 1, None);
+                }
+
+                self.decode_cache[ip] = Some(op);
+
+                Ok(op)
+            }
+        }
     }
-}
 
-impl Instruction for LessThan {
-    fn execute(
-        &self,
-        vm: &mut IntcodeVM,
-        _input_buffer: &mut VecDeque<i64>,
-        _output_buffer: &mut VecDeque<i64>,
-    ) -> VMStatus {
-        let res = match self.lhs < self.rhs {
-            true => 1,
-            false => 0,
+    fn get_addr(&self, mode: Mode, address: usize) -> Result<usize, VmError> {
+        let target = match mode {
+            Mode::Immediate => return Err(VmError::ImmediateWrite { address }),
+            Mode::Position => self.memory.read(address),
+            Mode::Relative => self
+                .memory
+                .read(address)
+                .checked_add(self.relative_base)
+                .ok_or(VmError::Overflow { address })?,
         };
 
-        vm.memory.insert(self.dest, res);
-
-        if self.dest != vm.instruction_ptr {
-            vm.instruction_ptr += self.get_len();
+        if target < 0 {
+            return Err(VmError::NegativeAddress { target, address });
         }
 
-        VMStatus::Ok
+        Ok(target as usize)
     }
 
-    fn get_len(&self) -> usize {
-        4
+    fn get_value(&self, mode: Mode, address: usize) -> Result<i64, VmError> {
+        match mode {
+            Mode::Immediate => Ok(self.memory.read(address)),
+            _ => Ok(self.memory.read(self.get_addr(mode, address)?)),
+        }
     }
-}
-
-struct Equals {
-    lhs: i64,
-    rhs: i64,
-    dest: usize,
-}
 
-impl Equals {
-    fn new(vm: &IntcodeVM) -> Self {
-        let p = &vm.memory;
-        let ip = vm.instruction_ptr;
+    fn jump(&mut self, target: i64) -> Result<(), VmError> {
+        if target < 0 {
+            return Err(VmError::NegativeAddress {
+                target,
+                address: self.instruction_ptr,
+            });
+        }
 
-        let modes = Mode::get_next_3(vm);
-        let lhs = modes[0].get_value(p, ip + 1, vm.relative_base);
-        let rhs = modes[1].get_value(p, ip + 2, vm.relative_base);
-        let dest = modes[2].get_addr(p, ip + 3, vm.relative_base);
+        self.instruction_ptr = target as usize;
 
-        Equals { lhs, rhs, dest }
+        Ok(())
     }
-}
 
-impl Instruction for Equals {
-    fn execute(
-        &self,
-        vm: &mut IntcodeVM,
-        _input_buffer: &mut VecDeque<i64>,
-        _output_buffer: &mut VecDeque<i64>,
-    ) -> VMStatus {
-        let res = match self.lhs == self.rhs {
-            true => 1,
-            false => 0,
-        };
-
-        vm.memory.insert(self.dest, res);
+    // Instructions that overwrite their own opcode are executed again
+    fn store(&mut self, dest: usize, value: i64, len: usize) -> Result<(), VmError> {
+        self.write(dest, value)?;
 
-        if self.dest != vm.instruction_ptr {
-            vm.instruction_ptr += self.get_len();
+        if dest != self.instruction_ptr {
+            self.instruction_ptr += len;
         }
 
-        VMStatus::Ok
+        Ok(())
     }
 
-    fn get_len(&self) -> usize {
-        4
-    }
-}
+    fn step<I: InputDevice + ?Sized, O: OutputDevice + ?Sized>(
+        &mut self,
+        input: &mut I,
+        output: &mut O,
+    ) -> Result<VMStatus, VmError> {
+        let status = match self.engine {
+            EngineKind::Interpreter => Interpreter.step(self, input, output),
+            EngineKind::Legacy => LegacyInterpreter.step(self, input, output),
+        }?;
 
-struct Input {
-    dest: usize,
-}
+        if status == VMStatus::Ok {
+            self.steps += 1;
+        }
 
-impl Input {
-    fn new(vm: &IntcodeVM) -> Self {
-        let p = &vm.memory;
-        let ip = vm.instruction_ptr;
+        Ok(status)
+    }
 
-        let modes = Mode::get_next_3(vm);
-        let dest = modes[0].get_addr(p, ip + 1, vm.relative_base);
+    fn execute<I: InputDevice + ?Sized, O: OutputDevice + ?Sized>(
+        &mut self,
+        input: &mut I,
+        output: &mut O,
+    ) -> Result<VMStatus, VmError> {
+        let ip = self.instruction_ptr;
+        let op = self.fetch()?;
+        let len = op.get_len();
 
-        Input { dest }
-    }
-}
+        match op {
+            Op::Add(a, b, c) | Op::Mult(a, b, c) | Op::LessThan(a, b, c) | Op::Equals(a, b, c) => {
+                let lhs = self.get_value(a, ip + 1)?;
+                let rhs = self.get_value(b, ip + 2)?;
+                let dest = self.get_addr(c, ip + 3)?;
 
-impl Instruction for Input {
-    fn execute(
-        &self,
-        vm: &mut IntcodeVM,
-        input_buffer: &mut VecDeque<i64>,
-        _output_buffer: &mut VecDeque<i64>,
-    ) -> VMStatus {
-        if input_buffer.is_empty() {
-            VMStatus::EmptyInputBuffer
-        } else {
-            vm.memory.insert(self.dest, input_buffer.pop_front().unwrap());
-
-            if self.dest != vm.instruction_ptr {
-                vm.instruction_ptr += self.get_len();
+                let res = match op {
+                    Op::Add(..) => lhs.checked_add(rhs),
+                    Op::Mult(..) => lhs.checked_mul(rhs),
+                    Op::LessThan(..) => Some((lhs < rhs) as i64),
+                    _ => Some((lhs == rhs) as i64),
+                };
+
+                self.store(dest, res.ok_or(VmError::Overflow { address: ip })?, len)?;
             }
+            Op::Input(a) => {
+                let dest = self.get_addr(a, ip + 1)?;
 
-            VMStatus::Ok
+                match input.input() {
+                    Some(value) => self.store(dest, value, len)?,
+                    None => return Ok(VMStatus::EmptyInputBuffer),
+                }
+            }
+            Op::Output(a) => {
+                output.output(self.get_value(a, ip + 1)?);
+                self.instruction_ptr += len;
+            }
+            Op::JumpIfTrue(a, b) | Op::JumpIfFalse(a, b) => {
+                let operand = self.get_value(a, ip + 1)?;
+                let target = self.get_value(b, ip + 2)?;
+
+                match (op, operand) {
+                    (Op::JumpIfTrue(..), 0) => self.instruction_ptr += len,
+                    (Op::JumpIfTrue(..), _) => self.jump(target)?,
+                    (_, 0) => self.jump(target)?,
+                    (_, _) => self.instruction_ptr += len,
+                }
+            }
+            Op::UpdateRelativeBase(a) => {
+                let offset = self.get_value(a, ip + 1)?;
+
+                self.relative_base = self
+                    .relative_base
+                    .checked_add(offset)
+                    .ok_or(VmError::Overflow { address: ip })?;
+                self.instruction_ptr += len;
+            }
+            Op::Halt => return Ok(VMStatus::Halted),
         }
-    }
 
-    fn get_len(&self) -> usize {
-        2
+        Ok(VMStatus::Ok)
     }
 }
 
-struct Output {
-    val: i64,
+// Records a finished step's status, or its fault, on the VM
+fn settle<M: Memory>(
+    vm: &mut IntcodeVM<M>,
+    result: Result<VMStatus, VmError>,
+) -> Result<VMStatus, VmError> {
+    match result {
+        Ok(status) => {
+            vm.status = status.clone();
+            Ok(status)
+        }
+        Err(e) => {
+            vm.status = VMStatus::Faulted;
+            vm.fault = Some(e.clone());
+            Err(e)
+        }
+    }
 }
 
-impl Output {
-    fn new(vm: &IntcodeVM) -> Self {
-        let p = &vm.memory;
-        let ip = vm.instruction_ptr;
+// Executes a single instruction, returning VMStatus::Ok if the VM can continue
+pub fn step<M: Memory, I: InputDevice + ?Sized, O: OutputDevice + ?Sized>(
+    vm: &mut IntcodeVM<M>,
+    input: &mut I,
+    output: &mut O,
+) -> Result<VMStatus, VmError> {
+    if let Some(fault) = &vm.fault {
+        return Err(fault.clone());
+    }
 
-        let modes = Mode::get_next_3(vm);
-        let val = modes[0].get_value(p, ip + 1, vm.relative_base);
+    let result = vm.step(input, output);
+    settle(vm, result)
+}
 
-        Output { val }
+pub fn run<M: Memory, I: InputDevice + ?Sized, O: OutputDevice + ?Sized>(
+    vm: &mut IntcodeVM<M>,
+    input: &mut I,
+    output: &mut O,
+) -> Result<VMStatus, VmError> {
+    if let Some(fault) = &vm.fault {
+        return Err(fault.clone());
+    }
+
+    loop {
+        match vm.step(input, output) {
+            Ok(VMStatus::Ok) => continue,
+            result => return settle(vm, result),
+        }
     }
 }
 
-impl Instruction for Output {
-    fn execute(
-        &self,
-        vm: &mut IntcodeVM,
-        _input_buffer: &mut VecDeque<i64>,
-        output_buffer: &mut VecDeque<i64>,
-    ) -> VMStatus {
-        vm.instruction_ptr += self.get_len();
+// Like run, but checks the predicate before each instruction and stops with
+// VMStatus::StepLimitReached once it holds
+pub fn run_until<M, I, O, F>(
+    vm: &mut IntcodeVM<M>,
+    input: &mut I,
+    output: &mut O,
+    mut predicate: F,
+) -> Result<VMStatus, VmError>
+where
+    M: Memory,
+    I: InputDevice + ?Sized,
+    O: OutputDevice + ?Sized,
+    F: FnMut(&IntcodeVM<M>) -> bool,
+{
+    if let Some(fault) = &vm.fault {
+        return Err(fault.clone());
+    }
 
-        output_buffer.push_back(self.val);
+    loop {
+        if predicate(vm) {
+            vm.status = VMStatus::StepLimitReached;
+            return Ok(VMStatus::StepLimitReached);
+        }
 
-        VMStatus::Ok
+        match vm.step(input, output) {
+            Ok(VMStatus::Ok) => continue,
+            result => return settle(vm, result),
+        }
     }
+}
 
-    fn get_len(&self) -> usize {
-        2
-    }
+// Like run, but executes at most the given number of instructions
+pub fn run_for<M: Memory, I: InputDevice + ?Sized, O: OutputDevice + ?Sized>(
+    vm: &mut IntcodeVM<M>,
+    input: &mut I,
+    output: &mut O,
+    steps: u64,
+) -> Result<VMStatus, VmError> {
+    let limit = vm.steps.saturating_add(steps);
+
+    run_until(vm, input, output, |vm| vm.steps >= limit)
 }
 
-struct JumpIfTrue {
-    operand: i64,
-    dest: usize,
+pub fn parse_program(input: &str) -> Result<Vec<i64>, ParseIntError> {
+    input
+        .trim()
+        .split(',')
+        .map(|x| x.trim().parse::<i64>())
+        .collect()
 }
 
-impl JumpIfTrue {
-    fn new(vm: &IntcodeVM) -> Self {
-        let p = &vm.memory;
-        let ip = vm.instruction_ptr;
+pub fn encode_ascii_v(input: &[String]) -> Vec<Vec<i64>> {
+    input.iter().map(|s| encode_ascii(s)).collect()
+}
 
-        let modes = Mode::get_next_3(vm);
-        let operand = modes[0].get_value(p, ip + 1, vm.relative_base);
-        let dest = modes[1].get_value(p, ip + 2, vm.relative_base) as usize;
+pub fn encode_ascii(input: &str) -> Vec<i64> {
+    input.trim_start().chars().map(|c| c as i64).collect()
+}
 
-        JumpIfTrue { operand, dest }
-    }
+pub fn decode_ascii_v(input: &[Vec<i64>]) -> Vec<String> {
+    input.iter().map(|w| decode_ascii(w)).collect()
 }
 
-impl Instruction for JumpIfTrue {
-    fn execute(
-        &self,
-        vm: &mut IntcodeVM,
-        _input_buffer: &mut VecDeque<i64>,
-        _output_buffer: &mut VecDeque<i64>,
-    ) -> VMStatus {
-        match self.operand {
-            0 => vm.instruction_ptr += self.get_len(),
-            _ => vm.instruction_ptr = self.dest,
-        }
+pub fn decode_ascii(input: &[i64]) -> String {
+    input.iter().map(|c| *c as u8 as char).collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::VecDeque;
+
+    fn run_program(program: &[i64], input: &[i64]) -> (IntcodeVM, Result<VMStatus, VmError>) {
+        let mut vm = IntcodeVM::new(program);
+        let mut input_buffer: VecDeque<i64> = input.iter().copied().collect();
+        let mut output_buffer = VecDeque::new();
 
-        VMStatus::Ok
+        let result = run(&mut vm, &mut input_buffer, &mut output_buffer);
+
+        (vm, result)
     }
 
-    fn get_len(&self) -> usize {
-        3
+    #[test]
+    fn halts_normally() {
+        let (vm, result) = run_program(&[1101, 2, 3, 5, 99, 0], &[]);
+
+        assert_eq!(result, Ok(VMStatus::Halted));
+        assert_eq!(vm.memory().read(5), 5);
+        assert_eq!(vm.fault(), None);
     }
-}
 
-struct JumpIfFalse {
-    operand: i64,
-    dest: usize,
-}
+    #[test]
+    fn unknown_opcode() {
+        let (vm, result) = run_program(&[1101, 2, 3, 5, 42], &[]);
 
-impl JumpIfFalse {
-    fn new(vm: &IntcodeVM) -> Self {
-        let p = &vm.memory;
-        let ip = vm.instruction_ptr;
+        assert_eq!(
+            result,
+            Err(VmError::UnknownOpcode {
+                opcode: 42,
+                address: 4
+            })
+        );
+        assert_eq!(vm.status(), &VMStatus::Faulted);
+        assert_eq!(vm.instruction_ptr(), 4);
+    }
 
-        let modes = Mode::get_next_3(vm);
-        let operand = modes[0].get_value(p, ip + 1, vm.relative_base);
-        let dest = modes[1].get_value(p, ip + 2, vm.relative_base) as usize;
+    #[test]
+    fn running_off_the_end_is_an_unknown_opcode() {
+        let (_, result) = run_program(&[1101, 2, 3, 5], &[]);
 
-        JumpIfFalse { operand, dest }
+        assert_eq!(
+            result,
+            Err(VmError::UnknownOpcode {
+                opcode: 0,
+                address: 4
+            })
+        );
     }
-}
 
-impl Instruction for JumpIfFalse {
-    fn execute(
-        &self,
-        vm: &mut IntcodeVM,
-        _input_buffer: &mut VecDeque<i64>,
-        _output_buffer: &mut VecDeque<i64>,
-    ) -> VMStatus {
-        match self.operand {
-            0 => vm.instruction_ptr = self.dest,
-            _ => vm.instruction_ptr += self.get_len(),
-        }
+    #[test]
+    fn invalid_mode() {
+        let (_, result) = run_program(&[1301, 2, 3, 5, 99], &[]);
 
-        VMStatus::Ok
+        assert_eq!(
+            result,
+            Err(VmError::InvalidMode {
+                digit: 3,
+                address: 0
+            })
+        );
     }
 
-    fn get_len(&self) -> usize {
-        3
+    #[test]
+    fn immediate_write() {
+        let (_, result) = run_program(&[11101, 2, 3, 5, 99], &[]);
+
+        assert_eq!(result, Err(VmError::ImmediateWrite { address: 3 }));
     }
-}
 
-struct UpdateRelativeBase {
-    offset: i64,
-}
+    #[test]
+    fn negative_address() {
+        let (_, result) = run_program(&[4, -1, 99], &[]);
 
-impl UpdateRelativeBase {
-    fn new(vm: &IntcodeVM) -> Self {
-        let p = &vm.memory;
-        let ip = vm.instruction_ptr;
+        assert_eq!(
+            result,
+            Err(VmError::NegativeAddress {
+                target: -1,
+                address: 1
+            })
+        );
 
-        let modes = Mode::get_next_3(vm);
-        let offset = modes[0].get_value(p, ip + 1, vm.relative_base);
+        let (_, result) = run_program(&[1105, 1, -7, 99], &[]);
 
-        UpdateRelativeBase { offset }
+        assert_eq!(
+            result,
+            Err(VmError::NegativeAddress {
+                target: -7,
+                address: 0
+            })
+        );
     }
-}
 
-impl Instruction for UpdateRelativeBase {
-    fn execute(
-        &self,
-        vm: &mut IntcodeVM,
-        _input_buffer: &mut VecDeque<i64>,
-        _output_buffer: &mut VecDeque<i64>,
-    ) -> VMStatus {
-        vm.instruction_ptr += self.get_len();
-        vm.relative_base += self.offset;
+    #[test]
+    fn overflow() {
+        let (_, result) = run_program(&[1102, i64::MAX, 2, 5, 99, 0], &[]);
 
-        VMStatus::Ok
+        assert_eq!(result, Err(VmError::Overflow { address: 0 }));
     }
 
-    fn get_len(&self) -> usize {
-        2
+    #[test]
+    fn faulted_vm_keeps_failing() {
+        let (mut vm, result) = run_program(&[42], &[]);
+        let mut input_buffer = VecDeque::new();
+        let mut output_buffer = VecDeque::new();
+
+        assert!(result.is_err());
+        assert_eq!(run(&mut vm, &mut input_buffer, &mut output_buffer), result);
     }
-}
 
-struct Halt {}
+    #[test]
+    fn decode_cache_is_invalidated_by_writes() {
+        // Outputs 7, then overwrites the output instruction with a halt and jumps back to it
+        let program = [
+            1105, 1, 10, 1101, 0, 99, 10, 1105, 1, 10, 104, 7, 1105, 1, 3,
+        ];
+        let mut vm = IntcodeVM::new(&program);
+        let mut input_buffer = VecDeque::new();
+        let mut output_buffer = VecDeque::new();
 
-impl Halt {
-    fn new(_vm: &IntcodeVM) -> Self {
-        Halt {}
-    }
-}
+        vm.set_dispatch(Dispatch::Cached);
+
+        let result = run(&mut vm, &mut input_buffer, &mut output_buffer);
+
+        assert_eq!(result, Ok(VMStatus::Halted));
+        assert_eq!(output_buffer, vec![7]);
+    }
+
+    #[test]
+    fn decode_cache_skips_far_addresses() {
+        // Writes an output instruction and a halt far away, then jumps to them
+        let far = 1 << 40;
+        let program = [1101, 104, 0, far, 1101, 7, 0, far + 1, 1101, 99, 0, far + 2, 1105, 1, far];
+        let mut vm = IntcodeVM::with_memory(PagedMemory::new(&program));
+        let mut input_buffer = VecDeque::new();
+        let mut output_buffer = VecDeque::new();
 
-impl Instruction for Halt {
-    fn execute(
-        &self,
-        _vm: &mut IntcodeVM,
-        _input_buffer: &mut VecDeque<i64>,
-        _output_buffer: &mut VecDeque<i64>,
-    ) -> VMStatus {
-        VMStatus::Halted
-    }
+        vm.set_dispatch(Dispatch::Cached);
+
+        let result = run(&mut vm, &mut input_buffer, &mut output_buffer);
 
-    fn get_len(&self) -> usize {
-        1
+        assert_eq!(result, Ok(VMStatus::Halted));
+        assert_eq!(output_buffer, vec![7]);
+        assert!(vm.decode_cache.len() <= program.len());
     }
-}
 
-impl dyn Instruction {
-    fn next(vm: &mut IntcodeVM) -> Box<dyn Instruction> {
-        let p = &vm.memory;
-        let ip = vm.instruction_ptr;
-        let instruction: Box<dyn Instruction>;
-
-        match p[&ip]
-            .to_string()
-            .chars()
-            .rev()
-            .take(2)
-            .collect::<String>()
-            .chars()
-            .rev()
-            .collect::<String>()
-            .parse::<isize>()
-        {
-            Ok(opcode) => match opcode {
-                1 => instruction = Box::new(Add::new(vm)),
-                2 => instruction = Box::new(Mult::new(vm)),
-                3 => instruction = Box::new(Input::new(vm)),
-                4 => instruction = Box::new(Output::new(vm)),
-                5 => instruction = Box::new(JumpIfTrue::new(vm)),
-                6 => instruction = Box::new(JumpIfFalse::new(vm)),
-                7 => instruction = Box::new(LessThan::new(vm)),
-                8 => instruction = Box::new(Equals::new(vm)),
-                9 => instruction = Box::new(UpdateRelativeBase::new(vm)),
-                99 => instruction = Box::new(Halt::new(vm)),
-                _ => panic!("Unknown opcode: {}", opcode),
-            },
-            _ => panic!("Invalid opcode"),
-        }
+    #[test]
+    fn paged_memory_with_large_relative_base() {
+        // Store the input far away via the relative base and echo it back
+        let program = [109, 1 << 40, 203, 0, 204, 0, 99];
+        let mut vm = IntcodeVM::with_memory(PagedMemory::new(&program));
+        let mut input_buffer: VecDeque<i64> = vec![17].into_iter().collect();
+        let mut output_buffer = VecDeque::new();
+
+        let result = run(&mut vm, &mut input_buffer, &mut output_buffer);
 
-        instruction
+        assert_eq!(result, Ok(VMStatus::Halted));
+        assert_eq!(output_buffer.pop_front(), Some(17));
+        assert_eq!(vm.memory().read(1 << 40), 17);
     }
-}
 
-#[derive(Debug, Clone, PartialEq)]
-pub enum VMStatus {
-    Ok,
-    EmptyInputBuffer,
-    Halted,
-}
+    #[test]
+    fn memory_limit() {
+        let program = [1101, 1, 1, 100, 99];
+        let mut vm = IntcodeVM::with_memory(VecMemory::with_limit(&program, 64));
+        let mut input_buffer = VecDeque::new();
+        let mut output_buffer = VecDeque::new();
 
-#[derive(Debug, Clone)]
-pub struct IntcodeVM {
-    instruction_ptr: usize,
-    relative_base: i64,
-    pub memory: HashMap<usize, i64>,
-}
+        let result = run(&mut vm, &mut input_buffer, &mut output_buffer);
 
-impl IntcodeVM {
-    pub fn new(program: &Vec<i64>) -> IntcodeVM {
-        IntcodeVM {
-            instruction_ptr: 0,
-            relative_base: 0,
-            memory: program
-                .iter()
-                .enumerate()
-                .map(|(i, x)| (i, x.clone()))
-                .collect(),
-        }
+        assert_eq!(
+            result,
+            Err(VmError::MemoryLimit {
+                address: 100,
+                limit: 64
+            })
+        );
+        assert_eq!(vm.status(), &VMStatus::Faulted);
     }
-}
 
-pub fn run(
-    vm: &mut IntcodeVM,
-    input_buffer: &mut VecDeque<i64>,
-    output_buffer: &mut VecDeque<i64>,
-) -> VMStatus {
-    loop {
-        let instr = Instruction::next(vm);
-        let status = instr.execute(vm, input_buffer, output_buffer);
-        if let VMStatus::Ok = status {
-            continue;
-        }
+    #[test]
+    fn run_for_stops_infinite_loops() {
+        let mut vm = IntcodeVM::new(&[1105, 1, 0]);
+        let mut input_buffer = VecDeque::new();
+        let mut output_buffer = VecDeque::new();
+
+        assert_eq!(
+            run_for(&mut vm, &mut input_buffer, &mut output_buffer, 1000),
+            Ok(VMStatus::StepLimitReached)
+        );
+        assert_eq!(vm.steps(), 1000);
+        assert_eq!(vm.status(), &VMStatus::StepLimitReached);
 
-        return status;
+        assert_eq!(
+            run_for(&mut vm, &mut input_buffer, &mut output_buffer, 5),
+            Ok(VMStatus::StepLimitReached)
+        );
+        assert_eq!(vm.steps(), 1005);
     }
-}
 
-pub fn encode_ascii_v(input: &Vec<String>) -> Vec<Vec<i64>> {
-    input.iter().map(|s| encode_ascii(s)).collect()
-}
+    #[test]
+    fn run_until_predicate() {
+        // Counts up from 0 at address 9, printing every value
+        let mut vm = IntcodeVM::new(&[4, 9, 1001, 9, 1, 9, 1105, 1, 0, 0]);
+        let mut input_buffer = VecDeque::new();
+        let mut output_buffer = VecDeque::new();
 
-pub fn encode_ascii(input: &str) -> Vec<i64> {
-    input.trim_start().chars().map(|c| c as i64).collect()
-}
+        assert_eq!(
+            run_until(&mut vm, &mut input_buffer, &mut output_buffer, |vm| vm
+                .memory()
+                .read(9)
+                == 3),
+            Ok(VMStatus::StepLimitReached)
+        );
+        assert_eq!(output_buffer, vec![0, 1, 2]);
 
-pub fn decode_ascii_v(input: &Vec<Vec<i64>>) -> Vec<String> {
-    input.iter().map(|w| decode_ascii(w)).collect()
-}
+        // Halting or waiting for input within the budget is reported as usual
+        let mut vm = IntcodeVM::new(&[3, 0, 99]);
 
-pub fn decode_ascii(input: &Vec<i64>) -> String {
-    input.iter().map(|c| *c as u8 as char).collect()
-}
\ No newline at end of file
+        assert_eq!(
+            run_for(&mut vm, &mut input_buffer, &mut output_buffer, 10),
+            Ok(VMStatus::EmptyInputBuffer)
+        );
+
+        input_buffer.push_back(99);
+
+        assert_eq!(
+            run_for(&mut vm, &mut input_buffer, &mut output_buffer, 10),
+            Ok(VMStatus::Halted)
+        );
+        assert_eq!(vm.steps(), 1);
+    }
+}
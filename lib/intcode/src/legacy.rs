@@ -0,0 +1,264 @@
+use crate::{Engine, InputDevice, IntcodeVM, Memory, OutputDevice, VMStatus, VmError};
+
+#[derive(Debug, Copy, Clone)]
+enum Mode {
+    Immediate,
+    Position,
+    Relative,
+}
+
+impl Mode {
+    fn from_digit(digit: i64, address: usize) -> Result<Mode, VmError> {
+        match digit {
+            0 => Ok(Mode::Position),
+            1 => Ok(Mode::Immediate),
+            2 => Ok(Mode::Relative),
+            _ => Err(VmError::InvalidMode { digit, address }),
+        }
+    }
+}
+
+#[derive(Debug, Copy, Clone)]
+enum Op {
+    UpdateRelativeBase(i64),
+    JumpIfTrue(i64),
+    JumpIfFalse(i64),
+    LessThan(i64, i64),
+    Equals(i64, i64),
+    Mult(i64, i64),
+    Add(i64, i64),
+    Input,
+    Output,
+    Halt,
+}
+
+#[derive(Debug, Copy, Clone)]
+enum Target {
+    Addr(usize, Op),
+    Value(i64, Op),
+    Jump(i64, Op),
+    UpdateState(Op),
+}
+
+impl Op {
+    fn get_len(&self) -> usize {
+        match self {
+            Op::UpdateRelativeBase(_) => 2,
+            Op::JumpIfTrue(_) => 3,
+            Op::JumpIfFalse(_) => 3,
+            Op::LessThan(_, _) => 4,
+            Op::Equals(_, _) => 4,
+            Op::Mult(_, _) => 4,
+            Op::Add(_, _) => 4,
+            Op::Input => 2,
+            Op::Output => 2,
+            Op::Halt => 1,
+        }
+    }
+}
+
+impl Target {
+    fn get_len(&self) -> usize {
+        match self {
+            Target::Addr(_, op) => op.get_len(),
+            Target::Value(_, op) => op.get_len(),
+            Target::Jump(_, op) => op.get_len(),
+            Target::UpdateState(op) => op.get_len(),
+        }
+    }
+}
+
+impl Mode {
+    fn get_value<M: Memory>(
+        &self,
+        program: &M,
+        stackptr: usize,
+        relative_base: i64,
+    ) -> Result<i64, VmError> {
+        match self {
+            Mode::Immediate => Ok(program.read(stackptr)),
+            _ => Ok(program.read(self.get_addr(program, stackptr, relative_base)?)),
+        }
+    }
+
+    fn get_addr<M: Memory>(
+        &self,
+        program: &M,
+        stackptr: usize,
+        relative_base: i64,
+    ) -> Result<usize, VmError> {
+        let t = match self {
+            Mode::Immediate => return Err(VmError::ImmediateWrite { address: stackptr }),
+            Mode::Position => program.read(stackptr),
+            Mode::Relative => program
+                .read(stackptr)
+                .checked_add(relative_base)
+                .ok_or(VmError::Overflow { address: stackptr })?,
+        };
+
+        if t < 0 {
+            return Err(VmError::NegativeAddress {
+                target: t,
+                address: stackptr,
+            });
+        }
+
+        Ok(t as usize)
+    }
+}
+
+impl Target {
+    fn next<M: Memory>(state: &IntcodeVM<M>) -> Result<Target, VmError> {
+        let p = &state.memory;
+        let sp = state.instruction_ptr;
+        let word = p.read(sp);
+
+        if word < 0 {
+            return Err(VmError::UnknownOpcode {
+                opcode: word,
+                address: sp,
+            });
+        }
+
+        let mut modes = [Mode::Position; 3];
+        for (i, c) in word.to_string().chars().rev().skip(2).enumerate() {
+            let digit = c.to_digit(10).map(i64::from).unwrap_or(-1);
+
+            if i > 2 {
+                return Err(VmError::InvalidMode { digit, address: sp });
+            }
+
+            modes[i] = Mode::from_digit(digit, sp)?;
+        }
+
+        let opcode = word % 100;
+
+        let op = match opcode {
+            1 | 2 | 7 | 8 => {
+                if let Mode::Immediate = modes[2] {
+                    return Err(VmError::ImmediateWrite { address: sp + 3 });
+                }
+
+                let a = modes[0].get_value(p, sp + 1, state.relative_base)?;
+                let b = modes[1].get_value(p, sp + 2, state.relative_base)?;
+                let t = modes[2].get_addr(p, sp + 3, state.relative_base)?;
+
+                match opcode {
+                    1 => Target::Addr(t, Op::Add(a, b)),
+                    2 => Target::Addr(t, Op::Mult(a, b)),
+                    7 => Target::Addr(t, Op::LessThan(a, b)),
+                    8 => Target::Addr(t, Op::Equals(a, b)),
+                    _ => unreachable!(),
+                }
+            }
+            3 => {
+                if let Mode::Immediate = modes[0] {
+                    return Err(VmError::ImmediateWrite { address: sp + 1 });
+                }
+
+                let t = modes[0].get_addr(p, sp + 1, state.relative_base)?;
+                Target::Addr(t, Op::Input)
+            }
+            4 => {
+                let v = modes[0].get_value(p, sp + 1, state.relative_base)?;
+                Target::Value(v, Op::Output)
+            }
+            5 | 6 => {
+                let a = modes[0].get_value(p, sp + 1, state.relative_base)?;
+                let n = modes[1].get_value(p, sp + 2, state.relative_base)?;
+
+                match opcode {
+                    5 => Target::Jump(n, Op::JumpIfTrue(a)),
+                    6 => Target::Jump(n, Op::JumpIfFalse(a)),
+                    _ => unreachable!(),
+                }
+            }
+            9 => {
+                let a = modes[0].get_value(p, sp + 1, state.relative_base)?;
+                Target::UpdateState(Op::UpdateRelativeBase(a))
+            }
+            99 => Target::UpdateState(Op::Halt),
+            _ => {
+                return Err(VmError::UnknownOpcode {
+                    opcode,
+                    address: sp,
+                })
+            }
+        };
+
+        Ok(op)
+    }
+}
+
+// The interpreter the day 5 to 9 solutions were originally written against. It computes
+// all operand values up front into a Target before applying the instruction.
+#[derive(Debug, Copy, Clone)]
+pub struct LegacyInterpreter;
+
+impl Engine for LegacyInterpreter {
+    fn step<M: Memory, I: InputDevice + ?Sized, O: OutputDevice + ?Sized>(
+        &self,
+        state: &mut IntcodeVM<M>,
+        input_queue: &mut I,
+        output_queue: &mut O,
+    ) -> Result<VMStatus, VmError> {
+        let sp = state.instruction_ptr;
+        let target = Target::next(state)?;
+
+        let mut next = sp + target.get_len();
+        if let Target::Addr(t, _) = target {
+            if t == sp {
+                next = sp;
+            }
+        };
+
+        let overflow = VmError::Overflow { address: sp };
+
+        match target {
+            Target::Addr(addr, op) => match op {
+                Op::Add(a, b) => state.write(addr, a.checked_add(b).ok_or(overflow)?)?,
+                Op::Mult(a, b) => state.write(addr, a.checked_mul(b).ok_or(overflow)?)?,
+                Op::LessThan(a, b) => state.write(addr, if a < b { 1 } else { 0 })?,
+                Op::Equals(a, b) => state.write(addr, if a == b { 1 } else { 0 })?,
+                Op::Input => match input_queue.input() {
+                    Some(value) => state.write(addr, value)?,
+                    None => return Ok(VMStatus::EmptyInputBuffer),
+                },
+                _ => unreachable!(),
+            },
+            Target::Value(val, op) => match op {
+                Op::Output => output_queue.output(val),
+                _ => unreachable!(),
+            },
+            Target::Jump(n, op) => {
+                let jump = match op {
+                    Op::JumpIfTrue(a) => a != 0,
+                    Op::JumpIfFalse(a) => a == 0,
+                    _ => unreachable!(),
+                };
+
+                if jump {
+                    if n < 0 {
+                        return Err(VmError::NegativeAddress {
+                            target: n,
+                            address: sp,
+                        });
+                    }
+
+                    next = n as usize;
+                }
+            }
+            Target::UpdateState(op) => match op {
+                Op::UpdateRelativeBase(a) => {
+                    state.relative_base = state.relative_base.checked_add(a).ok_or(overflow)?;
+                }
+                Op::Halt => return Ok(VMStatus::Halted),
+                _ => unreachable!(),
+            },
+        }
+
+        state.instruction_ptr = next;
+
+        Ok(VMStatus::Ok)
+    }
+}
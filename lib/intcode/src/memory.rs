@@ -0,0 +1,225 @@
+use std::collections::HashMap;
+
+use crate::VmError;
+
+// Default upper bound for the number of words a VM may address, 128 MiB worth of i64s
+pub const DEFAULT_MEMORY_LIMIT: usize = 1 << 24;
+
+pub const PAGE_SIZE: usize = 1024;
+
+pub trait Memory {
+    fn read(&self, address: usize) -> i64;
+    fn write(&mut self, address: usize, value: i64) -> Result<(), VmError>;
+
+    // One past the highest address that has been allocated
+    fn len(&self) -> usize;
+
+    fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    // The allocated memory as (start address, words) runs, in address order
+    fn segments(&self) -> Vec<(usize, Vec<i64>)> {
+        vec![(0, (0..self.len()).map(|a| self.read(a)).collect())]
+    }
+}
+
+// Contiguous memory that grows when the program writes past its end
+#[derive(Debug, Clone)]
+pub struct VecMemory {
+    data: Vec<i64>,
+    limit: usize,
+}
+
+impl VecMemory {
+    pub fn new(program: &[i64]) -> VecMemory {
+        VecMemory::with_limit(program, DEFAULT_MEMORY_LIMIT)
+    }
+
+    pub fn with_limit(program: &[i64], limit: usize) -> VecMemory {
+        VecMemory {
+            data: program.to_vec(),
+            limit,
+        }
+    }
+}
+
+impl Memory for VecMemory {
+    fn read(&self, address: usize) -> i64 {
+        *self.data.get(address).unwrap_or(&0)
+    }
+
+    fn write(&mut self, address: usize, value: i64) -> Result<(), VmError> {
+        if address >= self.data.len() {
+            if address >= self.limit {
+                return Err(VmError::MemoryLimit {
+                    address,
+                    limit: self.limit,
+                });
+            }
+
+            self.data.resize(address + 1, 0);
+        }
+
+        self.data[address] = value;
+
+        Ok(())
+    }
+
+    fn len(&self) -> usize {
+        self.data.len()
+    }
+
+    fn segments(&self) -> Vec<(usize, Vec<i64>)> {
+        vec![(0, self.data.clone())]
+    }
+}
+
+// Sparse memory allocated in fixed size pages on first write. Suits programs that
+// scatter data far away from the program image through the relative base.
+#[derive(Debug, Clone)]
+pub struct PagedMemory {
+    pages: HashMap<usize, Box<[i64; PAGE_SIZE]>>,
+    limit: usize,
+    len: usize,
+}
+
+impl PagedMemory {
+    pub fn new(program: &[i64]) -> PagedMemory {
+        PagedMemory::with_limit(program, DEFAULT_MEMORY_LIMIT)
+    }
+
+    // The limit is the number of words that may be allocated in total, not the
+    // highest address
+    pub fn with_limit(program: &[i64], limit: usize) -> PagedMemory {
+        let mut memory = PagedMemory {
+            pages: HashMap::new(),
+            limit,
+            len: 0,
+        };
+
+        for (page, chunk) in program.chunks(PAGE_SIZE).enumerate() {
+            let mut data = Box::new([0; PAGE_SIZE]);
+            data[..chunk.len()].copy_from_slice(chunk);
+            memory.pages.insert(page, data);
+        }
+
+        memory.len = program.len();
+        memory
+    }
+
+    pub fn allocated(&self) -> usize {
+        self.pages.len() * PAGE_SIZE
+    }
+}
+
+impl Memory for PagedMemory {
+    fn read(&self, address: usize) -> i64 {
+        match self.pages.get(&(address / PAGE_SIZE)) {
+            Some(page) => page[address % PAGE_SIZE],
+            None => 0,
+        }
+    }
+
+    fn write(&mut self, address: usize, value: i64) -> Result<(), VmError> {
+        let page = address / PAGE_SIZE;
+
+        if !self.pages.contains_key(&page) {
+            if self.allocated() + PAGE_SIZE > self.limit {
+                return Err(VmError::MemoryLimit {
+                    address,
+                    limit: self.limit,
+                });
+            }
+
+            self.pages.insert(page, Box::new([0; PAGE_SIZE]));
+        }
+
+        self.pages.get_mut(&page).unwrap()[address % PAGE_SIZE] = value;
+        self.len = std::cmp::max(self.len, address + 1);
+
+        Ok(())
+    }
+
+    fn len(&self) -> usize {
+        self.len
+    }
+
+    fn segments(&self) -> Vec<(usize, Vec<i64>)> {
+        let mut pages = self.pages.keys().copied().collect::<Vec<_>>();
+        pages.sort_unstable();
+
+        pages
+            .into_iter()
+            .map(|page| {
+                let start = page * PAGE_SIZE;
+                let end = std::cmp::min(PAGE_SIZE, self.len - start);
+
+                (start, self.pages[&page][..end].to_vec())
+            })
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn vec_memory_grows_on_write() {
+        let mut memory = VecMemory::new(&[1, 2, 3]);
+
+        assert_eq!(memory.read(10), 0);
+        assert_eq!(memory.len(), 3);
+
+        memory.write(10, 7).unwrap();
+
+        assert_eq!(memory.read(10), 7);
+        assert_eq!(memory.read(5), 0);
+        assert_eq!(memory.len(), 11);
+    }
+
+    #[test]
+    fn vec_memory_limit() {
+        let mut memory = VecMemory::with_limit(&[1, 2, 3], 8);
+
+        assert_eq!(memory.write(7, 1), Ok(()));
+        assert_eq!(
+            memory.write(8, 1),
+            Err(VmError::MemoryLimit {
+                address: 8,
+                limit: 8
+            })
+        );
+    }
+
+    #[test]
+    fn paged_memory_reads_and_writes() {
+        let program = (0..PAGE_SIZE as i64 + 5).collect::<Vec<_>>();
+        let mut memory = PagedMemory::new(&program);
+
+        assert_eq!(memory.read(PAGE_SIZE + 4), PAGE_SIZE as i64 + 4);
+        assert_eq!(memory.allocated(), 2 * PAGE_SIZE);
+
+        memory.write(1 << 40, 42).unwrap();
+
+        assert_eq!(memory.read(1 << 40), 42);
+        assert_eq!(memory.read((1 << 40) + 1), 0);
+        assert_eq!(memory.allocated(), 3 * PAGE_SIZE);
+        assert_eq!(memory.len(), (1 << 40) + 1);
+    }
+
+    #[test]
+    fn paged_memory_limit() {
+        let mut memory = PagedMemory::with_limit(&[1, 2, 3], 2 * PAGE_SIZE);
+
+        assert_eq!(memory.write(5 * PAGE_SIZE, 1), Ok(()));
+        assert_eq!(
+            memory.write(9 * PAGE_SIZE, 1),
+            Err(VmError::MemoryLimit {
+                address: 9 * PAGE_SIZE,
+                limit: 2 * PAGE_SIZE
+            })
+        );
+    }
+}
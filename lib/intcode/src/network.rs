@@ -0,0 +1,787 @@
+use std::cell::Cell;
+use std::collections::{BTreeMap, VecDeque};
+use std::fmt;
+use std::num::NonZeroUsize;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::mpsc::{self, Receiver, Sender, TryRecvError};
+use std::thread;
+
+use crate::{run_until, InputDevice, IntcodeVM, OutputDevice, Process, VMStatus, VmError};
+
+pub type NodeId = usize;
+
+// Where the packets a node sends go
+#[derive(Debug, Clone, PartialEq)]
+pub enum Link {
+    Direct(NodeId),
+    Broadcast(Vec<NodeId>),
+    // The first word of each packet is the address to send the rest of it to, either a
+    // node or an interceptor
+    Addressed,
+}
+
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub enum Control {
+    Continue,
+    // Stop the whole network with a result
+    Stop(i64),
+}
+
+// Receives the packets sent to an address that isn't a node, and gets to wake the network
+// up when it goes idle
+pub trait Interceptor {
+    fn receive(&mut self, from: NodeId, packet: &[i64]) -> Control;
+
+    // Called when every node is blocked on input or halted. Packets pushed to deliveries
+    // are sent, if there are none the network is deadlocked.
+    fn idle(&mut self, _deliveries: &mut Vec<(NodeId, Vec<i64>)>) -> Control {
+        Control::Continue
+    }
+}
+
+impl<F: FnMut(NodeId, &[i64]) -> Control> Interceptor for F {
+    fn receive(&mut self, from: NodeId, packet: &[i64]) -> Control {
+        self(from, packet)
+    }
+}
+
+// Keeps the last packet it received and sends it on to the target whenever the network is
+// idle. Stops once it sends two packets with the same last word in a row, the repeated y
+// value day 23 asks for.
+#[derive(Debug, Clone)]
+pub struct Nat {
+    target: NodeId,
+    packet: Option<Vec<i64>>,
+    sent: Option<i64>,
+}
+
+impl Nat {
+    pub fn new(target: NodeId) -> Nat {
+        Nat {
+            target,
+            packet: None,
+            sent: None,
+        }
+    }
+}
+
+impl Interceptor for Nat {
+    fn receive(&mut self, _: NodeId, packet: &[i64]) -> Control {
+        self.packet = Some(packet.to_vec());
+        Control::Continue
+    }
+
+    fn idle(&mut self, deliveries: &mut Vec<(NodeId, Vec<i64>)>) -> Control {
+        let packet = match &self.packet {
+            Some(packet) => packet.clone(),
+            None => return Control::Continue,
+        };
+
+        let last = packet.last().copied();
+
+        if let Some(y) = last.filter(|&y| self.sent == Some(y)) {
+            return Control::Stop(y);
+        }
+
+        self.sent = last;
+        deliveries.push((self.target, packet));
+
+        Control::Continue
+    }
+}
+
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub enum Schedule {
+    // Every node on its own thread, connected by mpsc channels
+    Threads,
+    // Every node on the calling thread, taking turns of up to quantum instructions
+    RoundRobin { quantum: u64 },
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum Outcome {
+    Halted,
+    Stopped(i64),
+    // Nothing can make progress, with the nodes that are waiting for input
+    Deadlock { blocked: Vec<NodeId> },
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum NetworkError {
+    Fault { node: NodeId, error: VmError },
+    UnknownAddress { node: NodeId, address: i64 },
+    // A Direct or Broadcast link to a node that was never added
+    UnknownNode { node: NodeId, to: NodeId },
+}
+
+impl fmt::Display for NetworkError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            NetworkError::Fault { node, error } => write!(f, "node {} faulted: {}", node, error),
+            NetworkError::UnknownAddress { node, address } => {
+                write!(
+                    f,
+                    "node {} sent a packet to unknown address {}",
+                    node, address
+                )
+            }
+            NetworkError::UnknownNode { node, to } => {
+                write!(f, "node {} is linked to unknown node {}", node, to)
+            }
+        }
+    }
+}
+
+impl std::error::Error for NetworkError {}
+
+struct Node {
+    process: Process,
+    link: Option<Link>,
+    packet_size: usize,
+    idle_input: Option<i64>,
+    last_packet: Option<Vec<i64>>,
+    // The input only holds the idle input the node was given
+    polling: bool,
+}
+
+type Interceptors = BTreeMap<i64, Box<dyn Interceptor>>;
+
+fn route(
+    from: NodeId,
+    link: &Link,
+    packet: &[i64],
+    interceptors: &mut Interceptors,
+    nodes: usize,
+    deliveries: &mut Vec<(NodeId, Vec<i64>)>,
+) -> Result<Control, NetworkError> {
+    let unknown = |to: NodeId| NetworkError::UnknownNode { node: from, to };
+
+    match link {
+        Link::Direct(to) if *to >= nodes => return Err(unknown(*to)),
+        Link::Direct(to) => deliveries.push((*to, packet.to_vec())),
+        Link::Broadcast(to) => {
+            if let Some(&to) = to.iter().find(|&&to| to >= nodes) {
+                return Err(unknown(to));
+            }
+
+            deliveries.extend(to.iter().map(|&to| (to, packet.to_vec())));
+        }
+        Link::Addressed => {
+            let address = packet[0];
+
+            if let Some(interceptor) = interceptors.get_mut(&address) {
+                return Ok(interceptor.receive(from, &packet[1..]));
+            }
+
+            if address < 0 || address as usize >= nodes {
+                return Err(NetworkError::UnknownAddress {
+                    node: from,
+                    address,
+                });
+            }
+
+            deliveries.push((address as usize, packet[1..].to_vec()));
+        }
+    }
+
+    Ok(Control::Continue)
+}
+
+fn idle(interceptors: &mut Interceptors, deliveries: &mut Vec<(NodeId, Vec<i64>)>) -> Control {
+    for interceptor in interceptors.values_mut() {
+        if let Control::Stop(result) = interceptor.idle(deliveries) {
+            return Control::Stop(result);
+        }
+    }
+
+    Control::Continue
+}
+
+enum Event {
+    Output(NodeId, i64),
+    // The node found its input empty after consuming this many words
+    Waiting(NodeId, u64),
+    Finished(NodeId, Result<VMStatus, VmError>),
+}
+
+struct ChannelInput<'a> {
+    node: NodeId,
+    receiver: &'a Receiver<i64>,
+    events: &'a Sender<Event>,
+    consumed: u64,
+    idle_input: Option<i64>,
+    // Reads of an empty input since the node last read or wrote anything
+    empty_reads: &'a Cell<u32>,
+    stop: &'a AtomicBool,
+}
+
+impl InputDevice for ChannelInput<'_> {
+    fn input(&mut self) -> Option<i64> {
+        let word = match self.receiver.try_recv() {
+            Ok(word) => Some(word),
+            Err(TryRecvError::Disconnected) => None,
+            Err(TryRecvError::Empty) => {
+                let empty_reads = self.empty_reads.get().saturating_add(1);
+                self.empty_reads.set(empty_reads);
+
+                // A node reading its idle input keeps running, so it only counts as waiting
+                // once it comes back for input again without having done anything
+                let waiting = match self.idle_input {
+                    Some(_) => 2,
+                    None => 1,
+                };
+
+                if empty_reads == waiting {
+                    let _ = self.events.send(Event::Waiting(self.node, self.consumed));
+                }
+
+                match self.idle_input {
+                    Some(_) if self.stop.load(Ordering::Relaxed) => return None,
+                    Some(value) => {
+                        thread::yield_now();
+                        return Some(value);
+                    }
+                    None => self.receiver.recv().ok(),
+                }
+            }
+        };
+
+        if word.is_some() {
+            self.consumed += 1;
+            self.empty_reads.set(0);
+        }
+
+        word
+    }
+}
+
+struct ChannelOutput<'a> {
+    node: NodeId,
+    events: &'a Sender<Event>,
+    empty_reads: &'a Cell<u32>,
+}
+
+impl OutputDevice for ChannelOutput<'_> {
+    fn output(&mut self, value: i64) {
+        self.empty_reads.set(0);
+        let _ = self.events.send(Event::Output(self.node, value));
+    }
+}
+
+// The network state the thread running the schedule keeps for each node
+struct HubNode<'a> {
+    sender: Sender<i64>,
+    link: &'a Option<Link>,
+    packet_size: usize,
+    output: &'a mut VecDeque<i64>,
+    last_packet: &'a mut Option<Vec<i64>>,
+    packet: Vec<i64>,
+    delivered: u64,
+    waiting: bool,
+    finished: bool,
+}
+
+// Declares a graph of VMs connected by channels and runs it until every VM halts, an
+// interceptor stops it or it deadlocks.
+//
+// Nodes send packets of packet_size words, one by default, along their link. Output of
+// nodes without a link stays in their output buffer. Nodes with an idle input read that
+// value instead of blocking when they have no input, like the NICs in day 23 read -1.
+#[derive(Default)]
+pub struct Network {
+    nodes: Vec<Node>,
+    interceptors: Interceptors,
+}
+
+impl Network {
+    pub fn new() -> Network {
+        Network::default()
+    }
+
+    pub fn add_node(&mut self, vm: IntcodeVM) -> NodeId {
+        self.nodes.push(Node {
+            process: Process::with_vm(vm),
+            link: None,
+            packet_size: 1,
+            idle_input: None,
+            last_packet: None,
+            polling: false,
+        });
+
+        self.nodes.len() - 1
+    }
+
+    // Queues input for a node, e.g. a phase setting or network address
+    pub fn send(&mut self, node: NodeId, words: &[i64]) {
+        self.nodes[node].process.input.extend(words);
+    }
+
+    pub fn connect(&mut self, node: NodeId, link: Link) {
+        self.nodes[node].link = Some(link);
+    }
+
+    // Packets are never empty, an Addressed packet holds at least its address
+    pub fn set_packet_size(&mut self, node: NodeId, packet_size: NonZeroUsize) {
+        self.nodes[node].packet_size = packet_size.get();
+    }
+
+    pub fn set_idle_input(&mut self, node: NodeId, value: i64) {
+        self.nodes[node].idle_input = Some(value);
+    }
+
+    // Receives the packets sent to an address on Addressed links
+    pub fn intercept<I: Interceptor + 'static>(&mut self, address: i64, interceptor: I) {
+        self.interceptors.insert(address, Box::new(interceptor));
+    }
+
+    pub fn vm(&self, node: NodeId) -> &IntcodeVM {
+        &self.nodes[node].process.vm
+    }
+
+    pub fn output(&self, node: NodeId) -> &VecDeque<i64> {
+        &self.nodes[node].process.output
+    }
+
+    pub fn last_packet(&self, node: NodeId) -> Option<&[i64]> {
+        self.nodes[node].last_packet.as_deref()
+    }
+
+    pub fn run(&mut self, schedule: Schedule) -> Result<Outcome, NetworkError> {
+        match schedule {
+            Schedule::Threads => self.run_threads(),
+            Schedule::RoundRobin { quantum } => self.run_round_robin(quantum),
+        }
+    }
+
+    fn run_round_robin(&mut self, quantum: u64) -> Result<Outcome, NetworkError> {
+        let count = self.nodes.len();
+        let mut deliveries = Vec::new();
+
+        loop {
+            let mut blocked = Vec::new();
+            let mut halted = 0;
+
+            for id in 0..count {
+                let node = &mut self.nodes[id];
+                let process = &mut node.process;
+
+                if process.vm.status() == &VMStatus::Halted {
+                    halted += 1;
+                    continue;
+                }
+
+                if let Some(value) = node.idle_input {
+                    if process.input.is_empty() {
+                        process.input.push_back(value);
+                        node.polling = true;
+                    }
+                }
+
+                let received = !node.polling && !process.input.is_empty();
+                let output_len = process.output.len();
+
+                let status = process
+                    .run_for(quantum)
+                    .map_err(|error| NetworkError::Fault { node: id, error })?;
+
+                let sent = process.output.len() > output_len;
+
+                // Nodes that read their idle input count as waiting, as they never run out
+                let waiting = match status {
+                    _ if sent => false,
+                    VMStatus::EmptyInputBuffer => !received,
+                    _ => node.polling && process.input.is_empty(),
+                };
+
+                if status == VMStatus::Halted {
+                    halted += 1;
+                } else if waiting {
+                    blocked.push(id);
+                }
+
+                let link = match &node.link {
+                    Some(link) => link,
+                    None => continue,
+                };
+
+                // Packets are only sent once they are complete
+                while process.output.len() >= node.packet_size {
+                    let packet = process.output.drain(..node.packet_size).collect::<Vec<_>>();
+
+                    let control = route(
+                        id,
+                        link,
+                        &packet,
+                        &mut self.interceptors,
+                        count,
+                        &mut deliveries,
+                    )?;
+
+                    node.last_packet = Some(packet);
+
+                    if let Control::Stop(result) = control {
+                        return Ok(Outcome::Stopped(result));
+                    }
+                }
+            }
+
+            if halted == count {
+                return Ok(Outcome::Halted);
+            }
+
+            if deliveries.is_empty() && blocked.len() + halted == count {
+                if let Control::Stop(result) = idle(&mut self.interceptors, &mut deliveries) {
+                    return Ok(Outcome::Stopped(result));
+                }
+
+                if deliveries.is_empty() {
+                    return Ok(Outcome::Deadlock { blocked });
+                }
+            }
+
+            for (to, packet) in deliveries.drain(..) {
+                self.nodes[to].process.input.extend(packet);
+                self.nodes[to].polling = false;
+            }
+        }
+    }
+
+    fn run_threads(&mut self) -> Result<Outcome, NetworkError> {
+        let stop = AtomicBool::new(false);
+        let (event_sender, events) = mpsc::channel();
+        let interceptors = &mut self.interceptors;
+        let nodes = &mut self.nodes;
+
+        thread::scope(|scope| {
+            let mut hub = Vec::new();
+
+            for (id, node) in nodes.iter_mut().enumerate() {
+                let (sender, receiver) = mpsc::channel();
+                let Process { vm, input, output } = &mut node.process;
+                let delivered = input.len() as u64;
+
+                for word in input.drain(..) {
+                    sender.send(word).unwrap();
+                }
+
+                let events = event_sender.clone();
+                let idle_input = node.idle_input;
+                let stop = &stop;
+
+                scope.spawn(move || {
+                    let empty_reads = Cell::new(0);
+                    let mut device = ChannelInput {
+                        node: id,
+                        receiver: &receiver,
+                        events: &events,
+                        consumed: 0,
+                        idle_input,
+                        empty_reads: &empty_reads,
+                        stop,
+                    };
+                    let mut output = ChannelOutput {
+                        node: id,
+                        events: &events,
+                        empty_reads: &empty_reads,
+                    };
+
+                    let result = run_until(vm, &mut device, &mut output, |_| {
+                        stop.load(Ordering::Relaxed)
+                    });
+
+                    // Hand back what the node didn't get to read
+                    input.extend(receiver.try_iter());
+                    let _ = events.send(Event::Finished(id, result));
+                });
+
+                hub.push(HubNode {
+                    sender,
+                    link: &node.link,
+                    packet_size: node.packet_size,
+                    output,
+                    last_packet: &mut node.last_packet,
+                    packet: Vec::new(),
+                    delivered,
+                    waiting: false,
+                    finished: false,
+                });
+            }
+
+            drop(event_sender);
+
+            let outcome = Network::hub(&mut hub, &events, interceptors);
+
+            // Wake up every node so that the scope can join them
+            stop.store(true, Ordering::Relaxed);
+            hub.clear();
+
+            outcome
+        })
+    }
+
+    // Routes the output of the node threads and keeps track of which are waiting
+    fn hub(
+        hub: &mut [HubNode],
+        events: &Receiver<Event>,
+        interceptors: &mut Interceptors,
+    ) -> Result<Outcome, NetworkError> {
+        let count = hub.len();
+        let mut deliveries = Vec::new();
+
+        while let Ok(event) = events.recv() {
+            match event {
+                Event::Output(id, word) => {
+                    let node = &mut hub[id];
+                    node.waiting = false;
+
+                    let link = match node.link {
+                        Some(link) => link,
+                        None => {
+                            node.output.push_back(word);
+                            continue;
+                        }
+                    };
+
+                    node.packet.push(word);
+
+                    if node.packet.len() == node.packet_size {
+                        let packet = std::mem::take(&mut node.packet);
+                        let control =
+                            route(id, link, &packet, interceptors, count, &mut deliveries)?;
+
+                        *node.last_packet = Some(packet);
+
+                        if let Control::Stop(result) = control {
+                            return Ok(Outcome::Stopped(result));
+                        }
+                    }
+                }
+                Event::Waiting(id, consumed) => {
+                    hub[id].waiting = consumed == hub[id].delivered;
+                }
+                Event::Finished(id, result) => {
+                    hub[id].finished = true;
+
+                    if let Err(error) = result {
+                        return Err(NetworkError::Fault { node: id, error });
+                    }
+                }
+            }
+
+            if hub.iter().all(|node| node.finished) {
+                return Ok(Outcome::Halted);
+            }
+
+            let idle_network =
+                deliveries.is_empty() && hub.iter().all(|node| node.finished || node.waiting);
+
+            if idle_network {
+                if let Control::Stop(result) = idle(interceptors, &mut deliveries) {
+                    return Ok(Outcome::Stopped(result));
+                }
+
+                if deliveries.is_empty() {
+                    let blocked = (0..count).filter(|&id| !hub[id].finished).collect();
+                    return Ok(Outcome::Deadlock { blocked });
+                }
+            }
+
+            for (to, packet) in deliveries.drain(..) {
+                let node = &mut hub[to];
+
+                for word in packet {
+                    if node.sender.send(word).is_ok() {
+                        node.delivered += 1;
+                        node.waiting = false;
+                    }
+                }
+            }
+        }
+
+        Ok(Outcome::Halted)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::assemble;
+
+    const SCHEDULES: [Schedule; 3] = [
+        Schedule::Threads,
+        Schedule::RoundRobin { quantum: 1 },
+        Schedule::RoundRobin { quantum: 1000 },
+    ];
+
+    fn vm(source: &str) -> IntcodeVM {
+        IntcodeVM::new(&assemble(source).unwrap())
+    }
+
+    // Adds its id to every value it receives, until it receives something over 100
+    fn adder() -> IntcodeVM {
+        vm("
+                    IN [id]
+            loop:   IN [value]
+                    LT #100, [value], [done]
+                    JT [done], #end
+                    ADD [value], [id], [value]
+                    OUT [value]
+                    JT #1, #loop
+            end:    OUT [value]
+                    HLT
+            id:     data 0
+            value:  data 0
+            done:   data 0
+            ")
+    }
+
+    #[test]
+    fn feedback_ring() {
+        for schedule in SCHEDULES.iter() {
+            let mut network = Network::new();
+
+            for id in 0..3 {
+                let node = network.add_node(adder());
+                network.send(node, &[id + 1]);
+                network.connect(node, Link::Direct(((id + 1) % 3) as usize));
+            }
+
+            network.send(0, &[0]);
+
+            assert_eq!(
+                network.run(*schedule),
+                Ok(Outcome::Halted),
+                "{:?}",
+                schedule
+            );
+            // 0 + 1 + 2 + 3 + 1 + ... until it passes 100 at node 0
+            assert_eq!(network.last_packet(2), Some(&[102][..]));
+        }
+    }
+
+    #[test]
+    fn deadlock() {
+        for schedule in SCHEDULES.iter() {
+            let mut network = Network::new();
+            let a = network.add_node(vm("IN [9]\nOUT [9]\nIN [9]\nHLT"));
+            let b = network.add_node(vm("IN [9]\nHLT"));
+            let c = network.add_node(vm("IN [9]\nIN [9]\nHLT"));
+
+            network.send(a, &[5]);
+            network.connect(a, Link::Broadcast(vec![b, c]));
+
+            assert_eq!(
+                network.run(*schedule),
+                Ok(Outcome::Deadlock {
+                    blocked: vec![a, c]
+                }),
+                "{:?}",
+                schedule
+            );
+        }
+    }
+
+    #[test]
+    fn unknown_nodes() {
+        for schedule in SCHEDULES.iter() {
+            for link in &[Link::Direct(3), Link::Broadcast(vec![0, 3])] {
+                let mut network = Network::new();
+                let a = network.add_node(vm("OUT #7\nHLT"));
+                network.connect(a, link.clone());
+
+                assert_eq!(
+                    network.run(*schedule),
+                    Err(NetworkError::UnknownNode { node: a, to: 3 }),
+                    "{:?}",
+                    schedule
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn single_word_packets() {
+        for schedule in SCHEDULES.iter() {
+            // An Addressed packet of one word is only the address, with nothing after it
+            let mut network = Network::new();
+            let a = network.add_node(vm("OUT #5\nHLT"));
+            network.connect(a, Link::Addressed);
+            network.set_packet_size(a, NonZeroUsize::new(1).unwrap());
+            network.intercept(5, |_: NodeId, packet: &[i64]| {
+                Control::Stop(packet.len() as i64)
+            });
+
+            assert_eq!(
+                network.run(*schedule),
+                Ok(Outcome::Stopped(0)),
+                "{:?}",
+                schedule
+            );
+        }
+    }
+
+    #[test]
+    fn addressed_packets_and_interceptors() {
+        // Forwards every packet it receives to the next address with x increased by one,
+        // reading -1 when idle
+        let network = || {
+            let mut network = Network::new();
+
+            for address in 0..3 {
+                let node = network.add_node(vm("
+                            IN [address]
+                    loop:   IN [x]
+                            EQ [x], #-1, [idle]
+                            JT [idle], #loop
+                            IN [y]
+                            ADD [address], #1, [next]
+                            ADD [x], #1, [x]
+                            OUT [next]
+                            OUT [x]
+                            OUT [y]
+                            JT #1, #loop
+                    address: data 0
+                    next:   data 0
+                    x:      data 0
+                    y:      data 0
+                    idle:   data 0
+                    "));
+
+                network.send(node, &[address]);
+                network.connect(node, Link::Addressed);
+                network.set_packet_size(node, NonZeroUsize::new(3).unwrap());
+                network.set_idle_input(node, -1);
+            }
+
+            network.send(0, &[7, 10]);
+            network
+        };
+
+        for schedule in SCHEDULES.iter() {
+            let mut first = network();
+            first.intercept(3, |from: NodeId, packet: &[i64]| {
+                assert_eq!(from, 2);
+                Control::Stop(packet[0])
+            });
+
+            assert_eq!(
+                first.run(*schedule),
+                Ok(Outcome::Stopped(10)),
+                "{:?}",
+                schedule
+            );
+
+            // The NAT sends the packet back to node 0 until the same y arrives twice
+            let mut nat = network();
+            nat.intercept(3, Nat::new(0));
+
+            assert_eq!(
+                nat.run(*schedule),
+                Ok(Outcome::Stopped(10)),
+                "{:?}",
+                schedule
+            );
+            assert_eq!(nat.last_packet(2), Some(&[3, 13, 10][..]));
+        }
+    }
+}
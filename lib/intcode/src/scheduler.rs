@@ -0,0 +1,139 @@
+use std::collections::VecDeque;
+
+use crate::{run_for, IntcodeVM, Memory, VMStatus, VecMemory, VmError};
+
+// A VM together with its I/O buffers
+#[derive(Debug, Clone)]
+pub struct Process<M = VecMemory> {
+    pub vm: IntcodeVM<M>,
+    pub input: VecDeque<i64>,
+    pub output: VecDeque<i64>,
+}
+
+impl Process {
+    pub fn new(program: &[i64]) -> Process {
+        Process::with_vm(IntcodeVM::new(program))
+    }
+}
+
+impl<M: Memory> Process<M> {
+    pub fn with_vm(vm: IntcodeVM<M>) -> Process<M> {
+        Process {
+            vm,
+            input: VecDeque::new(),
+            output: VecDeque::new(),
+        }
+    }
+
+    pub fn run_for(&mut self, steps: u64) -> Result<VMStatus, VmError> {
+        run_for(&mut self.vm, &mut self.input, &mut self.output, steps)
+    }
+
+    // Halted, faulted or waiting for input that hasn't arrived yet
+    pub fn is_blocked(&self) -> bool {
+        match self.vm.status() {
+            VMStatus::EmptyInputBuffer => self.input.is_empty(),
+            VMStatus::Halted | VMStatus::Faulted => true,
+            VMStatus::Ok | VMStatus::StepLimitReached => false,
+        }
+    }
+}
+
+// Interleaves processes by giving each one a budget of instructions per round. Only
+// instruction counts decide the interleaving, so the same programs and inputs always
+// interleave the same way.
+#[derive(Debug, Clone)]
+pub struct RoundRobin<M = VecMemory> {
+    processes: Vec<Process<M>>,
+    quantum: u64,
+}
+
+impl<M: Memory> RoundRobin<M> {
+    pub fn new(quantum: u64) -> RoundRobin<M> {
+        RoundRobin {
+            processes: Vec::new(),
+            quantum,
+        }
+    }
+
+    // Adds a process and returns its index
+    pub fn push(&mut self, process: Process<M>) -> usize {
+        self.processes.push(process);
+        self.processes.len() - 1
+    }
+
+    pub fn processes(&self) -> &[Process<M>] {
+        &self.processes
+    }
+
+    pub fn processes_mut(&mut self) -> &mut [Process<M>] {
+        &mut self.processes
+    }
+
+    // Runs every process that isn't blocked for up to a quantum of instructions, in order.
+    // Returns the number of instructions executed, 0 if every process is blocked.
+    pub fn round(&mut self) -> Result<u64, VmError> {
+        let mut executed = 0;
+
+        for process in self.processes.iter_mut() {
+            if process.is_blocked() {
+                continue;
+            }
+
+            let steps = process.vm.steps();
+            process.run_for(self.quantum)?;
+            executed += process.vm.steps() - steps;
+        }
+
+        Ok(executed)
+    }
+
+    pub fn all_blocked(&self) -> bool {
+        self.processes.iter().all(|p| p.is_blocked())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::assemble;
+
+    #[test]
+    fn interleaves_by_instruction_count() {
+        // Outputs its input, then spins for a while before asking for more
+        let program = assemble(
+            "
+            loop:   IN [value]
+                    OUT [value]
+                    ADD #0, #50, [count]
+            spin:   ADD [count], #-1, [count]
+                    JT [count], #spin
+                    JT #1, #loop
+            value:  data 0
+            count:  data 0
+            ",
+        )
+        .unwrap();
+
+        let mut scheduler = RoundRobin::new(10);
+        let a = scheduler.push(Process::new(&program));
+        let b = scheduler.push(Process::new(&program));
+
+        scheduler.processes_mut()[a].input.extend(&[1, 2]);
+        scheduler.processes_mut()[b].input.extend(&[3]);
+
+        assert_eq!(scheduler.round(), Ok(20));
+        assert_eq!(scheduler.processes()[a].output, vec![1]);
+        assert_eq!(scheduler.processes()[b].output, vec![3]);
+
+        let mut rounds = 1;
+        while scheduler.round().unwrap() > 0 {
+            rounds += 1;
+        }
+
+        assert!(scheduler.all_blocked());
+        assert_eq!(scheduler.processes()[a].output, vec![1, 2]);
+        assert_eq!(scheduler.processes()[b].vm.steps(), 104);
+        assert_eq!(rounds, 21);
+    }
+}
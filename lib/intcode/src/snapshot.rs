@@ -0,0 +1,277 @@
+use std::collections::VecDeque;
+use std::fmt;
+use std::fs::File;
+use std::io::{self, BufRead, BufReader, BufWriter, Write};
+use std::path::Path;
+
+use crate::{IntcodeVM, Memory, VecMemory, VmError};
+
+pub const SNAPSHOT_VERSION: u32 = 1;
+
+const MAGIC: &str = "intcode-snapshot";
+
+#[derive(Debug)]
+pub enum SnapshotError {
+    Io(io::Error),
+    NotASnapshot,
+    UnsupportedVersion(String),
+    Malformed { line: usize },
+    Missing(&'static str),
+    Restore(VmError),
+}
+
+impl fmt::Display for SnapshotError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            SnapshotError::Io(e) => write!(f, "{}", e),
+            SnapshotError::NotASnapshot => write!(f, "not an intcode snapshot"),
+            SnapshotError::UnsupportedVersion(v) => {
+                write!(f, "unsupported snapshot version {}", v)
+            }
+            SnapshotError::Malformed { line } => write!(f, "malformed snapshot at line {}", line),
+            SnapshotError::Missing(key) => write!(f, "snapshot has no {} line", key),
+            SnapshotError::Restore(e) => write!(f, "unable to restore memory: {}", e),
+        }
+    }
+}
+
+impl std::error::Error for SnapshotError {}
+
+impl From<io::Error> for SnapshotError {
+    fn from(e: io::Error) -> SnapshotError {
+        SnapshotError::Io(e)
+    }
+}
+
+// Everything needed to resume a VM, saved as text:
+//
+//     intcode-snapshot 1
+//     ip 25
+//     rb 1000
+//     input 1,2
+//     output
+//     memory 0 1102,34463338,34463338,63,...
+//
+// with one memory line per allocated segment. Engine and dispatch settings aren't state
+// and are left at their defaults when restoring.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Snapshot {
+    pub instruction_ptr: usize,
+    pub relative_base: i64,
+    pub memory: Vec<(usize, Vec<i64>)>,
+    pub input: VecDeque<i64>,
+    pub output: VecDeque<i64>,
+}
+
+fn join<'a, I: IntoIterator<Item = &'a i64>>(words: I) -> String {
+    words
+        .into_iter()
+        .map(|w| w.to_string())
+        .collect::<Vec<_>>()
+        .join(",")
+}
+
+fn split(text: &str) -> Option<Vec<i64>> {
+    if text.is_empty() {
+        return Some(Vec::new());
+    }
+
+    text.split(',').map(|w| w.parse().ok()).collect()
+}
+
+impl Snapshot {
+    pub fn capture<M: Memory>(
+        vm: &IntcodeVM<M>,
+        input_buffer: &VecDeque<i64>,
+        output_buffer: &VecDeque<i64>,
+    ) -> Snapshot {
+        Snapshot {
+            instruction_ptr: vm.instruction_ptr,
+            relative_base: vm.relative_base,
+            memory: vm.memory.segments(),
+            input: input_buffer.clone(),
+            output: output_buffer.clone(),
+        }
+    }
+
+    pub fn restore(&self) -> Result<IntcodeVM, SnapshotError> {
+        self.restore_into(VecMemory::new(&[]))
+    }
+
+    // Restores into the given, normally empty, memory backend
+    pub fn restore_into<M: Memory>(&self, mut memory: M) -> Result<IntcodeVM<M>, SnapshotError> {
+        for (start, words) in self.memory.iter() {
+            for (i, &word) in words.iter().enumerate() {
+                memory
+                    .write(start + i, word)
+                    .map_err(SnapshotError::Restore)?;
+            }
+        }
+
+        let mut vm = IntcodeVM::with_memory(memory);
+        vm.instruction_ptr = self.instruction_ptr;
+        vm.relative_base = self.relative_base;
+
+        Ok(vm)
+    }
+
+    pub fn save<W: Write>(&self, writer: &mut W) -> io::Result<()> {
+        writeln!(writer, "{} {}", MAGIC, SNAPSHOT_VERSION)?;
+        writeln!(writer, "ip {}", self.instruction_ptr)?;
+        writeln!(writer, "rb {}", self.relative_base)?;
+        writeln!(writer, "input {}", join(&self.input))?;
+        writeln!(writer, "output {}", join(&self.output))?;
+
+        for (start, words) in self.memory.iter() {
+            writeln!(writer, "memory {} {}", start, join(words))?;
+        }
+
+        Ok(())
+    }
+
+    pub fn load<R: BufRead>(reader: R) -> Result<Snapshot, SnapshotError> {
+        let mut lines = reader.lines();
+
+        let header = lines.next().ok_or(SnapshotError::NotASnapshot)??;
+        let mut header = header.split_whitespace();
+
+        if header.next() != Some(MAGIC) {
+            return Err(SnapshotError::NotASnapshot);
+        }
+
+        match header.next() {
+            Some(version) if version == SNAPSHOT_VERSION.to_string() => (),
+            version => {
+                let version = version.unwrap_or("").to_string();
+                return Err(SnapshotError::UnsupportedVersion(version));
+            }
+        }
+
+        let mut instruction_ptr = None;
+        let mut relative_base = None;
+        let mut input = None;
+        let mut output = None;
+        let mut memory = Vec::new();
+
+        for (i, line) in lines.enumerate() {
+            let line = line?;
+            let malformed = SnapshotError::Malformed { line: i + 2 };
+
+            let (key, value) = match line.find(' ') {
+                Some(space) => (&line[..space], line[space + 1..].trim()),
+                None => (line.trim(), ""),
+            };
+
+            match key {
+                "ip" => instruction_ptr = Some(value.parse().map_err(|_| malformed)?),
+                "rb" => relative_base = Some(value.parse().map_err(|_| malformed)?),
+                "input" => input = Some(split(value).ok_or(malformed)?),
+                "output" => output = Some(split(value).ok_or(malformed)?),
+                "memory" => {
+                    let mut parts = value.splitn(2, ' ');
+                    let start = parts.next().and_then(|s| s.parse().ok());
+                    let words = split(parts.next().unwrap_or(""));
+
+                    match (start, words) {
+                        (Some(start), Some(words)) => memory.push((start, words)),
+                        _ => return Err(malformed),
+                    }
+                }
+                "" => (),
+                _ => return Err(malformed),
+            }
+        }
+
+        Ok(Snapshot {
+            instruction_ptr: instruction_ptr.ok_or(SnapshotError::Missing("ip"))?,
+            relative_base: relative_base.ok_or(SnapshotError::Missing("rb"))?,
+            memory,
+            input: input.ok_or(SnapshotError::Missing("input"))?.into(),
+            output: output.ok_or(SnapshotError::Missing("output"))?.into(),
+        })
+    }
+
+    pub fn save_file<P: AsRef<Path>>(&self, path: P) -> io::Result<()> {
+        let mut writer = BufWriter::new(File::create(path)?);
+        self.save(&mut writer)?;
+        writer.flush()
+    }
+
+    pub fn load_file<P: AsRef<Path>>(path: P) -> Result<Snapshot, SnapshotError> {
+        Snapshot::load(BufReader::new(File::open(path)?))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{assemble, run, PagedMemory, VMStatus};
+
+    #[test]
+    fn resume_after_save_and_load() {
+        let program = assemble(
+            "
+                    ARB #1000
+            loop:   IN rb+0
+                    MUL rb+0, #2, rb+1
+                    OUT rb+1
+                    ARB #1000
+                    JT #1, #loop
+            ",
+        )
+        .unwrap();
+
+        let mut vm = IntcodeVM::with_memory(PagedMemory::new(&program));
+        let mut input: VecDeque<i64> = vec![1, 2].into_iter().collect();
+        let mut output = VecDeque::new();
+
+        assert_eq!(
+            run(&mut vm, &mut input, &mut output),
+            Ok(VMStatus::EmptyInputBuffer)
+        );
+
+        input.push_back(3);
+
+        let mut saved = Vec::new();
+        Snapshot::capture(&vm, &input, &output)
+            .save(&mut saved)
+            .unwrap();
+
+        let snapshot = Snapshot::load(&saved[..]).unwrap();
+
+        assert_eq!(snapshot.output, vec![2, 4]);
+        assert_eq!(snapshot.relative_base, 3000);
+
+        let mut restored = snapshot
+            .restore_into(PagedMemory::with_limit(&[], 4 * 1024))
+            .unwrap();
+        let mut input = snapshot.input.clone();
+        let mut output = VecDeque::new();
+
+        assert_eq!(
+            run(&mut restored, &mut input, &mut output),
+            Ok(VMStatus::EmptyInputBuffer)
+        );
+        assert_eq!(output, vec![6]);
+        assert_eq!(restored.memory().read(3001), 6);
+    }
+
+    #[test]
+    fn rejects_other_formats() {
+        let load = |text: &str| Snapshot::load(text.as_bytes()).unwrap_err().to_string();
+
+        assert_eq!(load("1,2,3"), "not an intcode snapshot");
+        assert_eq!(
+            load("intcode-snapshot 2\n"),
+            "unsupported snapshot version 2"
+        );
+        assert_eq!(
+            load("intcode-snapshot 1\nip 0\nrb x\n"),
+            "malformed snapshot at line 3"
+        );
+        assert_eq!(
+            load("intcode-snapshot 1\nip 0\nrb 0\ninput\n"),
+            "snapshot has no output line"
+        );
+    }
+}
@@ -0,0 +1,1160 @@
+use std::collections::{HashMap, HashSet, VecDeque};
+use std::fmt;
+
+use crate::{encode_ascii, render_ascii, run, IntcodeVM, VmError};
+
+// Instructions the springdroid's memory holds
+pub const MAX_INSTRUCTIONS: usize = 15;
+
+// Scripts tried by solve before giving up
+const MAX_ATTEMPTS: usize = 100;
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+pub enum Register {
+    // Ground sensor 0 to 8, A to I, reading one to nine tiles ahead
+    Sensor(u8),
+    T,
+    J,
+}
+
+impl Register {
+    fn parse(text: &str) -> Option<Register> {
+        match text.as_bytes() {
+            [b'T'] => Some(Register::T),
+            [b'J'] => Some(Register::J),
+            [c @ b'A'..=b'I'] => Some(Register::Sensor(c - b'A')),
+            _ => None,
+        }
+    }
+
+    fn is_writable(self) -> bool {
+        matches!(self, Register::T | Register::J)
+    }
+}
+
+impl fmt::Display for Register {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Register::Sensor(s) => write!(f, "{}", (b'A' + s) as char),
+            Register::T => write!(f, "T"),
+            Register::J => write!(f, "J"),
+        }
+    }
+}
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum Gate {
+    And,
+    Or,
+    Not,
+}
+
+// Y = X gate Y, or Y = !X for Not
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub struct Instruction {
+    pub gate: Gate,
+    pub x: Register,
+    pub y: Register,
+}
+
+impl fmt::Display for Instruction {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let gate = match self.gate {
+            Gate::And => "AND",
+            Gate::Or => "OR",
+            Gate::Not => "NOT",
+        };
+
+        write!(f, "{} {} {}", gate, self.x, self.y)
+    }
+}
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum Speed {
+    Walk,
+    Run,
+}
+
+impl Speed {
+    // How many ground sensors the droid has at this speed
+    pub fn sensors(self) -> u8 {
+        match self {
+            Speed::Walk => 4,
+            Speed::Run => 9,
+        }
+    }
+
+    fn command(self) -> &'static str {
+        match self {
+            Speed::Walk => "WALK",
+            Speed::Run => "RUN",
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum ScriptErrorKind {
+    UnknownInstruction(String),
+    OperandCount { expected: usize, found: usize },
+    InvalidRegister(String),
+    ReadOnlyRegister(Register),
+    UnavailableSensor(Register),
+    TooManyInstructions(usize),
+    MissingCommand,
+    AfterCommand,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct ScriptError {
+    pub line: usize,
+    pub kind: ScriptErrorKind,
+}
+
+impl fmt::Display for ScriptError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "line {}: ", self.line)?;
+
+        match &self.kind {
+            ScriptErrorKind::UnknownInstruction(i) => write!(f, "unknown instruction '{}'", i),
+            ScriptErrorKind::OperandCount { expected, found } => {
+                write!(f, "expected {} operands, found {}", expected, found)
+            }
+            ScriptErrorKind::InvalidRegister(r) => write!(f, "invalid register '{}'", r),
+            ScriptErrorKind::ReadOnlyRegister(r) => {
+                write!(f, "register {} is a sensor and can't be written", r)
+            }
+            ScriptErrorKind::UnavailableSensor(r) => {
+                write!(f, "sensor {} is only available when running", r)
+            }
+            ScriptErrorKind::TooManyInstructions(n) => write!(
+                f,
+                "{} instructions, the droid only holds {}",
+                n, MAX_INSTRUCTIONS
+            ),
+            ScriptErrorKind::MissingCommand => write!(f, "script doesn't end with WALK or RUN"),
+            ScriptErrorKind::AfterCommand => write!(f, "instruction after WALK or RUN"),
+        }
+    }
+}
+
+impl std::error::Error for ScriptError {}
+
+// Ground sensor readings, bit 0 set when there is ground under sensor A
+pub type Sensors = u16;
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Script {
+    pub instructions: Vec<Instruction>,
+    pub speed: Speed,
+}
+
+impl Script {
+    pub fn parse(text: &str) -> Result<Script, ScriptError> {
+        let mut instructions = Vec::new();
+        let mut speed = None;
+        let mut last_line = 0;
+
+        for (i, line) in text.lines().enumerate() {
+            let error = |kind| ScriptError { line: i + 1, kind };
+            let words = line.split_whitespace().collect::<Vec<_>>();
+
+            if words.is_empty() {
+                continue;
+            }
+
+            last_line = i + 1;
+
+            if speed.is_some() {
+                return Err(error(ScriptErrorKind::AfterCommand));
+            }
+
+            let (gate, operands) = match words[0] {
+                "WALK" | "RUN" if words.len() > 1 => {
+                    return Err(error(ScriptErrorKind::OperandCount {
+                        expected: 0,
+                        found: words.len() - 1,
+                    }));
+                }
+                "WALK" => {
+                    speed = Some(Speed::Walk);
+                    continue;
+                }
+                "RUN" => {
+                    speed = Some(Speed::Run);
+                    continue;
+                }
+                "AND" => (Gate::And, &words[1..]),
+                "OR" => (Gate::Or, &words[1..]),
+                "NOT" => (Gate::Not, &words[1..]),
+                other => {
+                    return Err(error(ScriptErrorKind::UnknownInstruction(
+                        other.to_string(),
+                    )))
+                }
+            };
+
+            if operands.len() != 2 {
+                return Err(error(ScriptErrorKind::OperandCount {
+                    expected: 2,
+                    found: operands.len(),
+                }));
+            }
+
+            let register = |text: &str| {
+                Register::parse(text)
+                    .ok_or_else(|| error(ScriptErrorKind::InvalidRegister(text.to_string())))
+            };
+
+            let x = register(operands[0])?;
+            let y = register(operands[1])?;
+
+            if !y.is_writable() {
+                return Err(error(ScriptErrorKind::ReadOnlyRegister(y)));
+            }
+
+            instructions.push((i + 1, Instruction { gate, x, y }));
+        }
+
+        let speed = speed.ok_or(ScriptError {
+            line: last_line,
+            kind: ScriptErrorKind::MissingCommand,
+        })?;
+
+        let script = Script {
+            instructions: instructions.iter().map(|&(_, i)| i).collect(),
+            speed,
+        };
+
+        script.validate().map_err(|kind| {
+            // Point at the offending instruction where there is one
+            let line = match kind {
+                ScriptErrorKind::TooManyInstructions(_) => instructions[MAX_INSTRUCTIONS].0,
+                ScriptErrorKind::UnavailableSensor(r) => {
+                    instructions.iter().find(|(_, i)| i.x == r).unwrap().0
+                }
+                _ => last_line,
+            };
+
+            ScriptError { line, kind }
+        })?;
+
+        Ok(script)
+    }
+
+    // Checks that the droid accepts the script
+    pub fn validate(&self) -> Result<(), ScriptErrorKind> {
+        if self.instructions.len() > MAX_INSTRUCTIONS {
+            return Err(ScriptErrorKind::TooManyInstructions(
+                self.instructions.len(),
+            ));
+        }
+
+        for instruction in self.instructions.iter() {
+            if let Register::Sensor(s) = instruction.x {
+                if s >= self.speed.sensors() {
+                    return Err(ScriptErrorKind::UnavailableSensor(instruction.x));
+                }
+            }
+
+            if !instruction.y.is_writable() {
+                return Err(ScriptErrorKind::ReadOnlyRegister(instruction.y));
+            }
+        }
+
+        Ok(())
+    }
+
+    // Whether the droid jumps with these sensor readings
+    pub fn jumps(&self, sensors: Sensors) -> bool {
+        let (mut t, mut j) = (false, false);
+
+        for instruction in self.instructions.iter() {
+            let x = match instruction.x {
+                Register::Sensor(s) => sensors & 1 << s != 0,
+                Register::T => t,
+                Register::J => j,
+            };
+
+            let y = match instruction.y {
+                Register::T => &mut t,
+                _ => &mut j,
+            };
+
+            *y = match instruction.gate {
+                Gate::And => x && *y,
+                Gate::Or => x || *y,
+                Gate::Not => !x,
+            };
+        }
+
+        j
+    }
+
+    // Walks the droid across a hull, true for ground, starting on the first tile. Returns
+    // the tile it falls through on failure.
+    pub fn simulate(&self, hull: &[bool]) -> Result<(), usize> {
+        let mut position = 0;
+
+        while position < hull.len() {
+            if !hull[position] {
+                return Err(position);
+            }
+
+            let sensors = hull_sensors(hull, position, self.speed);
+            position += if self.jumps(sensors) { 4 } else { 1 };
+        }
+
+        Ok(())
+    }
+}
+
+impl fmt::Display for Script {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        for instruction in self.instructions.iter() {
+            writeln!(f, "{}", instruction)?;
+        }
+
+        writeln!(f, "{}", self.speed.command())
+    }
+}
+
+// What the sensors read at a position, past the end of the hull is ground
+fn hull_sensors(hull: &[bool], position: usize, speed: Speed) -> Sensors {
+    (0..speed.sensors()).fold(0, |sensors, s| match hull.get(position + 1 + s as usize) {
+        Some(false) => sensors,
+        _ => sensors | 1 << s,
+    })
+}
+
+// The hull the droid fell through in the output of a failed script, from where it starts
+pub fn parse_failure(output: &str) -> Option<Vec<bool>> {
+    let mut lines = output.lines().skip_while(|l| !l.contains('@'));
+    let column = lines.next()?.find('@')?;
+    let ground = lines.find(|l| l.contains('#'))?;
+
+    Some(ground.chars().skip(column).map(|c| c == '#').collect())
+}
+
+// A condition on the ground sensors
+#[derive(Debug, Clone, PartialEq)]
+pub enum Formula {
+    Sensor(u8),
+    Not(Box<Formula>),
+    // An empty And is true, an empty Or false
+    And(Vec<Formula>),
+    Or(Vec<Formula>),
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct FormulaError {
+    pub position: usize,
+}
+
+impl fmt::Display for FormulaError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "unexpected input at position {}", self.position)
+    }
+}
+
+impl std::error::Error for FormulaError {}
+
+struct FormulaParser<'a> {
+    text: &'a [u8],
+    position: usize,
+}
+
+impl FormulaParser<'_> {
+    fn peek(&mut self) -> Option<u8> {
+        while self.text.get(self.position) == Some(&b' ') {
+            self.position += 1;
+        }
+
+        self.text.get(self.position).copied()
+    }
+
+    fn error(&self) -> FormulaError {
+        FormulaError {
+            position: self.position,
+        }
+    }
+
+    fn list(
+        &mut self,
+        separator: u8,
+        item: fn(&mut Self) -> Result<Formula, FormulaError>,
+        build: fn(Vec<Formula>) -> Formula,
+    ) -> Result<Formula, FormulaError> {
+        let mut items = vec![item(self)?];
+
+        while self.peek() == Some(separator) {
+            self.position += 1;
+            items.push(item(self)?);
+        }
+
+        Ok(if items.len() == 1 {
+            items.pop().unwrap()
+        } else {
+            build(items)
+        })
+    }
+
+    fn or(&mut self) -> Result<Formula, FormulaError> {
+        self.list(b'|', FormulaParser::and, Formula::Or)
+    }
+
+    fn and(&mut self) -> Result<Formula, FormulaError> {
+        self.list(b'&', FormulaParser::unary, Formula::And)
+    }
+
+    fn unary(&mut self) -> Result<Formula, FormulaError> {
+        let c = self.peek().ok_or_else(|| self.error())?;
+        self.position += 1;
+
+        match c {
+            b'!' => Ok(Formula::Not(Box::new(self.unary()?))),
+            b'(' => {
+                let formula = self.or()?;
+
+                if self.peek() != Some(b')') {
+                    return Err(self.error());
+                }
+
+                self.position += 1;
+                Ok(formula)
+            }
+            b'A'..=b'I' => Ok(Formula::Sensor(c - b'A')),
+            _ => {
+                self.position -= 1;
+                Err(self.error())
+            }
+        }
+    }
+}
+
+impl Formula {
+    // Parses sensors A to I combined with !, & and |, in order of precedence
+    pub fn parse(text: &str) -> Result<Formula, FormulaError> {
+        let mut parser = FormulaParser {
+            text: text.as_bytes(),
+            position: 0,
+        };
+
+        let formula = parser.or()?;
+
+        match parser.peek() {
+            None => Ok(formula),
+            Some(_) => Err(parser.error()),
+        }
+    }
+
+    pub fn eval(&self, sensors: Sensors) -> bool {
+        match self {
+            Formula::Sensor(s) => sensors & 1 << s != 0,
+            Formula::Not(f) => !f.eval(sensors),
+            Formula::And(fs) => fs.iter().all(|f| f.eval(sensors)),
+            Formula::Or(fs) => fs.iter().any(|f| f.eval(sensors)),
+        }
+    }
+
+    fn max_sensor(&self) -> Option<u8> {
+        match self {
+            Formula::Sensor(s) => Some(*s),
+            Formula::Not(f) => f.max_sensor(),
+            Formula::And(fs) | Formula::Or(fs) => fs.iter().filter_map(|f| f.max_sensor()).max(),
+        }
+    }
+
+    // Flattens nested Ands and Ors and removes double negations
+    fn simplify(self) -> Formula {
+        match self {
+            Formula::Not(f) => match f.simplify() {
+                Formula::Not(f) => *f,
+                f => Formula::Not(Box::new(f)),
+            },
+            Formula::And(fs) => Formula::flatten(fs, true),
+            Formula::Or(fs) => Formula::flatten(fs, false),
+            f => f,
+        }
+    }
+
+    fn flatten(fs: Vec<Formula>, and: bool) -> Formula {
+        let mut flat = Vec::new();
+
+        for f in fs.into_iter().map(Formula::simplify) {
+            match f {
+                Formula::And(inner) if and => flat.extend(inner),
+                Formula::Or(inner) if !and => flat.extend(inner),
+                f => flat.push(f),
+            }
+        }
+
+        match (flat.len(), and) {
+            (1, _) => flat.pop().unwrap(),
+            (_, true) => Formula::And(flat),
+            (_, false) => Formula::Or(flat),
+        }
+    }
+
+    // The negation with the Not moved one level down
+    fn negate(&self) -> Formula {
+        let negate_all = |fs: &[Formula]| fs.iter().map(|f| f.negated()).collect();
+
+        match self {
+            Formula::Not(f) => (**f).clone(),
+            Formula::And(fs) => Formula::Or(negate_all(fs)),
+            Formula::Or(fs) => Formula::And(negate_all(fs)),
+            f => Formula::Not(Box::new(f.clone())),
+        }
+    }
+
+    fn negated(&self) -> Formula {
+        match self {
+            Formula::Not(f) => (**f).clone(),
+            f => Formula::Not(Box::new(f.clone())),
+        }
+    }
+
+    // Pulls parts shared by several terms out of them, so (A & D) | (B & D) becomes
+    // D & (A | B), which often needs fewer instructions
+    fn factor(&self) -> Formula {
+        match self {
+            Formula::Not(f) => Formula::Not(Box::new(f.factor())),
+            Formula::And(fs) => Formula::factor_terms(fs.clone(), true),
+            Formula::Or(fs) => Formula::factor_terms(fs.clone(), false),
+            f => f.clone(),
+        }
+    }
+
+    fn factor_terms(terms: Vec<Formula>, and: bool) -> Formula {
+        let build = |fs: Vec<Formula>, and: bool| match and {
+            true => Formula::And(fs),
+            false => Formula::Or(fs),
+        };
+
+        // The parts of a term of an Or are the parts of an And, and the other way around
+        let parts = |term: &Formula| match (term, and) {
+            (Formula::And(fs), false) | (Formula::Or(fs), true) => fs.clone(),
+            (term, _) => vec![term.clone()],
+        };
+
+        let mut counts: Vec<(Formula, usize)> = Vec::new();
+
+        for part in terms.iter().flat_map(&parts) {
+            match counts.iter_mut().find(|(f, _)| *f == part) {
+                Some((_, count)) => *count += 1,
+                None => counts.push((part, 1)),
+            }
+        }
+
+        let shared = counts
+            .into_iter()
+            .filter(|&(_, count)| count > 1)
+            .max_by_key(|&(_, count)| count);
+
+        let shared = match shared {
+            Some((shared, _)) => shared,
+            None => return build(terms.iter().map(|t| t.factor()).collect(), and).simplify(),
+        };
+
+        let (with, mut without): (Vec<_>, Vec<_>) =
+            terms.into_iter().partition(|t| parts(t).contains(&shared));
+
+        let rest = with
+            .iter()
+            .map(|t| {
+                let rest = parts(t).into_iter().filter(|p| *p != shared).collect();
+                build(rest, !and).simplify()
+            })
+            .collect::<Vec<_>>();
+
+        // A term that is only the shared part absorbs the others
+        let group = if rest.contains(&build(vec![], !and)) {
+            shared
+        } else {
+            build(vec![shared, Formula::factor_terms(rest, and)], !and)
+        };
+
+        without.push(group);
+
+        match without.len() {
+            1 => without.pop().unwrap().simplify(),
+            _ => Formula::factor_terms(without, and),
+        }
+    }
+}
+
+impl fmt::Display for Formula {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let list = |f: &mut fmt::Formatter, fs: &[Formula], separator| {
+            let parts = fs
+                .iter()
+                .map(|part| match part {
+                    Formula::And(_) | Formula::Or(_) => format!("({})", part),
+                    _ => part.to_string(),
+                })
+                .collect::<Vec<_>>();
+
+            write!(f, "{}", parts.join(separator))
+        };
+
+        match self {
+            Formula::Sensor(s) => write!(f, "{}", (b'A' + s) as char),
+            Formula::Not(inner) => match **inner {
+                Formula::Sensor(_) | Formula::Not(_) => write!(f, "!{}", inner),
+                _ => write!(f, "!({})", inner),
+            },
+            Formula::And(fs) if fs.is_empty() => write!(f, "A | !A"),
+            Formula::Or(fs) if fs.is_empty() => write!(f, "A & !A"),
+            Formula::And(fs) => list(f, fs, " & "),
+            Formula::Or(fs) => list(f, fs, " | "),
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum CompileError {
+    // Needs more than the two writable registers
+    Unrepresentable,
+    UnavailableSensor(Register),
+    TooLong(Script),
+}
+
+impl fmt::Display for CompileError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            CompileError::Unrepresentable => {
+                write!(f, "formula needs more registers than T and J")
+            }
+            CompileError::UnavailableSensor(r) => {
+                write!(f, "sensor {} is only available when running", r)
+            }
+            CompileError::TooLong(script) => write!(
+                f,
+                "formula needs {} instructions, the droid only holds {}",
+                script.instructions.len(),
+                MAX_INSTRUCTIONS
+            ),
+        }
+    }
+}
+
+impl std::error::Error for CompileError {}
+
+// Which writable registers are known to still hold their initial false
+#[derive(Debug, Copy, Clone)]
+struct Fresh {
+    t: bool,
+    j: bool,
+}
+
+impl Fresh {
+    fn get(self, r: Register) -> bool {
+        match r {
+            Register::T => self.t,
+            _ => self.j,
+        }
+    }
+
+    fn written(self, r: Register) -> Fresh {
+        match r {
+            Register::T => Fresh { t: false, ..self },
+            _ => Fresh { j: false, ..self },
+        }
+    }
+}
+
+type Code = (Vec<Instruction>, Fresh);
+
+fn instruction(gate: Gate, x: Register, y: Register) -> Instruction {
+    Instruction { gate, x, y }
+}
+
+fn shortest(candidates: impl IntoIterator<Item = Option<Code>>) -> Option<Code> {
+    candidates
+        .into_iter()
+        .flatten()
+        .min_by_key(|(code, _)| code.len())
+}
+
+fn append(code: Option<Code>, more: &[Instruction], r: Register) -> Option<Code> {
+    code.map(|(mut code, fresh)| {
+        code.extend_from_slice(more);
+        (code, fresh.written(r))
+    })
+}
+
+// The shortest code found for r = formula, trying the formula both as it is and negated
+fn compile_into(
+    formula: &Formula,
+    r: Register,
+    scratch: Option<Register>,
+    fresh: Fresh,
+) -> Option<Code> {
+    let direct = compile_direct(formula, r, scratch, fresh);
+
+    match formula {
+        Formula::Sensor(_) | Formula::Not(_) => direct,
+        _ => {
+            let negated = compile_direct(&formula.negate(), r, scratch, fresh);
+            shortest(vec![
+                direct,
+                append(negated, &[instruction(Gate::Not, r, r)], r),
+            ])
+        }
+    }
+}
+
+fn compile_direct(
+    formula: &Formula,
+    r: Register,
+    scratch: Option<Register>,
+    fresh: Fresh,
+) -> Option<Code> {
+    let a = Register::Sensor(0);
+
+    let code = match formula {
+        Formula::Sensor(s) if fresh.get(r) => vec![instruction(Gate::Or, Register::Sensor(*s), r)],
+        Formula::Sensor(s) => vec![
+            instruction(Gate::Not, Register::Sensor(*s), r),
+            instruction(Gate::Not, r, r),
+        ],
+        Formula::Not(f) => match **f {
+            Formula::Sensor(s) => vec![instruction(Gate::Not, Register::Sensor(s), r)],
+            _ => {
+                let inner = compile_into(f, r, scratch, fresh);
+                return append(inner, &[instruction(Gate::Not, r, r)], r);
+            }
+        },
+        Formula::Or(fs) if fs.is_empty() && fresh.get(r) => vec![],
+        Formula::Or(fs) if fs.is_empty() => {
+            vec![instruction(Gate::Not, a, r), instruction(Gate::And, a, r)]
+        }
+        Formula::And(fs) if fs.is_empty() => {
+            vec![instruction(Gate::Not, a, r), instruction(Gate::Or, a, r)]
+        }
+        Formula::And(fs) | Formula::Or(fs) => {
+            let gate = match formula {
+                Formula::And(_) => Gate::And,
+                _ => Gate::Or,
+            };
+
+            // Any part can go first, the rest are folded into it
+            let candidates = (0..fs.len()).map(|first| {
+                let mut code = compile_into(&fs[first], r, scratch, fresh)?;
+
+                let rest = fs
+                    .iter()
+                    .enumerate()
+                    .filter(|&(i, _)| i != first)
+                    .map(|(_, f)| f);
+
+                for f in rest {
+                    let (more, fresh) = fold(f, gate, r, scratch, code.1)?;
+                    code.0.extend(more);
+                    code.1 = fresh;
+                }
+
+                Some(code)
+            });
+
+            return shortest(candidates.collect::<Vec<_>>());
+        }
+    };
+
+    Some((code, fresh.written(r)))
+}
+
+// Code for r = r gate formula
+fn fold(
+    formula: &Formula,
+    gate: Gate,
+    r: Register,
+    scratch: Option<Register>,
+    fresh: Fresh,
+) -> Option<Code> {
+    let code = match (formula, scratch) {
+        (Formula::Sensor(s), _) => vec![instruction(gate, Register::Sensor(*s), r)],
+        (Formula::Not(f), None) => match **f {
+            // r & !x is !(!r | x) and r | !x is !(!r & x)
+            Formula::Sensor(s) => {
+                let flipped = match gate {
+                    Gate::And => Gate::Or,
+                    _ => Gate::And,
+                };
+
+                vec![
+                    instruction(Gate::Not, r, r),
+                    instruction(flipped, Register::Sensor(s), r),
+                    instruction(Gate::Not, r, r),
+                ]
+            }
+            _ => return None,
+        },
+        (_, None) => return None,
+        (f, Some(scratch)) => {
+            let (mut code, fresh) = compile_into(f, scratch, None, fresh)?;
+            code.push(instruction(gate, scratch, r));
+            return Some((code, fresh.written(r)));
+        }
+    };
+
+    Some((code, fresh.written(r)))
+}
+
+// Compiles a formula for when to jump into the shortest script found. Every way of
+// splitting the formula between T and J is tried, with each part both as it is and
+// negated.
+pub fn compile(formula: &Formula, speed: Speed) -> Result<Script, CompileError> {
+    if let Some(s) = formula.max_sensor().filter(|&s| s >= speed.sensors()) {
+        return Err(CompileError::UnavailableSensor(Register::Sensor(s)));
+    }
+
+    let fresh = Fresh { t: true, j: true };
+    let formula = formula.clone().simplify();
+
+    let (instructions, _) = shortest(vec![
+        compile_into(&formula, Register::J, Some(Register::T), fresh),
+        compile_into(&formula.factor(), Register::J, Some(Register::T), fresh),
+    ])
+    .ok_or(CompileError::Unrepresentable)?;
+
+    let script = Script {
+        instructions,
+        speed,
+    };
+
+    match script.instructions.len() {
+        n if n > MAX_INSTRUCTIONS => Err(CompileError::TooLong(script)),
+        _ => Ok(script),
+    }
+}
+
+// A small sum of products that is true for every reading in on and false for every
+// reading in off, using Quine-McCluskey with everything else as don't care
+pub fn minimise(sensors: u8, on: &HashSet<Sensors>, off: &HashSet<Sensors>) -> Formula {
+    let all = 1 << sensors;
+
+    // Implicants as (value, mask), where mask has the bits that can be anything
+    let mut implicants = (0..all)
+        .filter(|s| !off.contains(s))
+        .map(|s| (s, 0))
+        .collect::<HashSet<(Sensors, Sensors)>>();
+    let mut primes = HashSet::new();
+
+    while !implicants.is_empty() {
+        let mut merged = HashSet::new();
+        let mut used = HashSet::new();
+
+        for &(value, mask) in implicants.iter() {
+            for bit in (0..sensors).map(|b| 1 << b) {
+                let other = (value ^ bit, mask);
+
+                if mask & bit == 0 && implicants.contains(&other) {
+                    merged.insert((value & !bit, mask | bit));
+                    used.insert((value, mask));
+                    used.insert(other);
+                }
+            }
+        }
+
+        primes.extend(implicants.difference(&used).copied());
+        implicants = merged;
+    }
+
+    let covers = |(value, mask): (Sensors, Sensors), s: Sensors| s & !mask == value;
+
+    // Prefer the implicants covering the most readings that still need covering, then the
+    // ones with the fewest sensors
+    let mut primes = primes.into_iter().collect::<Vec<_>>();
+    primes.sort();
+
+    let mut uncovered = on.clone();
+    let mut chosen = Vec::new();
+
+    while !uncovered.is_empty() {
+        let best = *primes
+            .iter()
+            .max_by_key(|&&p| {
+                let covered = uncovered.iter().filter(|&&s| covers(p, s)).count();
+                (covered, p.1.count_ones())
+            })
+            .unwrap();
+
+        uncovered.retain(|&s| !covers(best, s));
+        chosen.push(best);
+    }
+
+    let terms = chosen
+        .into_iter()
+        .map(|(value, mask)| {
+            let literals = (0..sensors)
+                .filter(|&s| mask & 1 << s == 0)
+                .map(|s| match value & 1 << s {
+                    0 => Formula::Not(Box::new(Formula::Sensor(s))),
+                    _ => Formula::Sensor(s),
+                })
+                .collect();
+
+            Formula::And(literals)
+        })
+        .collect();
+
+    Formula::Or(terms).simplify()
+}
+
+// Decides, for every sensor reading seen on the hulls, whether to jump so that the droid
+// makes it across all of them. Walking is tried before jumping.
+fn decide(hulls: &[Vec<bool>], speed: Speed) -> Option<HashMap<Sensors, bool>> {
+    fn cross(
+        hulls: &[Vec<bool>],
+        position: usize,
+        speed: Speed,
+        decisions: &mut HashMap<Sensors, bool>,
+    ) -> bool {
+        let hull = match hulls.first() {
+            Some(hull) => hull,
+            None => return true,
+        };
+
+        if position >= hull.len() {
+            return cross(&hulls[1..], 0, speed, decisions);
+        }
+
+        if !hull[position] {
+            return false;
+        }
+
+        let sensors = hull_sensors(hull, position, speed);
+        let step = |jump| position + if jump { 4 } else { 1 };
+
+        if let Some(&jump) = decisions.get(&sensors) {
+            return cross(hulls, step(jump), speed, decisions);
+        }
+
+        for &jump in [false, true].iter() {
+            decisions.insert(sensors, jump);
+
+            if cross(hulls, step(jump), speed, decisions) {
+                return true;
+            }
+        }
+
+        decisions.remove(&sensors);
+        false
+    }
+
+    let mut decisions = HashMap::new();
+
+    if cross(hulls, 0, speed, &mut decisions) {
+        Some(decisions)
+    } else {
+        None
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum SolveError {
+    Vm(VmError),
+    Script(ScriptError),
+    // No way to get across every hull seen so far
+    Impossible(Vec<Vec<bool>>),
+    Compile(CompileError),
+    UnexpectedOutput(String),
+    TooManyAttempts,
+}
+
+impl fmt::Display for SolveError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            SolveError::Vm(e) => write!(f, "{}", e),
+            SolveError::Script(e) => write!(f, "{}", e),
+            SolveError::Impossible(hulls) => {
+                write!(f, "no script gets across all of {} hulls", hulls.len())
+            }
+            SolveError::Compile(e) => write!(f, "{}", e),
+            SolveError::UnexpectedOutput(text) => write!(f, "unexpected output: {}", text),
+            SolveError::TooManyAttempts => {
+                write!(f, "no script found in {} attempts", MAX_ATTEMPTS)
+            }
+        }
+    }
+}
+
+impl std::error::Error for SolveError {}
+
+pub enum Attempt {
+    // The hull damage reported once the droid makes it across
+    Damage(i64),
+    // The hull it fell through
+    Fell(Vec<bool>),
+}
+
+// Runs a script on the springdroid
+pub fn execute(program: &[i64], script: &Script) -> Result<Attempt, SolveError> {
+    let mut vm = IntcodeVM::new(program);
+    let mut input = encode_ascii(&script.to_string())
+        .into_iter()
+        .collect::<VecDeque<_>>();
+    let mut output = Vec::new();
+
+    run(&mut vm, &mut input, &mut output).map_err(SolveError::Vm)?;
+
+    if let Some(&damage) = output.last().filter(|&&v| v > 127) {
+        return Ok(Attempt::Damage(damage));
+    }
+
+    let text = render_ascii(&output);
+
+    parse_failure(&text)
+        .map(Attempt::Fell)
+        .ok_or(SolveError::UnexpectedOutput(text))
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Solution {
+    pub script: Script,
+    pub formula: Formula,
+    pub damage: i64,
+    // The hulls the droid fell through on the way
+    pub hulls: Vec<Vec<bool>>,
+}
+
+// Finds a script without being told anything about the hulls: every time the droid falls,
+// the hull it fell through is added to the ones the next script has to get across.
+pub fn solve(program: &[i64], speed: Speed) -> Result<Solution, SolveError> {
+    let mut hulls = Vec::new();
+
+    for _ in 0..MAX_ATTEMPTS {
+        let decisions =
+            decide(&hulls, speed).ok_or_else(|| SolveError::Impossible(hulls.clone()))?;
+
+        let mut on = HashSet::new();
+        let mut off = HashSet::new();
+
+        for (&sensors, &jump) in decisions.iter() {
+            if jump {
+                on.insert(sensors);
+            } else {
+                off.insert(sensors);
+            }
+        }
+
+        let formula = minimise(speed.sensors(), &on, &off);
+        let script = compile(&formula, speed).map_err(SolveError::Compile)?;
+
+        match execute(program, &script)? {
+            Attempt::Damage(damage) => {
+                return Ok(Solution {
+                    script,
+                    formula,
+                    damage,
+                    hulls,
+                })
+            }
+            Attempt::Fell(hull) => hulls.push(hull),
+        }
+    }
+
+    Err(SolveError::TooManyAttempts)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn hull(text: &str) -> Vec<bool> {
+        text.chars().map(|c| c == '#').collect()
+    }
+
+    fn equivalent(formula: &Formula, script: &Script) -> bool {
+        (0..1 << script.speed.sensors()).all(|s| formula.eval(s) == script.jumps(s))
+    }
+
+    #[test]
+    fn parse_and_validate() {
+        let script = Script::parse("NOT A J\n\nNOT B T\nOR T J\nAND D J\nWALK\n").unwrap();
+
+        assert_eq!(script.instructions.len(), 4);
+        assert_eq!(
+            script.to_string(),
+            "NOT A J\nNOT B T\nOR T J\nAND D J\nWALK\n"
+        );
+
+        let error = |text: &str| Script::parse(text).unwrap_err();
+
+        assert_eq!(
+            error("NOT A J\nAND E J\nWALK").kind,
+            ScriptErrorKind::UnavailableSensor(Register::Sensor(4))
+        );
+        assert_eq!(error("NOT A J\nAND E J\nWALK").line, 2);
+        assert!(Script::parse("NOT A J\nAND E J\nRUN").is_ok());
+        assert_eq!(
+            error("AND A B\nRUN").kind,
+            ScriptErrorKind::ReadOnlyRegister(Register::Sensor(1))
+        );
+        assert_eq!(error("NOT A J").kind, ScriptErrorKind::MissingCommand);
+        assert_eq!(error("WALK\nNOT A J").kind, ScriptErrorKind::AfterCommand);
+        assert_eq!(
+            error("XOR A J\nWALK").kind,
+            ScriptErrorKind::UnknownInstruction("XOR".to_string())
+        );
+
+        let long = "NOT A J\n".repeat(16) + "WALK";
+        assert_eq!(error(&long).kind, ScriptErrorKind::TooManyInstructions(16));
+        assert_eq!(error(&long).line, 16);
+    }
+
+    #[test]
+    fn simulate() {
+        let script = Script::parse("NOT A J\nWALK").unwrap();
+
+        assert_eq!(script.simulate(&hull("#####.###")), Ok(()));
+        assert_eq!(script.simulate(&hull("#####.#..########")), Err(8));
+
+        let output = "Didn't make it across:\n\n....\n....\n@...\n##.#\n\n";
+        assert_eq!(parse_failure(output), Some(hull("##.#")));
+    }
+
+    #[test]
+    fn compiles_formulas() {
+        let formula = Formula::parse("!(A & B & C) & D").unwrap();
+        let script = compile(&formula, Speed::Walk).unwrap();
+
+        assert!(equivalent(&formula, &script));
+        assert_eq!(script.instructions.len(), 5);
+
+        let formula = Formula::parse("(!A | !B | !C) & D & (E | H)").unwrap();
+        let script = compile(&formula, Speed::Run).unwrap();
+
+        assert!(equivalent(&formula, &script));
+        assert_eq!(formula.to_string(), "(!A | !B | !C) & D & (E | H)");
+
+        assert_eq!(
+            compile(&formula, Speed::Walk),
+            Err(CompileError::UnavailableSensor(Register::Sensor(7)))
+        );
+        assert_eq!(Formula::parse("A & (B |").unwrap_err().position, 8);
+
+        let formula = Formula::parse("(A & D) | (B & D) | !C").unwrap();
+        assert_eq!(formula.factor().to_string(), "!C | (D & (A | B))");
+
+        for text in ["A", "!A", "!(A | !B) | C & !D", "(A & B) | (C & D)"].iter() {
+            let formula = Formula::parse(text).unwrap();
+            assert!(equivalent(
+                &formula,
+                &compile(&formula, Speed::Walk).unwrap()
+            ));
+        }
+    }
+
+    #[test]
+    fn minimises_with_dont_cares() {
+        let on = [0b0111, 0b1011].iter().copied().collect();
+        let off = [0b1111, 0b0110].iter().copied().collect();
+        let formula = minimise(4, &on, &off);
+
+        assert!(on.iter().all(|&s| formula.eval(s)));
+        assert!(off.iter().all(|&s| !formula.eval(s)));
+        assert_eq!(formula.to_string(), "!C | (A & !D)");
+    }
+}
@@ -0,0 +1,415 @@
+use std::collections::HashMap;
+use std::io::{self, Write};
+
+use crate::{decode, InputDevice, IntcodeVM, Memory, Op, OutputDevice, VMStatus, VmError};
+
+// Everything one executed instruction did
+#[derive(Debug, Clone, PartialEq)]
+pub struct TraceEvent {
+    pub address: usize,
+    pub op: Op,
+    // Parameter values as the instruction saw them, write targets as addresses
+    pub operands: Vec<i64>,
+    pub write: Option<(usize, i64)>,
+    pub input: Option<i64>,
+    pub output: Option<i64>,
+    // Where execution continues
+    pub next: usize,
+}
+
+pub trait Tracer {
+    // Called after each executed instruction, return false to stop the VM
+    fn trace(&mut self, event: &TraceEvent) -> bool;
+}
+
+impl<T: Tracer + ?Sized> Tracer for &mut T {
+    fn trace(&mut self, event: &TraceEvent) -> bool {
+        (**self).trace(event)
+    }
+}
+
+// Runs both tracers, stopping if either wants to
+impl<A: Tracer, B: Tracer> Tracer for (A, B) {
+    fn trace(&mut self, event: &TraceEvent) -> bool {
+        let a = self.0.trace(event);
+        let b = self.1.trace(event);
+
+        a && b
+    }
+}
+
+fn is_write_target(op: &Op, index: usize) -> bool {
+    match op {
+        Op::Add(..) | Op::Mult(..) | Op::LessThan(..) | Op::Equals(..) => index == 2,
+        Op::Input(_) => index == 0,
+        _ => false,
+    }
+}
+
+// Reads the parameters of the instruction at ip before it runs. Returns None for
+// anything that would fault, the step itself reports those.
+fn operands<M: Memory>(vm: &IntcodeVM<M>, op: &Op) -> Option<Vec<i64>> {
+    let ip = vm.instruction_ptr;
+
+    op.modes()
+        .into_iter()
+        .enumerate()
+        .map(|(i, mode)| {
+            let address = ip + 1 + i;
+
+            if is_write_target(op, i) {
+                vm.get_addr(mode, address).ok().map(|a| a as i64)
+            } else {
+                vm.get_value(mode, address).ok()
+            }
+        })
+        .collect()
+}
+
+// Passes output on while remembering it for the trace
+struct Watch<'a, O: ?Sized> {
+    device: &'a mut O,
+    value: Option<i64>,
+}
+
+impl<O: OutputDevice + ?Sized> OutputDevice for Watch<'_, O> {
+    fn output(&mut self, value: i64) {
+        self.value = Some(value);
+        self.device.output(value);
+    }
+}
+
+// Like run, but reports every executed instruction to the tracer. Returns
+// VMStatus::StepLimitReached if the tracer stopped the VM before the program did. Tracing
+// is kept out of run entirely, so untraced VMs don't pay for it.
+pub fn run_traced<M, I, O, T>(
+    vm: &mut IntcodeVM<M>,
+    input: &mut I,
+    output: &mut O,
+    tracer: &mut T,
+) -> Result<VMStatus, VmError>
+where
+    M: Memory,
+    I: InputDevice + ?Sized,
+    O: OutputDevice + ?Sized,
+    T: Tracer,
+{
+    loop {
+        let address = vm.instruction_ptr;
+        let decoded = decode(vm.memory.read(address), address)
+            .ok()
+            .and_then(|op| operands(vm, &op).map(|operands| (op, operands)));
+        let mut watch = Watch {
+            device: &mut *output,
+            value: None,
+        };
+
+        let status = crate::step(vm, input, &mut watch)?;
+
+        if status == VMStatus::EmptyInputBuffer {
+            return Ok(status);
+        }
+
+        // The step succeeded, so the instruction decoded
+        let (op, operands) = decoded.unwrap();
+
+        let write = (0..operands.len())
+            .find(|&i| is_write_target(&op, i))
+            .map(|i| operands[i] as usize)
+            .map(|target| (target, vm.memory.read(target)));
+
+        let event = TraceEvent {
+            address,
+            op,
+            input: match op {
+                Op::Input(_) => write.map(|(_, value)| value),
+                _ => None,
+            },
+            output: watch.value,
+            operands,
+            write,
+            next: vm.instruction_ptr,
+        };
+
+        let keep_going = tracer.trace(&event);
+
+        if status != VMStatus::Ok {
+            return Ok(status);
+        }
+
+        if !keep_going {
+            vm.status = VMStatus::StepLimitReached;
+            return Ok(VMStatus::StepLimitReached);
+        }
+    }
+}
+
+// Writes each event as a line of JSON. Every line has the same fields, with null for a
+// write, input or output the instruction didn't do. A line, wrapped here:
+//
+//     {"address":0,"op":"IN","operands":[5],"write":{"address":5,"value":3},
+//      "input":3,"output":null,"next":2}
+pub struct JsonTracer<W: Write> {
+    writer: W,
+    error: Option<io::Error>,
+}
+
+impl<W: Write> JsonTracer<W> {
+    pub fn new(writer: W) -> JsonTracer<W> {
+        JsonTracer {
+            writer,
+            error: None,
+        }
+    }
+
+    // The first write error, which also stopped the VM
+    pub fn error(&self) -> Option<&io::Error> {
+        self.error.as_ref()
+    }
+
+    pub fn into_inner(self) -> W {
+        self.writer
+    }
+}
+
+fn json_option(value: Option<i64>) -> String {
+    value.map_or("null".to_string(), |v| v.to_string())
+}
+
+impl<W: Write> Tracer for JsonTracer<W> {
+    fn trace(&mut self, event: &TraceEvent) -> bool {
+        let operands = event
+            .operands
+            .iter()
+            .map(|o| o.to_string())
+            .collect::<Vec<_>>()
+            .join(",");
+
+        let write = match event.write {
+            Some((address, value)) => format!("{{\"address\":{},\"value\":{}}}", address, value),
+            None => "null".to_string(),
+        };
+
+        let result = writeln!(
+            self.writer,
+            concat!(
+                "{{\"address\":{},\"op\":\"{}\",\"operands\":[{}],",
+                "\"write\":{},\"input\":{},\"output\":{},\"next\":{}}}"
+            ),
+            event.address,
+            event.op.mnemonic(),
+            operands,
+            write,
+            json_option(event.input),
+            json_option(event.output),
+            event.next
+        );
+
+        match result {
+            Ok(()) => true,
+            Err(e) => {
+                self.error = Some(e);
+                false
+            }
+        }
+    }
+}
+
+// Counts executions per address, and per backward jump to find the hot loops
+#[derive(Debug, Clone, Default)]
+pub struct Profiler {
+    hits: HashMap<usize, u64>,
+    back_edges: HashMap<(usize, usize), u64>,
+    total: u64,
+}
+
+impl Profiler {
+    pub fn new() -> Profiler {
+        Profiler::default()
+    }
+
+    pub fn total(&self) -> u64 {
+        self.total
+    }
+
+    pub fn hits(&self, address: usize) -> u64 {
+        self.hits.get(&address).copied().unwrap_or(0)
+    }
+
+    // The most executed addresses, most executed first
+    pub fn hottest(&self, count: usize) -> Vec<(usize, u64)> {
+        let mut hits = self.hits.iter().map(|(&a, &n)| (a, n)).collect::<Vec<_>>();
+        hits.sort_by(|a, b| b.1.cmp(&a.1).then(a.0.cmp(&b.0)));
+        hits.truncate(count);
+        hits
+    }
+
+    // Loops as (first address, jump address, iterations), most iterated first
+    pub fn loops(&self) -> Vec<(usize, usize, u64)> {
+        let mut loops = self
+            .back_edges
+            .iter()
+            .map(|(&(from, to), &n)| (to, from, n))
+            .collect::<Vec<_>>();
+        loops.sort_by(|a, b| b.2.cmp(&a.2).then(a.0.cmp(&b.0)));
+        loops
+    }
+
+    pub fn report(&self, count: usize) -> String {
+        let mut report = format!("{} instructions executed\n", self.total);
+        let percent = |n: u64| 100.0 * n as f64 / std::cmp::max(self.total, 1) as f64;
+
+        report += "hot instructions:\n";
+        for (address, n) in self.hottest(count) {
+            report += &format!("  {:>6}: {:>10} ({:.1}%)\n", address, n, percent(n));
+        }
+
+        report += "hot loops:\n";
+        for (start, end, n) in self.loops().into_iter().take(count) {
+            let body = (start..=end).map(|a| self.hits(a)).sum::<u64>();
+
+            report += &format!(
+                "  {:>6}..{:<6} {:>10} iterations ({:.1}% of instructions)\n",
+                start,
+                end,
+                n,
+                percent(body)
+            );
+        }
+
+        report
+    }
+}
+
+impl Tracer for Profiler {
+    fn trace(&mut self, event: &TraceEvent) -> bool {
+        *self.hits.entry(event.address).or_insert(0) += 1;
+        self.total += 1;
+
+        if event.next < event.address {
+            *self
+                .back_edges
+                .entry((event.address, event.next))
+                .or_insert(0) += 1;
+        }
+
+        true
+    }
+}
+
+// Stops the VM after a fixed number of instructions
+#[derive(Debug, Clone)]
+pub struct Limiter {
+    limit: u64,
+    count: u64,
+}
+
+impl Limiter {
+    pub fn new(limit: u64) -> Limiter {
+        Limiter { limit, count: 0 }
+    }
+
+    pub fn count(&self) -> u64 {
+        self.count
+    }
+
+    pub fn exhausted(&self) -> bool {
+        self.count >= self.limit
+    }
+}
+
+impl Tracer for Limiter {
+    fn trace(&mut self, _: &TraceEvent) -> bool {
+        self.count += 1;
+        !self.exhausted()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::assemble;
+    use std::collections::VecDeque;
+
+    fn traced<T: Tracer>(source: &str, input: &[i64], tracer: &mut T) -> VMStatus {
+        let mut vm = IntcodeVM::new(&assemble(source).unwrap());
+        let mut input_buffer: VecDeque<i64> = input.iter().copied().collect();
+
+        run_traced(&mut vm, &mut input_buffer, &mut VecDeque::new(), tracer).unwrap()
+    }
+
+    struct Recorder(Vec<TraceEvent>);
+
+    impl Tracer for Recorder {
+        fn trace(&mut self, event: &TraceEvent) -> bool {
+            self.0.push(event.clone());
+            true
+        }
+    }
+
+    #[test]
+    fn events() {
+        let mut recorder = Recorder(Vec::new());
+        let status = traced(
+            "ARB #10\nIN rb+2\nMUL rb+2, #3, [20]\nOUT [20]\nHLT",
+            &[5],
+            &mut recorder,
+        );
+
+        assert_eq!(status, VMStatus::Halted);
+        assert_eq!(recorder.0.len(), 5);
+
+        let events = &recorder.0;
+        assert_eq!((events[1].input, events[1].write), (Some(5), Some((12, 5))));
+        assert_eq!(events[2].operands, vec![5, 3, 20]);
+        assert_eq!(events[2].write, Some((20, 15)));
+        assert_eq!((events[3].output, events[3].next), (Some(15), 10));
+        assert_eq!(events[4].op, Op::Halt);
+    }
+
+    #[test]
+    fn json_lines() {
+        let mut tracer = JsonTracer::new(Vec::new());
+        traced("IN [5]\nOUT #7\nHLT", &[3], &mut tracer);
+
+        let text = String::from_utf8(tracer.into_inner()).unwrap();
+        let lines = text.lines().collect::<Vec<_>>();
+
+        assert_eq!(
+            lines[0],
+            concat!(
+                "{\"address\":0,\"op\":\"IN\",\"operands\":[5],",
+                "\"write\":{\"address\":5,\"value\":3},",
+                "\"input\":3,\"output\":null,\"next\":2}"
+            )
+        );
+        assert_eq!(lines.len(), 3);
+    }
+
+    #[test]
+    fn profiler_and_limiter() {
+        let countdown = "
+                    ADD #100, #0, [count]
+            loop:   ADD [count], #-1, [count]
+                    JT [count], #loop
+                    HLT
+            count:  data 0
+        ";
+
+        let mut profiler = Profiler::new();
+        traced(countdown, &[], &mut profiler);
+
+        assert_eq!(profiler.total(), 202);
+        assert_eq!(profiler.hits(4), 100);
+        assert_eq!(profiler.loops(), vec![(4, 8, 99)]);
+
+        let mut tracers = (Limiter::new(10), Profiler::new());
+
+        assert_eq!(
+            traced(countdown, &[], &mut tracers),
+            VMStatus::StepLimitReached
+        );
+        assert!(tracers.0.exhausted());
+        assert_eq!(tracers.1.total(), 10);
+    }
+}
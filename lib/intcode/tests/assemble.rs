@@ -0,0 +1,36 @@
+// Disassembles every intcode program in inputs/ and checks that assembling the listing
+// gives back the original program.
+
+use std::fs;
+use std::path::PathBuf;
+
+use intcode::*;
+
+#[test]
+fn puzzle_inputs_round_trip() {
+    let directory = &mut PathBuf::from(env!("CARGO_MANIFEST_DIR"));
+    directory.push("../../inputs");
+
+    let mut count = 0;
+
+    for entry in fs::read_dir(directory).expect("Unable to open inputs") {
+        let path = entry.unwrap().path();
+        let input = fs::read_to_string(&path).expect("Unable to open file");
+
+        let program = match parse_program(&input) {
+            Ok(program) if program.len() > 1 => program,
+            _ => continue,
+        };
+
+        let source = disassemble(&program)
+            .iter()
+            .map(|line| line.source())
+            .collect::<Vec<_>>()
+            .join("\n");
+
+        assert_eq!(assemble(&source), Ok(program), "{}", path.display());
+        count += 1;
+    }
+
+    assert!(count > 0);
+}
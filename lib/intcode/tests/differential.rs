@@ -0,0 +1,165 @@
+// Runs every intcode program in inputs/ through both execution engines and checks that
+// they agree on every status, output and memory word.
+
+use std::collections::VecDeque;
+use std::fs;
+use std::path::PathBuf;
+
+use intcode::*;
+
+const MAX_ROUNDS: usize = 500;
+
+fn load_programs() -> Vec<(String, Vec<i64>)> {
+    let directory = &mut PathBuf::from(env!("CARGO_MANIFEST_DIR"));
+    directory.push("../../inputs");
+
+    let mut programs = Vec::new();
+
+    for entry in fs::read_dir(directory).expect("Unable to open inputs") {
+        let path = entry.unwrap().path();
+        let input = fs::read_to_string(&path).expect("Unable to open file");
+
+        let program = input
+            .trim()
+            .split(',')
+            .map(|x| x.parse::<i64>())
+            .collect::<Result<Vec<_>, _>>();
+
+        if let Ok(program) = program {
+            if program.len() > 1 {
+                programs.push((path.file_name().unwrap().to_string_lossy().into(), program));
+            }
+        }
+    }
+
+    programs.sort();
+    programs
+}
+
+// Deterministic input values, a mix of the small numbers most puzzles expect and
+// printable ASCII for the text based ones
+fn input_value(seed: u64, round: usize) -> i64 {
+    let x = seed
+        .wrapping_mul(6364136223846793005)
+        .wrapping_add(round as u64)
+        .wrapping_mul(1442695040888963407);
+
+    match seed % 3 {
+        0 => (x >> 33) as i64 % 5,
+        1 => 32 + (x >> 33) as i64 % 95,
+        _ => (x >> 33) as i64 % 100 - 1,
+    }
+}
+
+fn assert_same_memory(name: &str, a: &IntcodeVM, b: &IntcodeVM) {
+    let len = std::cmp::max(a.memory().len(), b.memory().len());
+
+    for address in 0..len {
+        assert_eq!(
+            a.memory().read(address),
+            b.memory().read(address),
+            "{}: memory differs at address {}",
+            name,
+            address
+        );
+    }
+}
+
+fn compare(name: &str, program: &[i64], initial_input: &[i64], seed: u64) {
+    let mut interpreter = IntcodeVM::new(program);
+    let mut legacy = IntcodeVM::new(program);
+    legacy.set_engine(EngineKind::Legacy);
+
+    let mut input_buffer = initial_input.iter().copied().collect::<VecDeque<_>>();
+
+    for round in 0..MAX_ROUNDS {
+        let mut interpreter_input = input_buffer.clone();
+        let mut interpreter_output = VecDeque::new();
+        let interpreter_status = run(
+            &mut interpreter,
+            &mut interpreter_input,
+            &mut interpreter_output,
+        );
+
+        let mut legacy_output = VecDeque::new();
+        let legacy_status = run(&mut legacy, &mut input_buffer, &mut legacy_output);
+
+        let context = format!("{} (seed {}, round {})", name, seed, round);
+
+        assert_eq!(interpreter_status, legacy_status, "{}: status", context);
+        assert_eq!(interpreter_output, legacy_output, "{}: output", context);
+        assert_eq!(interpreter_input, input_buffer, "{}: input", context);
+        assert_eq!(
+            interpreter.instruction_ptr(),
+            legacy.instruction_ptr(),
+            "{}",
+            context
+        );
+        assert_eq!(
+            interpreter.relative_base(),
+            legacy.relative_base(),
+            "{}",
+            context
+        );
+        assert_same_memory(&context, &interpreter, &legacy);
+
+        if interpreter_status != Ok(VMStatus::EmptyInputBuffer) {
+            return;
+        }
+
+        input_buffer.push_back(input_value(seed, round));
+    }
+}
+
+#[test]
+fn engines_agree_on_all_inputs() {
+    let programs = load_programs();
+
+    assert!(!programs.is_empty());
+
+    for (name, program) in programs.iter() {
+        for seed in 0..3 {
+            compare(name, program, &[], seed);
+        }
+    }
+}
+
+#[test]
+fn engines_agree_on_puzzle_inputs() {
+    let cases: &[(&str, &[i64])] = &[
+        ("5.txt", &[1]),
+        ("5.txt", &[5]),
+        ("7_1.txt", &[3, 0]),
+        ("7_2.txt", &[9, 0]),
+        ("9_1.txt", &[1]),
+        ("9_1.txt", &[2]),
+        ("19.txt", &[20, 30]),
+        ("23.txt", &[7, -1]),
+    ];
+
+    let programs = load_programs();
+
+    for (name, input) in cases.iter() {
+        let (_, program) = programs.iter().find(|(n, _)| n == name).unwrap();
+
+        compare(name, program, input, 0);
+    }
+}
+
+#[test]
+fn engines_agree_on_day_2() {
+    let (_, program) = load_programs()
+        .into_iter()
+        .find(|(name, _)| name == "2.txt")
+        .unwrap();
+
+    for noun in 0..10 {
+        for verb in 0..10 {
+            let mut program = program.clone();
+            program[1] = noun;
+            program[2] = verb;
+
+            compare("2.txt", &program, &[], 0);
+        }
+    }
+}
@@ -0,0 +1,288 @@
+// Shortest paths over implicit graphs. A graph is a start state and a closure giving the
+// states reachable from a state, so anything hashable works as a node: a position, a
+// position with a level, a set of robots with the keys they carry...
+
+use std::cmp::Reverse;
+use std::collections::hash_map::Entry;
+use std::collections::{BinaryHeap, HashMap, VecDeque};
+use std::hash::Hash;
+use std::ops::Add;
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Path<S, C> {
+    pub cost: C,
+    // From the start to the goal, both included
+    pub states: Vec<S>,
+}
+
+#[derive(Debug, Default, Clone, Copy, PartialEq)]
+pub struct Stats {
+    // States taken off the queue and expanded
+    pub visited: usize,
+    // Distinct states reached
+    pub discovered: usize,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct SearchResult<S, C> {
+    // None if no goal state could be reached
+    pub path: Option<Path<S, C>>,
+    pub stats: Stats,
+}
+
+// The states found so far, each with the cost to reach it and the state it was reached from
+struct Explored<S, C> {
+    indices: HashMap<S, usize>,
+    states: Vec<(S, C, Option<usize>)>,
+}
+
+impl<S: Clone + Eq + Hash, C: Copy + Ord> Explored<S, C> {
+    fn new(start: S, zero: C) -> Explored<S, C> {
+        let mut indices = HashMap::new();
+        indices.insert(start.clone(), 0);
+
+        Explored {
+            indices,
+            states: vec![(start, zero, None)],
+        }
+    }
+
+    // The index of the state if it's new or now reached cheaper
+    fn relax(&mut self, state: S, cost: C, parent: usize) -> Option<usize> {
+        match self.indices.entry(state) {
+            Entry::Occupied(entry) => {
+                let index = *entry.get();
+                let known = &mut self.states[index];
+
+                if cost < known.1 {
+                    known.1 = cost;
+                    known.2 = Some(parent);
+                    Some(index)
+                } else {
+                    None
+                }
+            }
+            Entry::Vacant(entry) => {
+                let index = self.states.len();
+                self.states.push((entry.key().clone(), cost, Some(parent)));
+                entry.insert(index);
+                Some(index)
+            }
+        }
+    }
+
+    fn path(&self, goal: usize) -> Path<S, C> {
+        let mut states = Vec::new();
+        let mut index = Some(goal);
+
+        while let Some(i) = index {
+            states.push(self.states[i].0.clone());
+            index = self.states[i].2;
+        }
+
+        states.reverse();
+
+        Path {
+            cost: self.states[goal].1,
+            states,
+        }
+    }
+
+    fn result(&self, goal: Option<usize>, visited: usize) -> SearchResult<S, C> {
+        SearchResult {
+            path: goal.map(|g| self.path(g)),
+            stats: Stats {
+                visited,
+                discovered: self.states.len(),
+            },
+        }
+    }
+}
+
+// Fewest steps to a goal when every step costs the same
+pub fn bfs<S, F, I, G>(start: S, mut successors: F, mut goal: G) -> SearchResult<S, usize>
+where
+    S: Clone + Eq + Hash,
+    F: FnMut(&S) -> I,
+    I: IntoIterator<Item = S>,
+    G: FnMut(&S) -> bool,
+{
+    let mut explored = Explored::new(start, 0);
+    let mut queue = VecDeque::new();
+    let mut visited = 0;
+
+    queue.push_back(0);
+
+    while let Some(index) = queue.pop_front() {
+        let (state, steps, _) = explored.states[index].clone();
+        visited += 1;
+
+        if goal(&state) {
+            return explored.result(Some(index), visited);
+        }
+
+        for next in successors(&state) {
+            if explored.indices.contains_key(&next) {
+                continue;
+            }
+
+            if let Some(next) = explored.relax(next, steps + 1, index) {
+                queue.push_back(next);
+            }
+        }
+    }
+
+    explored.result(None, visited)
+}
+
+// Cheapest path to a goal with non-negative step costs
+pub fn dijkstra<S, C, F, I, G>(start: S, successors: F, goal: G) -> SearchResult<S, C>
+where
+    S: Clone + Eq + Hash,
+    C: Copy + Ord + Default + Add<Output = C>,
+    F: FnMut(&S) -> I,
+    I: IntoIterator<Item = (S, C)>,
+    G: FnMut(&S) -> bool,
+{
+    astar(start, successors, |_| C::default(), goal)
+}
+
+// Dijkstra guided by an estimate of the remaining cost. The estimate must never be more
+// than the real cost for the path found to be the cheapest.
+pub fn astar<S, C, F, I, H, G>(
+    start: S,
+    mut successors: F,
+    mut heuristic: H,
+    mut goal: G,
+) -> SearchResult<S, C>
+where
+    S: Clone + Eq + Hash,
+    C: Copy + Ord + Default + Add<Output = C>,
+    F: FnMut(&S) -> I,
+    I: IntoIterator<Item = (S, C)>,
+    H: FnMut(&S) -> C,
+    G: FnMut(&S) -> bool,
+{
+    let mut queue = BinaryHeap::new();
+    let mut visited = 0;
+
+    queue.push(Reverse((heuristic(&start), C::default(), 0)));
+
+    let mut explored = Explored::new(start, C::default());
+
+    while let Some(Reverse((_, cost, index))) = queue.pop() {
+        // A cheaper way here was found after this one was queued
+        if cost > explored.states[index].1 {
+            continue;
+        }
+
+        let state = explored.states[index].0.clone();
+        visited += 1;
+
+        if goal(&state) {
+            return explored.result(Some(index), visited);
+        }
+
+        for (next, step) in successors(&state) {
+            let next_cost = cost + step;
+            let estimate = next_cost + heuristic(&next);
+
+            if let Some(next) = explored.relax(next, next_cost, index) {
+                queue.push(Reverse((estimate, next_cost, next)));
+            }
+        }
+    }
+
+    explored.result(None, visited)
+}
+
+// The fewest steps to every reachable state
+pub fn flood_fill<S, F, I>(start: S, mut successors: F) -> HashMap<S, usize>
+where
+    S: Clone + Eq + Hash,
+    F: FnMut(&S) -> I,
+    I: IntoIterator<Item = S>,
+{
+    let mut distances = HashMap::new();
+    let mut queue = VecDeque::new();
+
+    distances.insert(start.clone(), 0);
+    queue.push_back(start);
+
+    while let Some(state) = queue.pop_front() {
+        let steps = distances[&state];
+
+        for next in successors(&state) {
+            if let Entry::Vacant(entry) = distances.entry(next) {
+                queue.push_back(entry.key().clone());
+                entry.insert(steps + 1);
+            }
+        }
+    }
+
+    distances
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // A 5x5 room with a wall from (2, 0) to (2, 3)
+    fn open(&(x, y): &(i32, i32)) -> bool {
+        (0..5).contains(&x) && (0..5).contains(&y) && !(x == 2 && y < 4)
+    }
+
+    fn neighbours(&(x, y): &(i32, i32)) -> Vec<(i32, i32)> {
+        vec![(x + 1, y), (x - 1, y), (x, y + 1), (x, y - 1)]
+            .into_iter()
+            .filter(open)
+            .collect()
+    }
+
+    #[test]
+    fn shortest_paths() {
+        let found = bfs((0, 0), neighbours, |&p| p == (4, 0));
+        let path = found.path.unwrap();
+
+        assert_eq!(path.cost, 12);
+        assert_eq!(path.states.len(), 13);
+        assert_eq!(path.states[0], (0, 0));
+        assert_eq!(path.states[6], (2, 4));
+        assert!(found.stats.visited <= found.stats.discovered);
+
+        let weighted = |p: &(i32, i32)| neighbours(p).into_iter().map(|n| (n, 1u32));
+        let dijkstra = dijkstra((0, 0), weighted, |&p| p == (4, 0));
+        let manhattan = |&(x, y): &(i32, i32)| ((4 - x).abs() + y.abs()) as u32;
+        let astar = astar((0, 0), weighted, manhattan, |&p| p == (4, 0));
+
+        assert_eq!(dijkstra.path.unwrap().cost, 12);
+        assert_eq!(astar.path.unwrap().cost, 12);
+        assert!(astar.stats.visited <= dijkstra.stats.visited);
+
+        assert_eq!(bfs((0, 0), neighbours, |&p| p == (2, 0)).path, None);
+    }
+
+    #[test]
+    fn weights() {
+        // Going around through b is cheaper than the direct edge
+        let edges = |&node: &char| match node {
+            'a' => vec![('c', 10), ('b', 3)],
+            'b' => vec![('c', 4)],
+            _ => vec![],
+        };
+
+        let path = dijkstra('a', edges, |&n| n == 'c').path.unwrap();
+
+        assert_eq!(path.cost, 7);
+        assert_eq!(path.states, vec!['a', 'b', 'c']);
+    }
+
+    #[test]
+    fn flood() {
+        let distances = flood_fill((0, 0), neighbours);
+
+        assert_eq!(distances.len(), 21);
+        assert_eq!(distances[&(4, 0)], 12);
+        assert_eq!(distances.values().max(), Some(&12));
+    }
+}
@@ -0,0 +1,297 @@
+// The expected answers, read from a manifest with a table for each day:
+//
+//     [8]
+//     part1 = 2975
+//     part2 = """
+//     ####.#..#
+//     ..."""
+//
+// Only the parts of TOML a manifest needs are understood: tables named by the day, and
+// integers, strings and multi-line strings as answers.
+
+use std::collections::HashMap;
+use std::error::Error;
+use std::fmt;
+use std::fs;
+use std::io;
+use std::path::Path;
+
+use crate::solution::Answer;
+
+#[derive(Debug)]
+pub enum AnswersError {
+    Read(io::Error),
+    Parse { line: usize, message: String },
+}
+
+impl fmt::Display for AnswersError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            AnswersError::Read(e) => write!(f, "Unable to read the answers: {}", e),
+            AnswersError::Parse { line, message } => write!(f, "Line {}: {}", line, message),
+        }
+    }
+}
+
+impl Error for AnswersError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match self {
+            AnswersError::Read(e) => Some(e),
+            _ => None,
+        }
+    }
+}
+
+// How an answer compares to the expected one
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Check {
+    Pass,
+    Fail { expected: Answer },
+    Missing,
+}
+
+#[derive(Debug, Default, Clone, PartialEq)]
+pub struct Answers {
+    expected: HashMap<(u32, u32), Answer>,
+}
+
+fn parse_error(line: usize, message: &str) -> AnswersError {
+    AnswersError::Parse {
+        line,
+        message: message.to_string(),
+    }
+}
+
+fn unescape(text: &str, line: usize) -> Result<String, AnswersError> {
+    let mut unescaped = String::new();
+    let mut chars = text.chars();
+
+    while let Some(c) = chars.next() {
+        if c != '\\' {
+            unescaped.push(c);
+            continue;
+        }
+
+        match chars.next() {
+            Some('n') => unescaped.push('\n'),
+            Some('t') => unescaped.push('\t'),
+            Some('"') => unescaped.push('"'),
+            Some('\\') => unescaped.push('\\'),
+            _ => return Err(parse_error(line, "Unknown escape in string")),
+        }
+    }
+
+    Ok(unescaped)
+}
+
+// The text before a comment, for the lines that can't have a # in a string
+fn uncommented(line: &str) -> &str {
+    line.split('#').next().unwrap().trim()
+}
+
+impl Answers {
+    pub fn new() -> Answers {
+        Answers::default()
+    }
+
+    pub fn parse(text: &str) -> Result<Answers, AnswersError> {
+        let mut answers = Answers::new();
+        let mut day = None;
+        let mut lines = text.lines().enumerate().map(|(i, line)| (i + 1, line));
+
+        while let Some((number, line)) = lines.next() {
+            let line = line.trim();
+
+            if line.is_empty() || line.starts_with('#') {
+                continue;
+            }
+
+            if line.starts_with('[') {
+                let table = uncommented(line);
+                let name = table
+                    .strip_prefix('[')
+                    .and_then(|t| t.strip_suffix(']'))
+                    .ok_or_else(|| parse_error(number, "Unclosed table"))?;
+
+                day = Some(
+                    name.trim()
+                        .parse::<u32>()
+                        .map_err(|_| parse_error(number, "Tables are named by the day"))?,
+                );
+
+                continue;
+            }
+
+            let (key, value) = line
+                .split_once('=')
+                .ok_or_else(|| parse_error(number, "Expected key = value"))?;
+
+            let day = day.ok_or_else(|| parse_error(number, "Answer outside of a day"))?;
+            let part = match key.trim() {
+                "part1" => 1,
+                "part2" => 2,
+                _ => return Err(parse_error(number, "The keys are part1 and part2")),
+            };
+
+            let value = value.trim();
+
+            let answer = if let Some(rest) = value.strip_prefix("\"\"\"") {
+                // A newline right after the opening quotes isn't part of the string
+                let mut text = String::new();
+                let mut rest = rest.to_string();
+
+                if rest.is_empty() {
+                    let (_, next) = lines
+                        .next()
+                        .ok_or_else(|| parse_error(number, "Unclosed string"))?;
+                    rest = next.to_string();
+                }
+
+                loop {
+                    if let Some(end) = rest.find("\"\"\"") {
+                        text.push_str(&rest[..end]);
+                        break;
+                    }
+
+                    text.push_str(&rest);
+                    text.push('\n');
+
+                    let (_, next) = lines
+                        .next()
+                        .ok_or_else(|| parse_error(number, "Unclosed string"))?;
+                    rest = next.to_string();
+                }
+
+                Answer::Text(unescape(&text, number)?)
+            } else if let Some(rest) = value.strip_prefix('"') {
+                let end = rest
+                    .rfind('"')
+                    .ok_or_else(|| parse_error(number, "Unclosed string"))?;
+
+                Answer::Text(unescape(&rest[..end], number)?)
+            } else {
+                let value = uncommented(value).replace('_', "");
+
+                Answer::Number(
+                    value
+                        .parse()
+                        .map_err(|_| parse_error(number, "Expected an integer or a string"))?,
+                )
+            };
+
+            if answers.expected.insert((day, part), answer).is_some() {
+                return Err(parse_error(number, "Answer given twice"));
+            }
+        }
+
+        Ok(answers)
+    }
+
+    pub fn load(path: &Path) -> Result<Answers, AnswersError> {
+        let text = fs::read_to_string(path).map_err(AnswersError::Read)?;
+
+        Answers::parse(&text)
+    }
+
+    pub fn get(&self, day: u32, part: u32) -> Option<&Answer> {
+        self.expected.get(&(day, part))
+    }
+
+    pub fn len(&self) -> usize {
+        self.expected.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.expected.is_empty()
+    }
+
+    // Answers are compared as they are printed, so a number may be given as a string and
+    // a picture may end with a newline. A picture may be given by its letters or its
+    // pixels. A part without a puzzle needs no answer.
+    pub fn check(&self, day: u32, part: u32, answer: &Answer) -> Check {
+        let matches = |expected: &Answer| {
+            let expected = expected.to_string();
+            let expected = expected.trim_end();
+
+            expected == answer.to_string().trim_end()
+                || answer.rendered().is_some_and(|pixels| expected == pixels)
+        };
+
+        match self.get(day, part) {
+            None if *answer == Answer::None => Check::Pass,
+            Some(expected) if matches(expected) => Check::Pass,
+            Some(expected) => Check::Fail {
+                expected: expected.clone(),
+            },
+            None => Check::Missing,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use grid::Grid;
+
+    const MANIFEST: &str = r#"
+# Expected answers
+[1]
+part1 = 3_271_095 # fuel
+part2 = "4903759"
+
+[8]
+part2 = """
+#..#
+####"""
+
+[ 16 ]
+part1 = "0123\"4\\"
+"#;
+
+    #[test]
+    fn parse() {
+        let answers = Answers::parse(MANIFEST).unwrap();
+        let text = |s: &str| Some(Answer::Text(s.to_string()));
+
+        assert_eq!(answers.len(), 4);
+        assert_eq!(answers.get(1, 1), Some(&Answer::Number(3271095)));
+        assert_eq!(answers.get(1, 2).cloned(), text("4903759"));
+        assert_eq!(answers.get(8, 1), None);
+        assert_eq!(answers.get(8, 2).cloned(), text("#..#\n####"));
+        assert_eq!(answers.get(16, 1).cloned(), text("0123\"4\\"));
+    }
+
+    #[test]
+    fn check() {
+        let answers = Answers::parse(MANIFEST).unwrap();
+        let pixels = Grid::parse("#..#\n####\n", |c| Some(c == '#')).unwrap();
+
+        assert_eq!(answers.check(1, 1, &Answer::Number(3271095)), Check::Pass);
+        assert_eq!(answers.check(1, 2, &Answer::Number(4903759)), Check::Pass);
+        assert_eq!(answers.check(8, 2, &"#..#\n####\n".into()), Check::Pass);
+        assert_eq!(answers.check(8, 2, &Answer::picture(pixels)), Check::Pass);
+        assert_eq!(
+            answers.check(1, 1, &Answer::Number(1)),
+            Check::Fail {
+                expected: Answer::Number(3271095)
+            }
+        );
+        assert_eq!(answers.check(2, 1, &Answer::Number(1)), Check::Missing);
+        assert_eq!(answers.check(25, 2, &Answer::None), Check::Pass);
+    }
+
+    #[test]
+    fn errors() {
+        let line = |text| match Answers::parse(text) {
+            Err(AnswersError::Parse { line, .. }) => line,
+            other => panic!("Expected a parse error, got {:?}", other),
+        };
+
+        assert_eq!(line("part1 = 1"), 1);
+        assert_eq!(line("[1]\npart3 = 1"), 2);
+        assert_eq!(line("[1]\npart1 = 1\npart1 = 2"), 3);
+        assert_eq!(line("[1]\npart1 = one"), 2);
+        assert_eq!(line("[one]"), 1);
+        assert_eq!(line("[1]\npart2 = \"\"\"\n#..#"), 2);
+    }
+}
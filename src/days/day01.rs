@@ -1,8 +1,6 @@
-use std::env;
-use std::fs;
-use std::path::PathBuf;
+use crate::solution::*;
 
-fn get_fuel1(masses: &Vec<i32>) -> i32 {
+fn get_fuel1(masses: &[i32]) -> i32 {
     let mut sum = 0;
 
     for mass in masses.iter() {
@@ -16,13 +14,13 @@ fn get_fuel2_helper(mass: i32) -> i32{
     let fuel = std::cmp::max(mass / 3 - 2, 0);
 
     if fuel > 0 {
-        return fuel + get_fuel2_helper(fuel);
+        fuel + get_fuel2_helper(fuel)
     } else {
-        return fuel;
+        fuel
     }
 }
 
-fn get_fuel2(masses: &Vec<i32>) -> i32 {
+fn get_fuel2(masses: &[i32]) -> i32 {
     let mut sum = 0;
 
     for mass in masses.iter() {
@@ -32,26 +30,23 @@ fn get_fuel2(masses: &Vec<i32>) -> i32 {
     sum
 }
 
-fn main() {
-    let filename = &mut PathBuf::from(env!("CARGO_MANIFEST_DIR"));
-    filename.push("inputs/1.txt");
-
-    println!("Reading {}", filename.display());
-
-    let input = fs::read_to_string(filename).expect("Unable to open file");
-
-    let input = input
-    .split("\n")
-    .map(|x| x.parse::<i32>().unwrap())
-    .collect::<Vec<_>>();
-
-    let sum = get_fuel1(&input);
+fn parse_masses(input: &str) -> Vec<i32> {
+    input
+        .lines()
+        .map(|x| x.parse::<i32>().expect("Invalid mass"))
+        .collect()
+}
 
-    println!("Part 1: {}", sum);
+pub struct Day01;
 
-    let sum = get_fuel2(&input);
+impl Solution for Day01 {
+    fn part1(input: &str) -> Answer {
+        get_fuel1(&parse_masses(input)).into()
+    }
 
-    println!("Part 2: {}", sum);
+    fn part2(input: &str) -> Answer {
+        get_fuel2(&parse_masses(input)).into()
+    }
 }
 
 #[cfg(test)]
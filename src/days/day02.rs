@@ -0,0 +1,70 @@
+use crate::solution::*;
+
+fn run(mut program: Vec<usize>) -> usize {
+    let mut stackptr: usize = 0;
+
+    while stackptr < program.len() {
+        if program[stackptr] != 99 {
+            let instr = program[stackptr];
+            let a = program[stackptr + 1];
+            let b = program[stackptr + 2];
+            let c = program[stackptr + 3];
+
+            let res;
+
+            match instr {
+                1 => res = program[a] + program[b],
+                2 => res = program[a] * program[b],
+
+                _ => panic!(),
+            }
+            program[c] = res;
+
+            stackptr += 4;
+        } else {
+            break;
+        }
+    }
+
+    program[0]
+}
+
+fn parse_program(input: &str) -> Vec<usize> {
+    input
+        .trim()
+        .split(",")
+        .map(|x| x.parse::<usize>().unwrap())
+        .collect::<Vec<_>>()
+}
+
+pub struct Day02;
+
+impl Solution for Day02 {
+    fn part1(input: &str) -> Answer {
+        let program = &mut parse_program(input);
+
+        program[1] = 12;
+        program[2] = 2;
+
+        run(program.clone()).into()
+    }
+
+    fn part2(input: &str) -> Answer {
+        let program = &mut parse_program(input);
+
+        for noun in 0..99 {
+            for verb in 0..99 {
+                program[1] = noun;
+                program[2] = verb;
+
+                let res = run(program.clone());
+
+                if res == 19690720 {
+                    return (100 * noun + verb).into();
+                }
+            }
+        }
+
+        panic!("No noun and verb give 19690720");
+    }
+}
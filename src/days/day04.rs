@@ -0,0 +1,139 @@
+use crate::solution::*;
+
+// The puzzle input is the range of passwords, like 382345-843167
+fn parse_range(input: &str) -> (u32, u32) {
+    let bounds = input
+        .trim()
+        .split("-")
+        .map(|x| x.parse::<u32>().expect("Invalid range"))
+        .collect::<Vec<_>>();
+
+    (bounds[0], bounds[1])
+}
+
+pub struct Day04;
+
+impl Solution for Day04 {
+    fn part1(input: &str) -> Answer {
+        let (lower, upper) = parse_range(input);
+
+        let mut k = 0;
+
+        for num in lower..upper {
+            let mut double = false;
+            let mut non_dec = true;
+
+            {
+                let mut prev = num
+                    .to_string()
+                    .chars()
+                    .next()
+                    .unwrap()
+                    .to_digit(10)
+                    .unwrap();
+
+                for c in num.to_string().chars().skip(1) {
+                    let d = c.to_digit(10).unwrap();
+                    if d == prev {
+                        double = true;
+                        break;
+                    } else {
+                        prev = d;
+                    }
+                }
+            }
+
+            {
+                let mut prev = num
+                    .to_string()
+                    .chars()
+                    .next()
+                    .unwrap()
+                    .to_digit(10)
+                    .unwrap();
+
+                for c in num.to_string().chars().skip(1) {
+                    let d = c.to_digit(10).unwrap();
+                    if d < prev {
+                        non_dec = false;
+                        break;
+                    } else {
+                        prev = d;
+                    }
+                }
+            }
+
+            if non_dec && double {
+                k += 1;
+            }
+        }
+
+        k.into()
+    }
+
+    fn part2(input: &str) -> Answer {
+        let (lower, upper) = parse_range(input);
+
+        let mut k = 0;
+
+        for num in lower..upper {
+            let mut double = false;
+            let mut non_dec = true;
+
+            {
+                let mut prev = num
+                    .to_string()
+                    .chars()
+                    .next()
+                    .unwrap()
+                    .to_digit(10)
+                    .unwrap();
+
+                let mut oc = 1;
+
+                for c in num.to_string().chars().skip(1) {
+                    let d = c.to_digit(10).unwrap();
+                    if d == prev {
+                        oc += 1;
+                    } else {
+                        if oc == 2 {
+                            double = true;
+                            break;
+                        }
+                        oc = 1;
+                    }
+                    prev = d;
+                }
+                if oc == 2 {
+                    double = true;
+                }
+            }
+
+            {
+                let mut prev = num
+                    .to_string()
+                    .chars()
+                    .next()
+                    .unwrap()
+                    .to_digit(10)
+                    .unwrap();
+
+                for c in num.to_string().chars().skip(1) {
+                    let d = c.to_digit(10).unwrap();
+                    if d < prev {
+                        non_dec = false;
+                        break;
+                    } else {
+                        prev = d;
+                    }
+                }
+            }
+
+            if non_dec && double {
+                k += 1;
+            }
+        }
+
+        k.into()
+    }
+}
@@ -0,0 +1,30 @@
+use std::collections::VecDeque;
+
+use intcode::*;
+
+use crate::solution::*;
+
+// Runs the diagnostic program for a system and returns the diagnostic code, the last output
+fn diagnose(input: &str, system: i64) -> i64 {
+    let program = parse_program(input).expect("Invalid intcode program");
+
+    let mut input_buffer = vec![system].into_iter().collect::<VecDeque<_>>();
+    let mut output_buffer = VecDeque::new();
+    let mut vm = IntcodeVM::new(&program);
+
+    run(&mut vm, &mut input_buffer, &mut output_buffer).expect("Intcode program failed");
+
+    *output_buffer.back().expect("No diagnostic code")
+}
+
+pub struct Day05;
+
+impl Solution for Day05 {
+    fn part1(input: &str) -> Answer {
+        diagnose(input, 1).into()
+    }
+
+    fn part2(input: &str) -> Answer {
+        diagnose(input, 5).into()
+    }
+}
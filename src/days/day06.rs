@@ -0,0 +1,86 @@
+use std::collections::HashMap;
+
+use crate::solution::*;
+
+fn parse_orbits(input: &str) -> Vec<Vec<&str>> {
+    input.lines().map(|l| l.split(")").collect()).collect()
+}
+
+fn n_orbits<'a>(body: &str, orbits: &HashMap<&'a str, Vec<&'a str>>) -> Vec<&'a str> {
+    let stack = &mut vec![(body, 0)];
+    let mut hierarchy = Vec::new();
+
+    while let Some((n, c)) = stack.pop() {
+        if let Some(satellites) = orbits.get(n) {
+            for &s in satellites {
+                stack.push((s, c + 1));
+                hierarchy.push(s);
+            }
+        }
+    }
+
+    hierarchy
+}
+
+pub struct Day06;
+
+impl Solution for Day06 {
+    fn part1(input: &str) -> Answer {
+        let orbits = &mut HashMap::<&str, Vec<&str>>::new();
+
+        for orbit in parse_orbits(input) {
+            if let [l, r] = orbit[..] {
+                if let Some(v) = orbits.get_mut(l) {
+                    v.push(r);
+                } else {
+                    orbits.insert(l, vec![r]);
+                }
+            } else {
+                panic!("Invalid input");
+            }
+        }
+
+        let stack = &mut vec![("COM", 0)];
+        let mut cntr = 0;
+
+        while let Some((n, c)) = stack.pop() {
+            if let Some(satellites) = orbits.get(n) {
+                for &s in satellites {
+                    stack.push((s, c + 1));
+                    cntr += c + 1;
+                }
+            }
+        }
+
+        cntr.into()
+    }
+
+    fn part2(input: &str) -> Answer {
+        let orbits = &mut HashMap::<&str, Vec<&str>>::new();
+
+        for orbit in parse_orbits(input) {
+            if let [l, r] = orbit[..] {
+                if let Some(v) = orbits.get_mut(r) {
+                    v.push(l);
+                } else {
+                    orbits.insert(r, vec![l]);
+                }
+            } else {
+                panic!("Invalid input: {:?}", orbit);
+            }
+        }
+
+        let my_hierarchy = n_orbits("YOU", orbits);
+        let san_hierarchy = n_orbits("SAN", orbits);
+
+        let mut min_dist = usize::MAX;
+
+        for (i, &body) in my_hierarchy.iter().enumerate() {
+            if let Some(j) = san_hierarchy.iter().position(|&x| x == body) {
+                min_dist = std::cmp::min(min_dist, i + j);
+            }
+        }
+
+        min_dist.into()
+    }
+}
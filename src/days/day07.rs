@@ -0,0 +1,32 @@
+use std::thread;
+
+use intcode::*;
+
+use crate::solution::*;
+
+// The highest signal the amplifiers can send to the thrusters with the given phase settings
+fn max_signal(input: &str, mode: ChainMode, phases: &[i64]) -> i64 {
+    let program = parse_program(input).expect("Invalid intcode program");
+
+    let threads = thread::available_parallelism().map_or(1, |n| n.get());
+    let chain = AmplifierChain::new(&program, 5, mode);
+
+    let best = chain
+        .search(phases, threads)
+        .expect("Intcode program failed")
+        .unwrap();
+
+    best.signal
+}
+
+pub struct Day07;
+
+impl Solution for Day07 {
+    fn part1(input: &str) -> Answer {
+        max_signal(input, ChainMode::Linear, &[0, 1, 2, 3, 4]).into()
+    }
+
+    fn part2(input: &str) -> Answer {
+        max_signal(input, ChainMode::Feedback, &[5, 6, 7, 8, 9]).into()
+    }
+}
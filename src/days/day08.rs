@@ -0,0 +1,60 @@
+use grid::Grid;
+
+use crate::solution::*;
+
+const WIDTH: usize = 25;
+const HEIGHT: usize = 6;
+
+fn parse_image(input: &str) -> Vec<u32> {
+    input
+        .trim()
+        .chars()
+        .map(|c| c.to_digit(10).unwrap())
+        .collect::<Vec<_>>()
+}
+
+pub struct Day08;
+
+impl Solution for Day08 {
+    fn part1(input: &str) -> Answer {
+        let input = parse_image(input);
+
+        let mut min_zero_layer = Vec::<u32>::new();
+        let mut min_zeros = usize::MAX;
+
+        for layer in input.chunks(WIDTH * HEIGHT) {
+            let zeros = layer.iter().filter(|&c| *c == 0).count();
+
+            if zeros < min_zeros {
+                min_zeros = zeros;
+                min_zero_layer = layer.to_vec();
+            }
+        }
+
+        let ones = min_zero_layer.iter().filter(|&c| *c == 1).count();
+        let twos = min_zero_layer.iter().filter(|&c| *c == 2).count();
+
+        (ones * twos).into()
+    }
+
+    fn part2(input: &str) -> Answer {
+        let input = parse_image(input);
+
+        let mut canvas = vec![0; WIDTH * HEIGHT];
+
+        for layer in input.chunks(WIDTH * HEIGHT).rev() {
+            for (i, v) in layer.iter().enumerate() {
+                match v {
+                    2 => (),
+                    _ => canvas[i] = *v,
+                }
+            }
+        }
+
+        let pixels = Grid::from_fn(WIDTH, HEIGHT, |p| {
+            canvas[p.y as usize * WIDTH + p.x as usize] == 1
+        });
+
+        Answer::picture(pixels)
+    }
+}
@@ -0,0 +1,30 @@
+use std::collections::VecDeque;
+
+use intcode::*;
+
+use crate::solution::*;
+
+// Runs the BOOST program in a mode and returns the keycode or coordinates it outputs
+fn boost(input: &str, mode: i64) -> i64 {
+    let program = parse_program(input).expect("Invalid intcode program");
+
+    let input_queue = &mut vec![mode].into_iter().collect::<VecDeque<_>>();
+    let output_queue = &mut VecDeque::new();
+
+    let mut vm = IntcodeVM::new(&program);
+    run(&mut vm, &mut *input_queue, &mut *output_queue).expect("Intcode program failed");
+
+    *output_queue.back().expect("No output")
+}
+
+pub struct Day09;
+
+impl Solution for Day09 {
+    fn part1(input: &str) -> Answer {
+        boost(input, 1).into()
+    }
+
+    fn part2(input: &str) -> Answer {
+        boost(input, 2).into()
+    }
+}
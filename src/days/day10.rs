@@ -0,0 +1,127 @@
+use std::collections::HashMap;
+use std::collections::VecDeque;
+use grid::*;
+
+use crate::solution::*;
+
+fn length2(v: &Vec2i) -> usize {
+    (v.x * v.x + v.y * v.y) as usize
+}
+
+// The direction divided by the greatest common divisor of its coordinates
+fn reduced(v: &Vec2i) -> Vec2i {
+    let mut a = v.x.abs();
+    let mut b = v.y.abs();
+    let mut t;
+
+    loop {
+        if b == 0 {
+            return Vec2i {
+                x: v.x / a,
+                y: v.y / a,
+            };
+        }
+
+        t = b;
+        b = ((a % b) + b) % b;
+        a = t;
+    }
+}
+
+fn azimuth(v: &Vec2i) -> f32 {
+    (v.y as f32).atan2(v.x as f32)
+}
+
+fn is_same<T>(a: &T, b: &T) -> bool {
+    std::ptr::eq(a, b)
+}
+
+fn parse_asteroids(input: &str) -> Vec<Vec2i> {
+    let map = Grid::parse(input.trim(), |c| Some(c == '#')).expect("Invalid asteroid map");
+
+    map.iter()
+        .filter(|(_, &asteroid)| asteroid)
+        .map(|(position, _)| position)
+        .collect::<Vec<_>>()
+}
+
+// The asteroid that sees the most others, with the asteroids it sees in each direction
+fn best_station(asteroids: &Vec<Vec2i>) -> (Vec2i, HashMap<Vec2i, Vec<Vec2i>>) {
+    let mut max_view = HashMap::new();
+    let mut max_asteroid = Vec2i::ZERO;
+
+    for center in asteroids {
+        let mut set: HashMap<Vec2i, Vec<Vec2i>> = HashMap::new();
+        for other in asteroids {
+            if is_same(center, other) {
+                continue;
+            }
+
+            let other_relative_position = *other - *center;
+            let reduced = reduced(&other_relative_position);
+
+            if let std::collections::hash_map::Entry::Vacant(e) = set.entry(reduced) {
+                e.insert(vec![other_relative_position]);
+            } else {
+                set.get_mut(&reduced).unwrap().push(other_relative_position);
+            }
+        }
+
+        if set.len() > max_view.len() {
+            max_view = set;
+            max_asteroid = *center;
+        }
+    }
+
+    (max_asteroid, max_view)
+}
+
+// The asteroids in the order the laser vaporizes them, turning clockwise from up
+fn vaporize(station: Vec2i, mut view: HashMap<Vec2i, Vec<Vec2i>>) -> Vec<Vec2i> {
+    view.iter_mut().for_each(|(_dir, list)| {
+        list.sort_by(|a, b| length2(a).partial_cmp(&length2(b)).unwrap())
+    });
+
+    let pi = std::f32::consts::PI;
+
+    let mut azimuth_asteroids = view
+        .iter()
+        .map(|(dir, list)| {
+            (
+                (((azimuth(dir) / pi - 1.5) % 2.0) + 2.0) % 2.0,
+                list.clone().into_iter().collect::<VecDeque<Vec2i>>(),
+            )
+        })
+        .collect::<Vec<(f32, VecDeque<Vec2i>)>>();
+
+    azimuth_asteroids.sort_by(|l, r| l.0.partial_cmp(&r.0).unwrap());
+
+    let mut vaporized = Vec::new();
+
+    while azimuth_asteroids.iter().any(|(_, list)| !list.is_empty()) {
+        for (_, list) in azimuth_asteroids.iter_mut() {
+            if let Some(asteroid) = list.pop_front() {
+                vaporized.push(station + asteroid);
+            }
+        }
+    }
+
+    vaporized
+}
+
+pub struct Day10;
+
+impl Solution for Day10 {
+    fn part1(input: &str) -> Answer {
+        let (_, view) = best_station(&parse_asteroids(input));
+
+        view.len().into()
+    }
+
+    fn part2(input: &str) -> Answer {
+        let (station, view) = best_station(&parse_asteroids(input));
+        let bet = vaporize(station, view)[199];
+
+        (bet.x * 100 + bet.y).into()
+    }
+}
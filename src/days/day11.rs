@@ -0,0 +1,126 @@
+use std::cell::RefCell;
+
+use grid::*;
+use intcode::*;
+
+use crate::solution::*;
+
+struct RobotState {
+    position: Vec2i,
+    heading: Vec2i,
+}
+
+impl RobotState {
+    fn turn(&mut self, dir: i64) {
+        self.heading = if dir == 1 {
+            self.heading.turn_right()
+        } else {
+            self.heading.turn_left()
+        };
+    }
+
+    fn move_forward(&mut self) {
+        self.position += self.heading;
+    }
+}
+
+#[derive(Debug)]
+enum Color {
+    Black,
+    White,
+}
+
+impl From<i64> for Color {
+    fn from(i: i64) -> Color {
+        match i {
+            0 => Color::Black,
+            1 => Color::White,
+            _ => panic!("Unknown color"),
+        }
+    }
+}
+
+impl From<&Color> for i64 {
+    fn from(color: &Color) -> Self {
+        match color {
+            Color::Black => 0,
+            Color::White => 1,
+        }
+    }
+}
+
+fn draw(map: &SparseGrid<Color>) -> Grid<bool> {
+    map.to_dense(|col| matches!(col, Some(Color::White)))
+}
+
+// The robot and the hull it paints, told a color and then a turn for each panel
+struct Painter {
+    robot: RobotState,
+    hull: SparseGrid<Color>,
+    pending: Vec<i64>,
+}
+
+impl Painter {
+    // The camera, the color of the panel under the robot
+    fn camera(&self) -> i64 {
+        self.hull
+            .get(self.robot.position)
+            .unwrap_or(&Color::Black)
+            .into()
+    }
+}
+
+impl OutputDevice for Painter {
+    fn output(&mut self, value: i64) {
+        self.pending.push(value);
+
+        if let [color, direction] = self.pending[..] {
+            self.hull.insert(self.robot.position, Color::from(color));
+            self.robot.turn(direction);
+            self.robot.move_forward();
+            self.pending.clear();
+        }
+    }
+}
+
+fn run_painter(init_tile_color: i64, program: &[i64]) -> SparseGrid<Color> {
+    let mut program_state = IntcodeVM::new(program);
+    let mut hull = SparseGrid::new();
+
+    hull.insert(Vec2i::ZERO, Color::from(init_tile_color));
+
+    let painter = RefCell::new(Painter {
+        robot: RobotState {
+            position: Vec2i::ZERO,
+            heading: Vec2i::NORTH,
+        },
+        hull,
+        pending: Vec::new(),
+    });
+    let mut brush = OutputFn(|v| painter.borrow_mut().output(v));
+    let mut camera = InputFn(|| Some(painter.borrow().camera()));
+
+    let result = run(&mut program_state, &mut camera, &mut brush).expect("Intcode program failed");
+    assert_eq!(result, VMStatus::Halted);
+
+    painter.into_inner().hull
+}
+
+pub struct Day11;
+
+impl Solution for Day11 {
+    fn part1(input: &str) -> Answer {
+        let program = parse_program(input).expect("Invalid intcode program");
+        let hull = run_painter(0, &program);
+
+        hull.len().into()
+    }
+
+    // The registration identifier the robot paints when starting on a white panel
+    fn part2(input: &str) -> Answer {
+        let program = parse_program(input).expect("Invalid intcode program");
+        let hull = run_painter(1, &program);
+
+        Answer::picture(draw(&hull))
+    }
+}
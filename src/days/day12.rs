@@ -0,0 +1,149 @@
+use regex::Regex;
+use std::str::FromStr;
+use std::num::ParseIntError;
+
+use crate::solution::*;
+
+#[derive(Debug, Clone, Copy)]
+struct Moon {
+    pos: [isize; 3],
+    vel: [isize; 3],
+}
+
+impl FromStr for Moon {
+    type Err = ParseIntError;
+    
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let x_re = Regex::new(r"x=(-?\d+)").unwrap();
+        let y_re = Regex::new(r"y=(-?\d+)").unwrap();
+        let z_re = Regex::new(r"z=(-?\d+)").unwrap();
+    
+        let x = x_re.captures(s).unwrap().get(1).unwrap().as_str().parse::<isize>()?;
+        let y = y_re.captures(s).unwrap().get(1).unwrap().as_str().parse::<isize>()?;
+        let z = z_re.captures(s).unwrap().get(1).unwrap().as_str().parse::<isize>()?;
+
+        Ok(Moon { pos: [x, y, z], vel: [0, 0, 0] })
+    }
+}
+
+impl Moon {
+    fn get_kin(&self) -> isize {
+        self.vel[0].abs() + self.vel[1].abs() + self.vel[2].abs()
+    }
+
+    fn get_pot(&self) -> isize {
+        self.pos[0].abs() + self.pos[1].abs() + self.pos[2].abs()
+    }
+}
+
+fn get_total_energy(moons: &[Moon]) -> isize {
+    let mut energy = 0;
+
+    for m in moons.iter() {
+        energy += m.get_pot() * m.get_kin();
+    }
+
+    energy
+}
+
+fn get_acc(l: isize, r: isize) -> isize {
+    if l < r {
+        1
+    }else if l > r {
+        -1
+    }else {
+        0
+    }
+}
+
+fn is_axis_similar(l: &[Moon], r: &[Moon], axis: usize) -> bool {
+    for i in 0..l.len() {
+        if l[i].pos[axis] != r[i].pos[axis] || l[i].vel[axis] != r[i].vel[axis] {
+            return false;
+        }
+    }
+
+    true
+}
+
+fn gcd(mut a: usize, mut b: usize) -> usize {
+    while a != b { 
+        if a > b {
+           a -= b;
+        }else{
+           b -= a;
+        }
+    }
+    a
+}
+
+fn parse_moons(input: &str) -> Vec<Moon> {
+    input.lines().map(|l| Moon::from_str(l).unwrap()).collect::<Vec<_>>()
+}
+
+pub struct Day12;
+
+impl Solution for Day12 {
+    fn part1(input: &str) -> Answer {
+        let moons = &mut parse_moons(input);
+
+        for _ in 0..1000 {
+            for i in 0..moons.len() {
+                for j in i..moons.len() {
+                    for axis in 0..3 {
+                        moons[i].vel[axis] += get_acc(moons[i].pos[axis], moons[j].pos[axis]);
+                        moons[j].vel[axis] -= get_acc(moons[i].pos[axis], moons[j].pos[axis]);
+                    }
+                }
+            }
+
+            for m in moons.iter_mut() {
+                for axis in 0..3 {
+                    m.pos[axis] += m.vel[axis];
+                }
+            }
+        }
+
+        get_total_energy(moons).into()
+    }
+
+    // The axes are independent, so the whole system repeats when every axis has
+    fn part2(input: &str) -> Answer {
+        let moons = &mut parse_moons(input);
+
+        let initial = moons.clone();
+
+        let mut min_period = [0, 0, 0];
+
+        for (axis, period) in min_period.iter_mut().enumerate() {
+            let mut t = 1;
+            loop {
+                for i in 0..moons.len() {
+                    for j in i..moons.len() {
+                        moons[i].vel[axis] += get_acc(moons[i].pos[axis], moons[j].pos[axis]);
+                        moons[j].vel[axis] -= get_acc(moons[i].pos[axis], moons[j].pos[axis]);
+                    }
+                }
+
+                for m in moons.iter_mut() {
+                    m.pos[axis] += m.vel[axis];
+                }
+
+                if is_axis_similar(moons, &initial, axis) {
+                    *period = t;
+                    break;
+                }
+
+                t += 1;
+            }
+        }
+
+        let gcd_xy = gcd(min_period[0], min_period[1]);
+        let lcm_xy = (min_period[0] * min_period[1]) / gcd_xy;
+
+        let gcd_xyz = gcd(lcm_xy, min_period[2]);
+        let lcm_xyz = (lcm_xy * min_period[2]) / gcd_xyz;
+
+        lcm_xyz.into()
+    }
+}
@@ -0,0 +1,152 @@
+use std::cell::RefCell;
+use std::cmp::Ordering;
+use std::collections::VecDeque;
+use grid::*;
+use intcode::*;
+
+use crate::solution::*;
+
+#[derive(PartialEq, Eq)]
+enum Tile {
+    Empty,
+    Ball,
+    Paddle,
+    Wall,
+    Block,
+}
+
+impl From<i64> for Tile {
+    fn from(x: i64) -> Self {
+        match x {
+            0 => Tile::Empty,
+            1 => Tile::Wall,
+            2 => Tile::Block,
+            3 => Tile::Paddle,
+            4 => Tile::Ball,
+            _ => panic!("Unknown tile"),
+        }
+    }
+}
+
+impl From<&Tile> for char {
+    fn from(tile: &Tile) -> Self {
+        match tile {
+            Tile::Empty => ' ',
+            Tile::Wall => 'w',
+            Tile::Block => '#',
+            Tile::Paddle => '-',
+            Tile::Ball => 'o',
+        }
+    }
+}
+
+struct GameState {
+    screen: SparseGrid<Tile>,
+    score: usize,
+    paddle_pos: Vec2i,
+    ball_pos: Vec2i,
+    pending: Vec<i64>,
+}
+
+impl GameState {
+    fn new() -> GameState {
+        GameState {
+            screen: SparseGrid::new(),
+            score: 0,
+            paddle_pos: Vec2i::ZERO,
+            ball_pos: Vec2i::ZERO,
+            pending: Vec::new(),
+        }
+    }
+
+    fn update(&mut self, x: i64, y: i64, t: i64) {
+        if x == -1 && y == 0 {
+            self.score = t as usize;
+            return;
+        }
+
+        let position = Vec2i::new(x as isize, y as isize);
+        let t = Tile::from(t);
+
+        match t {
+            Tile::Ball => self.ball_pos = position,
+            Tile::Paddle => self.paddle_pos = position,
+            _ => (),
+        }
+
+        self.screen.insert(position, t);
+    }
+
+    fn blocks(&self) -> usize {
+        self.screen
+            .iter()
+            .filter(|(_, t)| **t == Tile::Block)
+            .count()
+    }
+
+    // The paddle AI, keeps the paddle under the ball
+    fn joystick(&self) -> i64 {
+        match self.paddle_pos.x.cmp(&self.ball_pos.x) {
+            Ordering::Less => 1,
+            Ordering::Equal => 0,
+            Ordering::Greater => -1,
+        }
+    }
+}
+
+// The arcade screen, updated as the x, y and tile id of each tile arrive
+impl OutputDevice for GameState {
+    fn output(&mut self, value: i64) {
+        self.pending.push(value);
+
+        if let [x, y, t] = self.pending[..] {
+            self.update(x, y, t);
+            self.pending.clear();
+        }
+    }
+}
+
+fn _draw_game(game: &GameState) {
+    print!("{}", game.screen.render(|t| t.unwrap_or(&Tile::Empty).into()));
+
+    println!("Score: {}", game.score);
+    println!("Blocks left: {}", game.blocks());
+}
+
+pub struct Day13;
+
+impl Solution for Day13 {
+    // The blocks on the screen when the game exits
+    fn part1(input: &str) -> Answer {
+        let program = parse_program(input).expect("Invalid intcode program");
+
+        let mut vm = IntcodeVM::new(&program);
+        let mut game_state = GameState::new();
+
+        run(&mut vm, &mut VecDeque::new(), &mut game_state).expect("Intcode program failed");
+
+        game_state.blocks().into()
+    }
+
+    // The score after breaking every block, playing for free
+    fn part2(input: &str) -> Answer {
+        let program = &mut parse_program(input).expect("Invalid intcode program");
+
+        program[0] = 2;
+        let mut vm = IntcodeVM::new(program);
+
+        // The screen and the joystick share the game state, and the joystick is only read
+        // once the screen is done drawing the frame
+        let game_state = RefCell::new(GameState::new());
+        let mut screen = OutputFn(|v| game_state.borrow_mut().output(v));
+        let mut joystick = InputFn(|| Some(game_state.borrow().joystick()));
+
+        let result = run(&mut vm, &mut joystick, &mut screen).expect("Intcode program failed");
+        assert_eq!(result, VMStatus::Halted);
+
+        let game_state = game_state.borrow();
+        assert_eq!(game_state.blocks(), 0);
+
+        game_state.score.into()
+    }
+}
@@ -1,13 +1,12 @@
 use regex::Regex;
 use std::collections::HashMap;
 use std::collections::VecDeque;
-use std::env;
-use std::fs;
 use std::num::ParseIntError;
 use std::ops::Mul;
-use std::path::PathBuf;
 use std::str::FromStr;
 
+use crate::solution::*;
+
 #[derive(Debug, Clone)]
 struct Component {
     name: String,
@@ -100,8 +99,7 @@ fn ore_required(fuel: usize, recipes: &HashMap<String, Recipe>) -> usize {
                 avail = 0;
             }
 
-            let rounds = (current_requirement.amount - avail + recipe.result.amount - 1)
-                / recipe.result.amount;
+            let rounds = (current_requirement.amount - avail).div_ceil(recipe.result.amount);
 
             *available.entry(current_requirement.name).or_insert(0) =
                 rounds * recipe.result.amount + avail - current_requirement.amount;
@@ -117,56 +115,50 @@ fn ore_required(fuel: usize, recipes: &HashMap<String, Recipe>) -> usize {
     ore
 }
 
-fn main() {
-    let filename = &mut PathBuf::from(env::var("CARGO_MANIFEST_DIR").unwrap());
-    filename.push("inputs/14.txt");
-
-    println!("Reading {}", filename.display());
-
-    let input = fs::read_to_string(filename).expect("Unable to open file");
-    let input = input.lines().collect::<Vec<_>>();
-
-    let recipes = &mut input
-        .iter()
+fn parse_recipes(input: &str) -> HashMap<String, Recipe> {
+    input
+        .lines()
         .map(|l| Recipe::from_str(l).unwrap())
-        .collect::<Vec<_>>();
+        .map(|r| (r.result.name.clone(), r))
+        .collect::<HashMap<_, _>>()
+}
 
-    /*for r in recipes.iter().rev() {
-        println!("{:?}", r);
-    }*/
+pub struct Day14;
 
-    let recipes_map = recipes
-        .iter()
-        .map(|r| (r.result.name.clone(), r.clone()))
-        .collect::<HashMap<_, _>>();
+impl Solution for Day14 {
+    fn part1(input: &str) -> Answer {
+        ore_required(1, &parse_recipes(input)).into()
+    }
 
-    let ore = ore_required(1, &recipes_map);
+    // The most fuel a trillion ore makes
+    fn part2(input: &str) -> Answer {
+        let recipes_map = parse_recipes(input);
 
-    println!("Ore required: {}", ore);
+        // trillion 10^6 * 10^6
+        const STEP: usize = 1000;
+        const LIMIT: usize = 1_000_000_000_000;
+        let mut fuel = STEP;
 
-    // trillion 10^6 * 10^6
-    const STEP: usize = 1000;
-    const LIMIT: usize = 1_000_000_000_000;
-    let mut fuel = STEP;
+        loop {
+            let ore = ore_required(fuel, &recipes_map);
 
-    loop {
-        let ore = ore_required(fuel, &recipes_map);
+            if ore > LIMIT {
+                fuel -= STEP;
 
-        if ore > LIMIT {
-            fuel = fuel - STEP;
+                break;
+            }
 
-            break;
+            fuel += STEP;
         }
 
-        fuel += STEP;
-    }
-
-    for i in 0..STEP {
-        let ore = ore_required(fuel + i, &recipes_map);
+        for i in 0..STEP {
+            let ore = ore_required(fuel + i, &recipes_map);
 
-        if ore > LIMIT {
-            println!("Part 2: {}", fuel + i - 1);
-            break;
+            if ore > LIMIT {
+                return (fuel + i - 1).into();
+            }
         }
+
+        panic!("No fuel limit found");
     }
 }
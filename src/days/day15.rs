@@ -0,0 +1,211 @@
+use std::cell::RefCell;
+
+use grid::*;
+use intcode::*;
+use search::*;
+
+use crate::solution::*;
+
+#[derive(Debug, PartialEq, Clone, Copy)]
+enum Tile {
+    Wall,
+    Empty,
+    Tank,
+}
+
+#[derive(PartialEq, Debug, Clone, Copy)]
+enum Command {
+    North = 0,
+    West = 1,
+    South = 2,
+    East = 3,
+}
+
+#[derive(PartialEq, Eq, Clone, Copy)]
+enum CommandResult {
+    HitWall = 0,
+    Ok = 1,
+    FoundTank = 2,
+}
+
+impl From<i64> for Command {
+    fn from(x: i64) -> Self {
+        match x {
+            0 => Command::North,
+            1 => Command::West,
+            2 => Command::South,
+            3 => Command::East,
+            _ => panic!("Unknown command"),
+        }
+    }
+}
+
+impl From<i64> for CommandResult {
+    fn from(x: i64) -> Self {
+        match x {
+            0 => CommandResult::HitWall,
+            1 => CommandResult::Ok,
+            2 => CommandResult::FoundTank,
+            _ => panic!("Unknown output"),
+        }
+    }
+}
+
+impl From<i64> for Tile {
+    fn from(x: i64) -> Self {
+        match x {
+            0 => Tile::Wall,
+            1 => Tile::Empty,
+            2 => Tile::Tank,
+            _ => panic!("Unknown tile"),
+        }
+    }
+}
+
+impl From<&Tile> for char {
+    fn from(tile: &Tile) -> Self {
+        match tile {
+            Tile::Wall => '#',
+            Tile::Empty => '.',
+            Tile::Tank => 'X',
+        }
+    }
+}
+
+#[derive(Debug)]
+struct SearchState {
+    map: SparseGrid<Tile>,
+    robot_pos: Vec2i,
+    tank_pos: Option<Vec2i>,
+    // The move being made, or None once the robot is back at the start
+    command: Option<Command>,
+}
+
+fn get_internal_command_code(command: Command) -> isize {
+    match command {
+        Command::North => 1,
+        Command::South => 2,
+        Command::West => 3,
+        Command::East => 4,
+    }
+}
+
+impl SearchState {
+    fn new() -> SearchState {
+        let mut map = SparseGrid::new();
+        map.insert(Vec2i::ZERO, Tile::Empty);
+
+        SearchState {
+            map,
+            robot_pos: Vec2i::ZERO,
+            tank_pos: None,
+            command: Some(Command::North),
+        }
+    }
+
+    // The next move, following the left wall
+    fn joystick(&self) -> Option<i64> {
+        self.command
+            .map(|command| get_internal_command_code(command) as i64)
+    }
+
+    // Records where the last move went and picks the next one
+    fn advance(&mut self, response: CommandResult) {
+        let command = match self.command {
+            Some(command) => command,
+            None => return,
+        };
+
+        let next_pos = match command {
+            Command::North => self.robot_pos + Vec2i::NORTH,
+            Command::South => self.robot_pos + Vec2i::SOUTH,
+            Command::West => self.robot_pos + Vec2i::WEST,
+            Command::East => self.robot_pos + Vec2i::EAST,
+        };
+
+        match response {
+            CommandResult::HitWall => {
+                self.map.insert(next_pos, Tile::Wall);
+            }
+            CommandResult::Ok => {
+                self.map.insert(next_pos, Tile::Empty);
+                self.robot_pos = next_pos;
+            }
+            CommandResult::FoundTank => {
+                self.map.insert(next_pos, Tile::Tank);
+                self.robot_pos = next_pos;
+                self.tank_pos = Some(next_pos);
+            }
+        }
+
+        let command = if response == CommandResult::HitWall {
+            Command::from((command as i64 + 1) % 4)
+        } else {
+            Command::from((command as i64 + 4 - 1) % 4)
+        };
+
+        self.command = if self.robot_pos == Vec2i::ZERO && command == Command::North {
+            None
+        } else {
+            Some(command)
+        };
+    }
+}
+
+// The repair droid, reporting the outcome of each move
+impl OutputDevice for SearchState {
+    fn output(&mut self, value: i64) {
+        self.advance(CommandResult::from(value));
+    }
+}
+
+fn _draw(state: &SearchState) {
+    print!("{}", state.map.render(|t| t.map_or(' ', |t| t.into())));
+}
+
+// The tiles the robot and the oxygen can move to from a tile
+fn open_neighbours(map: &SparseGrid<Tile>, coord: Vec2i) -> impl Iterator<Item = Vec2i> + '_ {
+    coord
+        .neighbours4()
+        .filter(move |&n| matches!(map.get(n), Some(Tile::Empty) | Some(Tile::Tank)))
+}
+
+// Maps the whole area by following the left wall until the robot is back where it started
+fn explore(input: &str) -> SearchState {
+    let program = parse_program(input).expect("Invalid intcode program");
+
+    let mut vm = IntcodeVM::new(&program);
+
+    // The droid waits for input once the robot is back at the start
+    let search_state = RefCell::new(SearchState::new());
+    let mut droid = OutputFn(|v| search_state.borrow_mut().output(v));
+    let mut joystick = InputFn(|| search_state.borrow().joystick());
+
+    let result = run(&mut vm, &mut joystick, &mut droid).expect("Intcode program failed");
+    assert_eq!(result, VMStatus::EmptyInputBuffer);
+
+    search_state.into_inner()
+}
+
+pub struct Day15;
+
+impl Solution for Day15 {
+    fn part1(input: &str) -> Answer {
+        let search_state = explore(input);
+
+        let tank_pos = search_state.tank_pos.unwrap();
+        let to_tank = bfs(Vec2i::ZERO, |&c| open_neighbours(&search_state.map, c), |&c| c == tank_pos);
+
+        to_tank.path.expect("No path to the tank").cost.into()
+    }
+
+    fn part2(input: &str) -> Answer {
+        let search_state = explore(input);
+
+        // The oxygen spreads one tile per minute, so the farthest tile fills last
+        let tank_pos = search_state.tank_pos.unwrap();
+        let oxygen = flood_fill(tank_pos, |&c| open_neighbours(&search_state.map, c));
+
+        (*oxygen.values().max().unwrap()).into()
+    }
+}
@@ -1,6 +1,4 @@
-use std::env;
-use std::fs;
-use std::path::PathBuf;
+use crate::solution::*;
 
 struct InputGenerator {
     sequence: Vec<i32>,
@@ -12,10 +10,10 @@ struct InputGenerator {
 impl InputGenerator {
     fn new(sequence: Vec<i32>, repetitions: usize) -> InputGenerator {
         InputGenerator {
-            sequence: sequence,
+            sequence,
             idx: 0,
             current_repetition: 0,
-            repetitions: repetitions,
+            repetitions,
         }
     }
 
@@ -63,17 +61,6 @@ fn to_num_vec(input: &str) -> Vec<i32> {
         .collect::<Vec<_>>()
 }
 
-fn get_input() -> String {
-    let filename = &mut PathBuf::from(env::var("CARGO_MANIFEST_DIR").unwrap());
-    filename.push("inputs/16.txt");
-
-    println!("Reading {}", filename.display());
-
-    let input = fs::read_to_string(filename).expect("Unable to open file");
-
-    input
-}
-
 struct BaseGenerator {
     base_sequence: Vec<i32>,
     base_idx: usize,
@@ -87,7 +74,7 @@ impl BaseGenerator {
             base_sequence: vec![0, 1, 0, -1],
             base_idx: 0,
             current_repetition: 1,
-            repetitions: repetitions,
+            repetitions,
         }
     }
 }
@@ -118,12 +105,12 @@ fn fft(mut input: InputGenerator, phases: usize) -> Vec<i32> {
             let mut base = BaseGenerator::new(iter);
             let mut dot = 0;
 
-            while let Some(i) = input.next() {
+            for i in input.by_ref() {
                 dot += i * base.next().unwrap();
             }
 
             let ls_digit = dot.abs() % 10;
-            output.push(ls_digit as i32);
+            output.push(ls_digit);
         }
 
         input = InputGenerator::new(output, 1);
@@ -158,26 +145,38 @@ fn fft_fake(mut input: InputGenerator, phases: usize, skip: usize) -> Vec<i32> {
     output
 }
 
-fn main() {
-    let input = get_input();
+// The first eight digits of a signal, as the message reads them
+fn message(output: &[i32]) -> String {
+    output[0..8].iter().map(|d| d.to_string()).collect()
+}
 
-    let input_gen = InputGenerator::new(to_num_vec(&input), 1);
-    let output = fft(input_gen, 100);
+pub struct Day16;
 
-    println!("Part 1 output: {:?}", &output[0..8]);
+impl Solution for Day16 {
+    fn part1(input: &str) -> Answer {
+        let input_gen = InputGenerator::new(to_num_vec(input.trim()), 1);
+        let output = fft(input_gen, 100);
 
-    let skip = input
-        .chars()
-        .take(7)
-        .collect::<String>()
-        .parse::<usize>()
-        .unwrap();
+        message(&output).into()
+    }
 
-    let input_gen = InputGenerator::new(to_num_vec(&input), 10000);
-    println!("Part 2 input length: {}", input_gen.len());
-    let output = fft_fake(input_gen, 100, skip);
+    // The message is at the offset given by the first seven digits, in the second half of
+    // the signal where each digit only depends on the ones after it
+    fn part2(input: &str) -> Answer {
+        let input = input.trim();
 
-    println!("Part 2 output: {:?}", &output[0..8]);
+        let skip = input
+            .chars()
+            .take(7)
+            .collect::<String>()
+            .parse::<usize>()
+            .unwrap();
+
+        let input_gen = InputGenerator::new(to_num_vec(input), 10000);
+        let output = fft_fake(input_gen, 100, skip);
+
+        message(&output).into()
+    }
 }
 
 #[cfg(test)]
@@ -0,0 +1,583 @@
+use std::collections::HashMap;
+use std::collections::VecDeque;
+
+use grid::*;
+use intcode::*;
+
+use crate::solution::*;
+
+#[derive(Debug, Hash, Eq, PartialEq, Clone, Copy)]
+enum Heading {
+    East,
+    West,
+    North,
+    South,
+}
+
+impl Heading {
+    fn get_opposite(&self) -> Heading {
+        match self {
+            Heading::East => Heading::West,
+            Heading::West => Heading::East,
+            Heading::North => Heading::South,
+            Heading::South => Heading::North,
+        }
+    }
+
+    fn get_neighbor(&self, coord: &Vec2i) -> Vec2i {
+        match self {
+            Heading::East => *coord + Vec2i::EAST,
+            Heading::West => *coord + Vec2i::WEST,
+            Heading::North => *coord + Vec2i::NORTH,
+            Heading::South => *coord + Vec2i::SOUTH,
+        }
+    }
+}
+
+impl From<i8> for Heading {
+    fn from(i: i8) -> Self {
+        match i {
+            0 => Heading::North,
+            1 => Heading::East,
+            2 => Heading::South,
+            3 => Heading::West,
+            _ => panic!("Unknown heading"),
+        }
+    }
+}
+
+impl From<Heading> for i8 {
+    fn from(heading: Heading) -> Self {
+        match heading {
+            Heading::North => 0,
+            Heading::East => 1,
+            Heading::South => 2,
+            Heading::West => 3,
+        }
+    }
+}
+#[derive(Clone)]
+struct Map {
+    grid: Grid<char>,
+    robot_position: Vec2i,
+    robot_heading: Heading
+}
+
+impl Map {
+    fn new(output: &[i64]) -> Map {
+        let view = output.iter().map(|&o| o as u8 as char).collect::<String>();
+        let grid = Grid::parse(view.trim_end(), Some).expect("Invalid camera view");
+
+        let robot_position = grid
+            .find(|c| "<>^v".contains(*c))
+            .expect("No robot in view");
+        let robot_heading = get_initial_heading(grid[robot_position] as i64);
+
+        Map {
+            grid,
+            robot_position,
+            robot_heading
+        }
+    }
+}
+
+#[derive(Debug)]
+struct Intersection {
+    position: Vec2i,
+}
+
+fn _draw_view(map: &Map) {
+    print!("{}", map.grid.render(|&c| c));
+}
+
+fn is_road(map: &Map, coordinate: &Vec2i) -> bool {
+    map.grid.get(*coordinate) == Some(&'#')
+}
+
+fn get_intersections(map: &Map) -> Vec<Intersection> {
+    map.grid
+        .positions()
+        .filter(|p| is_road(map, p) && p.neighbours4().all(|n| is_road(map, &n)))
+        .map(|position| Intersection { position })
+        .collect()
+}
+
+fn prod_sum(intersections: &Vec<Intersection>) -> isize {
+    let mut alignment = 0;
+
+    for intersection in intersections {
+        alignment += intersection.position.x * intersection.position.y;
+    }
+
+    alignment
+}
+
+fn get_alignment(map: &Map) -> isize {
+    let intersections = get_intersections(map);
+
+    prod_sum(&intersections)
+}
+
+fn get_initial_heading(map_value: i64) -> Heading {
+    match map_value {
+        60 => Heading::West,
+        62 => Heading::East,
+        94 => Heading::North,
+        118 => Heading::South,
+        _ => panic!("Failed to parse initial heading"),
+    }
+}
+
+#[derive(Debug, Hash, Eq, PartialEq, Clone, Copy)]
+enum Move {
+    Forward,
+    TurnLeft,
+    TurnRight,
+}
+
+impl From<i8> for Move {
+    fn from(i: i8) -> Self {
+        match i {
+            -1 => Move::TurnLeft,
+            0 => Move::Forward,
+            1 => Move::TurnRight,
+            _ => panic!("Unrecognized move: {}", i),
+        }
+    }
+}
+
+impl Move {
+    fn get_heading_after(&self, heading: Heading) -> Heading {
+        match self {
+            Move::TurnLeft => Heading::from((Into::<i8>::into(heading) + 4 - 1) % 4),
+            Move::TurnRight => Heading::from((Into::<i8>::into(heading) + 1) % 4),
+            _ => heading,
+        }
+    }
+}
+
+fn get_next_move(mut neighbors: HashMap<Heading, bool>, current_heading: Heading) -> Option<Move> {
+    if neighbors[&current_heading] {
+        return Some(Move::Forward);
+    }
+
+    neighbors.remove(&current_heading);
+    neighbors.remove(&current_heading.get_opposite());
+
+    for (heading, traversable) in neighbors.into_iter() {
+        if traversable {
+            let turned_quarters = Into::<i8>::into(heading) - Into::<i8>::into(current_heading);
+            let turned_quarters = (turned_quarters + 2).rem_euclid(4) - 2;
+
+            return Some(Move::from(turned_quarters));
+        }
+    }
+
+    None
+}
+
+fn get_to_goal_commands(map: &Map) -> Vec<String> {
+    let mut current_position = map.robot_position;
+    let mut current_heading = map.robot_heading;
+    let mut segment_length = 0;
+    let mut commands = Vec::new();
+
+    loop {
+        let west = Heading::West.get_neighbor(&current_position);
+        let east = Heading::East.get_neighbor(&current_position);
+        let north = Heading::North.get_neighbor(&current_position);
+        let south = Heading::South.get_neighbor(&current_position);
+
+        let neighbors = [
+            (Heading::West, west),
+            (Heading::East, east),
+            (Heading::North, north),
+            (Heading::South, south),
+        ]
+        .iter()
+        .map(|n| (n.0, is_road(map, &n.1)))
+        .collect::<HashMap<_, _>>();
+
+        let next_move = get_next_move(neighbors.clone(), current_heading);
+
+        if let Some(mov) = next_move {
+            match mov {
+                Move::Forward => {
+                    segment_length += 1;
+                    current_position = current_heading.get_neighbor(&current_position);
+                }
+                Move::TurnLeft => {
+                    if segment_length > 0 {
+                        commands.push(segment_length.to_string())
+                    }
+                    segment_length = 0;
+                    commands.push("L".to_string());
+                }
+                Move::TurnRight => {
+                    if segment_length > 0 {
+                        commands.push(segment_length.to_string())
+                    }
+                    segment_length = 0;
+                    commands.push("R".to_string());
+                }
+            }
+
+            current_heading = mov.get_heading_after(current_heading);
+        } else {
+            commands.push(segment_length.to_string());
+            break;
+        }
+    }
+
+    commands
+}
+
+#[derive(Debug, Hash, Eq, PartialEq, Clone, Copy)]
+enum Step {
+    Left,
+    Right,
+    Forward(u32),
+}
+
+impl std::fmt::Display for Step {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        match self {
+            Step::Left => write!(f, "L"),
+            Step::Right => write!(f, "R"),
+            Step::Forward(n) => write!(f, "{}", n),
+        }
+    }
+}
+
+fn parse_steps(commands: &[String]) -> Vec<Step> {
+    commands
+        .iter()
+        .map(|c| match c.as_str() {
+            "L" => Step::Left,
+            "R" => Step::Right,
+            n => Step::Forward(n.parse().expect("Invalid movement command")),
+        })
+        .collect()
+}
+
+// Joins consecutive forward steps into one move
+fn merge_steps(steps: &[Step]) -> Vec<Step> {
+    let mut merged: Vec<Step> = Vec::new();
+
+    for step in steps {
+        match (merged.last_mut(), step) {
+            (Some(Step::Forward(n)), Step::Forward(m)) => *n += m,
+            _ => merged.push(*step),
+        }
+    }
+
+    merged
+}
+
+fn encode_steps(steps: &[Step]) -> String {
+    merge_steps(steps)
+        .iter()
+        .map(|s| s.to_string())
+        .collect::<Vec<_>>()
+        .join(",")
+}
+
+#[derive(Debug, Clone, PartialEq)]
+struct Compression {
+    // Indices into functions, in the order they are called
+    main: Vec<usize>,
+    functions: Vec<Vec<Step>>,
+}
+
+impl Compression {
+    // The main routine followed by every function, as the robot reads them
+    fn lines(&self) -> Vec<String> {
+        let main = self
+            .main
+            .iter()
+            .map(|&i| ((b'A' + i as u8) as char).to_string())
+            .collect::<Vec<_>>()
+            .join(",");
+
+        std::iter::once(main)
+            .chain(self.functions.iter().map(|f| encode_steps(f)))
+            .collect()
+    }
+}
+
+struct Compressor<'a> {
+    steps: &'a [Step],
+    function_count: usize,
+    max_length: usize,
+    functions: Vec<&'a [Step]>,
+    main: Vec<usize>,
+    solutions: Vec<Compression>,
+    // The search stops once it has found this many
+    limit: usize,
+}
+
+impl<'a> Compressor<'a> {
+    // Calls that fit in the main routine, each one a letter and a comma
+    fn max_calls(&self) -> usize {
+        self.max_length.div_ceil(2)
+    }
+
+    fn search(&mut self, position: usize) {
+        if self.solutions.len() == self.limit {
+            return;
+        }
+
+        if position == self.steps.len() {
+            self.solutions.push(Compression {
+                main: self.main.clone(),
+                functions: self.functions.iter().map(|f| merge_steps(f)).collect(),
+            });
+            return;
+        }
+
+        if self.main.len() == self.max_calls() {
+            return;
+        }
+
+        let rest = &self.steps[position..];
+
+        for i in 0..self.functions.len() {
+            let function = self.functions[i];
+
+            if rest.starts_with(function) {
+                self.main.push(i);
+                self.search(position + function.len());
+                self.main.pop();
+            }
+        }
+
+        if self.functions.len() == self.function_count {
+            return;
+        }
+
+        // Encoded lengths only grow with more steps, so stop at the first one too long
+        for end in 1..=rest.len() {
+            let function = &rest[..end];
+
+            if encode_steps(function).len() > self.max_length {
+                break;
+            }
+
+            if self.functions.contains(&function) {
+                continue;
+            }
+
+            self.main.push(self.functions.len());
+            self.functions.push(function);
+            self.search(position + end);
+            self.functions.pop();
+            self.main.pop();
+        }
+    }
+}
+
+// Up to limit ways of covering the path with a main routine calling at most function_count
+// functions, with no line longer than max_length characters. Functions are named in the
+// order they are first called, by a letter each, so there are none for more than 26. With
+// split, a move forward may be broken up between functions, so that R,12 can end one
+// function with R,4 and start the next with 8. That multiplies the ways there are, so
+// looking for all of them can take very long.
+fn compress(
+    steps: &[Step],
+    function_count: usize,
+    max_length: usize,
+    split: bool,
+    limit: usize,
+) -> Vec<Compression> {
+    if function_count > 26 {
+        return Vec::new();
+    }
+
+    let steps = if split {
+        steps
+            .iter()
+            .flat_map(|&step| match step {
+                Step::Forward(n) => vec![Step::Forward(1); n as usize],
+                turn => vec![turn],
+            })
+            .collect()
+    } else {
+        steps.to_vec()
+    };
+
+    let mut compressor = Compressor {
+        steps: &steps,
+        function_count,
+        max_length,
+        functions: Vec::new(),
+        main: Vec::new(),
+        solutions: Vec::new(),
+        limit,
+    };
+
+    compressor.search(0);
+    compressor.solutions
+}
+
+// The first way of driving the robot along the whole scaffold, preferring whole moves
+fn get_program(map: &Map) -> Option<Vec<String>> {
+    let steps = parse_steps(&get_to_goal_commands(map));
+
+    let solution = compress(&steps, 3, 20, false, 1)
+        .pop()
+        .or_else(|| compress(&steps, 3, 20, true, 1).pop())?;
+
+    let mut lines = solution.lines();
+
+    // The robot always asks for three functions, even if fewer are used
+    while lines.len() < 4 {
+        lines.push(lines[1].clone());
+    }
+
+    Some(lines)
+}
+
+// The camera view the program prints when it runs without input
+fn camera(program: &[i64]) -> Option<Map> {
+    let mut vm = IntcodeVM::new(program);
+    let mut view = Vec::new();
+
+    match run(&mut vm, &mut VecDeque::new(), &mut view) {
+        Ok(VMStatus::Halted) => Some(Map::new(&view)),
+        _ => None,
+    }
+}
+
+fn part1(program: &[i64]) -> isize {
+    let map = camera(program).expect("Intcode program failed");
+
+    get_alignment(&map)
+}
+
+fn part2(program: &[i64]) -> Result<i64, ()> {
+    let map = camera(program).ok_or(())?;
+    let robot_program = get_program(&map).ok_or(())?;
+
+    let mut program = program.to_vec();
+    program[0] = 2;
+    let mut vm = IntcodeVM::new(&program);
+
+    // The routines, then n to turn the video feed off, typed as the robot asks for them
+    let mut text = robot_program.join("\n");
+    text.push_str("\nn\n");
+
+    let mut keyboard = AsciiReader::new(text.as_bytes());
+    let mut output = Vec::new();
+
+    if Ok(VMStatus::Halted) == run(&mut vm, &mut keyboard, &mut output) {
+        return output.pop().ok_or(());
+    }
+
+    Err(())
+}
+
+pub struct Day17;
+
+impl Solution for Day17 {
+    fn part1(input: &str) -> Answer {
+        part1(&parse_program(input).expect("Invalid intcode program")).into()
+    }
+
+    fn part2(input: &str) -> Answer {
+        let dust = part2(&parse_program(input).expect("Invalid intcode program"));
+
+        dust.expect("The robot did not finish the scaffold").into()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    macro_rules! string_vec {
+        ($($x:expr),*) => (vec![$($x.to_string()),*]);
+    }
+
+    fn get_test_input() -> Vec<i64> {
+        let input = "..#..........\n..#..........\n#######...###\n#.#...#...#.#\n#############\n..#...#...#..\n..#####...^..";
+
+        input.chars().map(|c| c as i64).collect()
+    }
+
+    fn get_scaffold() -> Map {
+        let input = "#######...#####
+#.....#...#...#
+#.....#...#...#
+......#...#...#
+......#...###.#
+......#.....#.#
+^########...#.#
+......#.#...#.#
+......#########
+........#...#..
+....#########..
+....#...#......
+....#...#......
+....#...#......
+....#####......";
+
+        Map::new(&input.chars().map(|c| c as i64).collect::<Vec<_>>())
+    }
+
+    #[test]
+    fn test_example_part1() {
+        let test_input = get_test_input();
+
+        let map = Map::new(&test_input);
+
+        let alignment = get_alignment(&map);
+
+        assert_eq!(alignment, 76);
+    }
+
+    #[test]
+    fn test_compress() {
+        let steps = parse_steps(&get_to_goal_commands(&get_scaffold()));
+
+        assert_eq!(
+            encode_steps(&steps),
+            "R,8,R,8,R,4,R,4,R,8,L,6,L,2,R,4,R,4,R,8,R,8,R,8,L,6,L,2"
+        );
+
+        let solutions = compress(&steps, 3, 20, false, usize::MAX);
+        let lines = solutions.iter().map(|s| s.lines()).collect::<Vec<_>>();
+
+        assert!(lines.contains(&string_vec!["A,B,C,B,A,C", "R,8,R,8", "R,4,R,4,R,8", "L,6,L,2"]));
+        assert!(lines.iter().flatten().all(|l| l.len() <= 20));
+
+        // Splitting moves finds everything found without it, and more
+        let split = compress(&steps, 3, 20, true, usize::MAX);
+
+        assert!(solutions.iter().all(|s| split.contains(s)));
+        assert!(split.len() > solutions.len());
+    }
+
+    #[test]
+    fn test_compress_split() {
+        let steps = parse_steps(&["R".to_string(), "12".to_string()]);
+
+        // R,12 is too long for a line, so whole moves only allow R and 12, while splitting
+        // also allows R,k and 12-k for k up to 9
+        let whole = compress(&steps, 2, 3, false, usize::MAX);
+
+        assert_eq!(whole.len(), 1);
+        assert_eq!(whole[0].lines(), string_vec!["A,B", "R", "12"]);
+
+        let lines = compress(&steps, 2, 3, true, usize::MAX)
+            .iter()
+            .map(|s| s.lines())
+            .collect::<Vec<_>>();
+
+        assert_eq!(lines.len(), 10);
+        assert_eq!(lines[0], string_vec!["A,B", "R", "12"]);
+        assert!(lines.contains(&string_vec!["A,B", "R,6", "6"]));
+
+        assert_eq!(compress(&steps, 2, 3, true, 4).len(), 4);
+        assert!(compress(&steps, 27, 3, false, 1).is_empty());
+    }
+}
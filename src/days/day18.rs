@@ -0,0 +1,406 @@
+use std::collections::{HashMap, VecDeque};
+use std::hash::Hash;
+
+use grid::*;
+use search::*;
+
+use crate::solution::*;
+
+type Map = Grid<Tile>;
+
+fn build_map(input: &str) -> Map {
+    Grid::parse(input, tile).expect("Invalid map")
+}
+
+// Doors hold the key that opens them
+#[derive(Debug, PartialEq, Copy, Clone)]
+enum Tile {
+    Empty,
+    Entrance,
+    Wall,
+    Key(char),
+    Door(char)
+}
+
+// Any lowercase letter is a key and the same letter in uppercase its door
+fn tile(c: char) -> Option<Tile> {
+    match c {
+        '#' => Some(Tile::Wall),
+        '.' => Some(Tile::Empty),
+        '@' => Some(Tile::Entrance),
+        c if c.is_lowercase() => Some(Tile::Key(c)),
+        c if c.is_uppercase() => c.to_lowercase().next().map(Tile::Door),
+        _ => None,
+    }
+}
+
+fn find_entrances(map: &Map) -> Vec<Vec2i> {
+    map.iter()
+        .filter(|(_, tile)| **tile == Tile::Entrance)
+        .map(|(position, _)| position)
+        .collect()
+}
+
+// A set of keys by their index, wide enough for every key in the vault
+trait KeySet: Copy + Eq + Hash + Default {
+    const CAPACITY: usize;
+
+    fn with(self, key: usize) -> Self;
+    fn contains_all(self, other: Self) -> bool;
+}
+
+macro_rules! impl_key_set {
+    ($($t:ty),*) => {
+        $(
+            impl KeySet for $t {
+                const CAPACITY: usize = <$t>::BITS as usize;
+
+                fn with(self, key: usize) -> Self {
+                    self | 1 << key
+                }
+
+                fn contains_all(self, other: Self) -> bool {
+                    self & other == other
+                }
+            }
+        )*
+    };
+}
+
+impl_key_set!(u32, u128);
+
+// The way from one point of interest to a key
+#[derive(Debug, Clone)]
+struct Edge<K> {
+    // The point the key is at, and its key index
+    to: usize,
+    key: usize,
+    distance: usize,
+    // Keys needed for the doors on the way
+    doors: K,
+    // Keys picked up on the way, in the order they are passed
+    passed: Vec<usize>,
+}
+
+impl<K: KeySet> Edge<K> {
+    // The keys held after following the edge
+    fn pick_up(&self, keys: K) -> K {
+        self.passed
+            .iter()
+            .fold(keys.with(self.key), |k, &p| k.with(p))
+    }
+}
+
+// The entrances followed by the keys in alphabetical order, and the key index of each key
+struct Vault {
+    points: Vec<Vec2i>,
+    keys: Vec<char>,
+    robots: usize,
+}
+
+impl Vault {
+    fn new(map: &Map) -> Vault {
+        let entrances = find_entrances(map);
+
+        let mut keys = map
+            .iter()
+            .filter_map(|(position, tile)| match tile {
+                Tile::Key(k) => Some((*k, position)),
+                _ => None,
+            })
+            .collect::<Vec<_>>();
+        keys.sort();
+
+        Vault {
+            robots: entrances.len(),
+            points: entrances.into_iter().chain(keys.iter().map(|k| k.1)).collect(),
+            keys: keys.into_iter().map(|k| k.0).collect(),
+        }
+    }
+
+    fn key_index(&self, key: char) -> Option<usize> {
+        self.keys.binary_search(&key).ok()
+    }
+
+    // Every way from a point of interest to a key that isn't as long as another way with only
+    // some of its doors. A vault with loops can have several ways to a key.
+    fn edges<K: KeySet>(&self, map: &Map, from: usize) -> Vec<Edge<K>> {
+        let start = self.points[from];
+        let mut edges = Vec::new();
+        // The doors on the ways found to each position, none longer than a later way
+        let mut seen: HashMap<Vec2i, Vec<K>> = HashMap::new();
+        let mut queue = VecDeque::new();
+
+        queue.push_back((start, 0, K::default(), Vec::new()));
+
+        while let Some((position, distance, mut doors, mut passed)) = queue.pop_front() {
+            if let Tile::Door(d) = map[position] {
+                // A door without a key in the vault never opens
+                match self.key_index(d) {
+                    Some(k) => doors = doors.with(k),
+                    None => continue,
+                }
+            }
+
+            let ways = seen.entry(position).or_default();
+
+            if ways.iter().any(|&d| doors.contains_all(d)) {
+                continue;
+            }
+            ways.push(doors);
+
+            match map[position] {
+                Tile::Key(k) if position != start => {
+                    let key = self.key_index(k).unwrap();
+
+                    edges.push(Edge {
+                        to: self.robots + key,
+                        key,
+                        distance,
+                        doors,
+                        passed: passed.clone(),
+                    });
+                    passed.push(key);
+                }
+                _ => (),
+            }
+
+            for next in map.neighbours4(position) {
+                if map[next] != Tile::Wall {
+                    queue.push_back((next, distance + 1, doors, passed.clone()));
+                }
+            }
+        }
+
+        edges
+    }
+}
+
+#[derive(Debug, PartialEq)]
+struct Collection {
+    steps: usize,
+    order: String,
+}
+
+// The fewest steps for the robots to collect every key between them, moving one at a time
+fn collect_keys<K: KeySet>(map: &Map) -> Option<Collection> {
+    let vault = Vault::new(map);
+
+    if vault.keys.len() > K::CAPACITY {
+        return None;
+    }
+
+    let edges = (0..vault.points.len())
+        .map(|point| vault.edges::<K>(map, point))
+        .collect::<Vec<_>>();
+    let all_keys = (0..vault.keys.len()).fold(K::default(), |keys, k| keys.with(k));
+
+    let start = ((0..vault.robots).collect::<Vec<_>>(), K::default());
+
+    let found = dijkstra(
+        start,
+        |(robots, keys)| {
+            let mut moves = Vec::new();
+
+            for (robot, &point) in robots.iter().enumerate() {
+                for edge in edges[point].iter() {
+                    let held = keys.contains_all(K::default().with(edge.key));
+
+                    if held || !keys.contains_all(edge.doors) {
+                        continue;
+                    }
+
+                    let mut next = robots.clone();
+                    next[robot] = edge.to;
+
+                    moves.push(((next, edge.pick_up(*keys)), edge.distance));
+                }
+            }
+
+            moves
+        },
+        |(_, keys)| *keys == all_keys,
+    );
+
+    let path = found.path?;
+
+    // The keys picked up by each move, in the order the robot reached them
+    let mut order = String::new();
+
+    for step in path.states.windows(2) {
+        let (from, keys) = &step[0];
+        let (to, picked) = &step[1];
+        let robot = (0..vault.robots).find(|&r| from[r] != to[r]).unwrap();
+        let edge = edges[from[robot]]
+            .iter()
+            .filter(|e| e.to == to[robot] && keys.contains_all(e.doors))
+            .filter(|e| e.pick_up(*keys) == *picked)
+            .min_by_key(|e| e.distance)
+            .unwrap();
+
+        for &key in edge.passed.iter().chain(Some(&edge.key)) {
+            if !keys.contains_all(K::default().with(key)) {
+                order.push(vault.keys[key]);
+            }
+        }
+    }
+
+    Some(Collection {
+        steps: path.cost,
+        order,
+    })
+}
+
+fn solve(map: &Map) -> Collection {
+    collect_keys::<u32>(map)
+        .or_else(|| collect_keys::<u128>(map))
+        .expect("Unable to collect every key")
+}
+
+fn part1(input: String) -> u32 {
+    solve(&build_map(&input)).steps as u32
+}
+
+fn replace(pos: &Vec2i, block: &Map, map: &mut Map) {
+    for (offset, tile) in block.iter() {
+        map[*pos + offset] = *tile;
+    }
+}
+
+// The vault split into four, unless it already is
+fn part2(input: String) -> u32 {
+    let mut map = build_map(&input);
+    let entrances = find_entrances(&map);
+
+    if let [entrance_position] = entrances[..] {
+        let block = build_map("@#@\n###\n@#@");
+        let block_upper_left = entrance_position + Vec2i::new(-1, -1);
+
+        // An entrance on the edge of the map has no room around it to split the vault
+        let fits = block
+            .iter()
+            .all(|(offset, _)| map.contains(block_upper_left + offset));
+
+        if fits {
+            replace(&block_upper_left, &block, &mut map);
+        }
+    }
+
+    solve(&map).steps as u32
+}
+
+pub struct Day18;
+
+impl Solution for Day18 {
+    fn part1(input: &str) -> Answer {
+        part1(input.trim().to_string()).into()
+    }
+
+    fn part2(input: &str) -> Answer {
+        part2(input.trim().to_string()).into()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn part1_test0() {
+        let input = "#########\n#b.A.@.a#\n#########".to_owned();
+
+        let steps = part1(input);
+
+        assert_eq!(steps, 8);
+    }
+
+    #[test]
+    fn part1_test1() {
+        let input = "########################\n#f.D.E.e.C.b.A.@.a.B.c.#\n######################.#\n#d.....................#\n########################".to_owned();
+
+        let steps = part1(input);
+
+        assert_eq!(steps, 86);
+    }
+
+    #[test]
+    fn part1_test2() {
+        let input = "########################\n#...............b.C.D.f#\n#.######################\n#.....@.a.B.c.d.A.e.F.g#\n########################".to_owned();
+
+        let steps = part1(input);
+
+        assert_eq!(steps, 132);
+    }
+
+    #[test]
+    fn part2_test0() {
+        let input = "#######\n#a.#Cd#\n##...##\n##.@.##\n##...##\n#cB#Ab#\n#######".to_owned();
+
+        let steps = part2(input);
+
+        assert_eq!(steps, 8);
+    }
+
+    #[test]
+    fn part2_test1() {
+        let input = "#############\n#DcBa.#.GhKl#\n#.###...#I###\n#e#d#.@.#j#k#\n###C#...###J#\n#fEbA.#.FgHi#\n#############".to_owned();
+
+        let steps = part2(input);
+
+        assert_eq!(steps, 32);
+    }
+
+    #[test]
+    fn key_order() {
+        let map = build_map("#########\n#b.A.@.a#\n#########");
+
+        assert_eq!(solve(&map), Collection { steps: 8, order: "ab".to_string() });
+    }
+
+    #[test]
+    fn looped_vault() {
+        // The short way to a is behind its own door, so the robot takes the long way round
+        let map = build_map("#########\n#b.@.A.a#\n###.###.#\n###.....#\n#########");
+
+        assert_eq!(
+            solve(&map),
+            Collection {
+                steps: 12,
+                order: "ba".to_string()
+            }
+        );
+    }
+
+    #[test]
+    fn entrance_on_the_edge() {
+        let input = "#@.a#\n#####".to_owned();
+
+        assert_eq!(part2(input), 2);
+    }
+
+    #[test]
+    fn wide_key_set() {
+        // 40 keys, more than fit in 32 bits, each one behind the door of the previous key
+        let keys = ('a'..='z').chain('α'..='ξ').collect::<Vec<_>>();
+        let mut corridor = String::from("@");
+
+        for (i, key) in keys.iter().enumerate() {
+            if i > 0 {
+                corridor.extend(keys[i - 1].to_uppercase());
+            }
+            corridor.push(*key);
+        }
+
+        let wall = "#".repeat(corridor.chars().count() + 2);
+        let map = build_map(&format!("{}\n#{}#\n{}", wall, corridor, wall));
+
+        assert_eq!(collect_keys::<u32>(&map), None);
+        assert_eq!(
+            solve(&map),
+            Collection {
+                steps: 79,
+                order: keys.iter().collect(),
+            }
+        );
+    }
+}
@@ -0,0 +1,107 @@
+use std::char::from_digit;
+use std::collections::VecDeque;
+
+use grid::*;
+use intcode::*;
+
+use crate::solution::*;
+
+const SIZE: usize = 100;
+
+fn _draw_view(map: &Grid<u8>) {
+    print!("{}", map.render(|&t| from_digit(t as u32, 10).unwrap()));
+}
+
+fn part1(program: &[i64]) -> i64 {
+    let mut map = Grid::new(50, 50, 0);
+
+    for position in map.positions().collect::<Vec<_>>() {
+        let mut vm = IntcodeVM::new(program);
+
+        let input_queue = &mut VecDeque::new();
+        let output_queue = &mut VecDeque::new();
+
+        input_queue.push_back(position.x as i64);
+        input_queue.push_back(position.y as i64);
+
+        run(&mut vm, input_queue, output_queue).expect("Intcode program failed");
+
+        map[position] = output_queue.pop_front().unwrap() as u8;
+    }
+
+    map.iter().map(|(_, &t)| t as i64).sum()
+}
+
+fn has_traction(coordinate: &Vec2u, program: &[i64]) -> bool {
+    let input_queue = &mut VecDeque::new();
+    let output_queue = &mut VecDeque::new();
+
+    input_queue.push_back(coordinate.x as i64);
+    input_queue.push_back(coordinate.y as i64);
+
+    let mut vm = IntcodeVM::new(program);
+    run(&mut vm, input_queue, output_queue).expect("Intcode program failed");
+
+    output_queue.pop_front().unwrap() == 1
+}
+
+fn fits_in_x(program: &[i64], upper_right_corner: &Vec2u) -> bool {
+    let mut runner = *upper_right_corner;
+    for _x in 0..SIZE {
+        runner.x -= 1;
+
+        if !has_traction(&runner, program) {
+            return false;
+        }
+    }
+
+    true
+}
+
+fn fits_in_y(program: &[i64], upper_left_corner: &Vec2u) -> bool {
+    let mut runner = *upper_left_corner;
+    for _y in 0..SIZE-1 {
+        runner.y += 1;
+        if !has_traction(&runner, program) {
+            return false;
+        }
+    }
+
+    true
+}
+
+fn part2(program: &[i64]) -> usize {
+    let mut upper_right_corner = Vec2u::new(4, 3);
+
+    loop {
+        if fits_in_x(program, &upper_right_corner) && fits_in_y(program, &Vec2u{x: upper_right_corner.x - SIZE + 1, y: upper_right_corner.y}) {
+            break;
+        }else{
+            upper_right_corner.y += 1;
+
+            loop {
+                let upper_right_corner_candidate = Vec2u{x: upper_right_corner.x + 1, y: upper_right_corner.y};
+
+                if !has_traction(&upper_right_corner_candidate, program) {
+                    break;
+                }
+
+                upper_right_corner.x += 1;
+            }
+        }
+    }
+
+    (upper_right_corner.x - SIZE + 1) * 10000 + upper_right_corner.y
+}
+
+pub struct Day19;
+
+impl Solution for Day19 {
+    fn part1(input: &str) -> Answer {
+        part1(&parse_program(input).expect("Invalid intcode program")).into()
+    }
+
+    fn part2(input: &str) -> Answer {
+        part2(&parse_program(input).expect("Invalid intcode program")).into()
+    }
+}
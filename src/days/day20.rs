@@ -0,0 +1,311 @@
+use std::rc::Rc;
+use std::cell::RefCell;
+use std::collections::{HashSet, HashMap, VecDeque};
+
+use grid::*;
+use search::*;
+
+use crate::solution::*;
+
+type Map = Grid<Tile>;
+
+#[derive(Debug, PartialEq, Copy, Clone)]
+enum Tile {
+    Empty,
+    Wall,
+    Path,
+    PortalHalf(char)
+}
+
+fn tile(c: char) -> Option<Tile> {
+    match c {
+        '#' => Some(Tile::Wall),
+        ' ' => Some(Tile::Empty),
+        '.' => Some(Tile::Path),
+        'A'..='Z' => Some(Tile::PortalHalf(c)),
+        _ => None,
+    }
+}
+
+fn build_map(input: &str) -> Map {
+    Grid::parse_padded(input.trim_start_matches('\n'), ' ', tile).expect("Invalid map")
+}
+
+fn get_frame(map: &Map) -> HashSet<Vec2i> {
+    let mut queue: VecDeque<Vec2i> = vec![Vec2i::ZERO].into_iter().collect();
+    let mut visited = HashSet::new();
+
+    while !queue.is_empty() {
+        let current = queue.pop_front().unwrap();
+        
+        if visited.contains(&current) {
+            continue;
+        } else {
+            visited.insert(current);
+        }
+
+        if let Some(Tile::Empty | Tile::PortalHalf(_)) = map.get(current) {
+            for neighbour in current.neighbours4() {
+                queue.push_back(neighbour);
+            }
+        }
+    }
+
+    visited
+}
+
+fn get_portals(map: &Map) -> HashMap<String, Vec<Vec2i>> {
+    let mut portals_coordinates: Vec<(Rc<RefCell<String>>, Vec<Vec2i>)> = Vec::new();
+    let mut coordinates_portals = HashMap::new();
+
+    for (coord, tile) in map.iter() {
+        if let Tile::PortalHalf(id) = tile {
+            if let Some(portal_id) = coordinates_portals.get(&coord) {
+                let portal_index = portals_coordinates.iter().position(|x| Rc::ptr_eq(&x.0, portal_id)).unwrap();
+                let (portal, coordinates) = portals_coordinates.get_mut(portal_index).unwrap();
+
+                portal.borrow_mut().push(*id);
+                coordinates.push(coord);
+            } else {
+                let portal = Rc::new(RefCell::new(id.to_string()));
+                portals_coordinates.push((portal.clone(), vec![coord]));
+
+                for neighbor in [coord + Vec2i::SOUTH, coord + Vec2i::EAST].iter() {
+                    coordinates_portals.insert(*neighbor, portal.clone());
+                }
+            }
+        }
+    }
+
+    let mut portals = HashMap::new();
+
+    for portal in portals_coordinates.iter() {
+        for portal_section in portal.1.iter() {
+            for neighbour in portal_section.neighbours4() {
+                if let Some(Tile::Path) = map.get(neighbour) {
+                    portals.entry(portal.0.borrow().clone()).or_insert_with(Vec::new).push(neighbour);
+                }
+            }
+        }
+    }
+
+    portals
+}
+
+#[derive(Debug)]
+struct Destination {
+    position: Vec2i,
+    level_difference: i32
+}
+
+impl Destination {
+    fn new(frame: &HashSet<Vec2i>, from_position: &Vec2i, to_position: Vec2i) -> Self {
+        if frame.contains(from_position) {
+            Destination{position: to_position, level_difference: -1}
+        } else {
+            Destination{position: to_position, level_difference: 1}
+        }
+    }
+}
+
+fn get_transfer_table(map: &Map, portals: &HashMap<String, Vec<Vec2i>>) -> HashMap<Vec2i,Destination> {
+    let mut transfer_table = HashMap::new();
+    let frame = get_frame(map);
+
+    for (_, positions) in portals.iter() {
+        if positions.len() != 2 {
+            continue;
+        }
+
+        let first_position = *positions.first().unwrap();
+        let last_position = *positions.last().unwrap();
+
+        transfer_table.insert(first_position, Destination::new(&frame, &first_position, last_position));
+        transfer_table.insert(last_position, Destination::new(&frame, &last_position, first_position));
+    }
+
+    transfer_table
+}
+
+// The open tiles next to a position, and where its portal leads if it's next to one
+fn get_moves<'a>(
+    map: &'a Map,
+    transfer_table: &'a HashMap<Vec2i, Destination>,
+    position: Vec2i,
+) -> impl Iterator<Item = (Vec2i, i32)> + 'a {
+    let steps = position
+        .neighbours4()
+        .filter(move |&n| matches!(map.get(n), Some(Tile::Path)))
+        .map(|n| (n, 0));
+
+    let teleport = transfer_table
+        .get(&position)
+        .map(|destination| (destination.position, destination.level_difference));
+
+    steps.chain(teleport)
+}
+
+fn part1(input: &str) -> u32 {
+    let map = build_map(input);
+    let portals = get_portals(&map);
+    let transfer_table = get_transfer_table(&map, &portals);
+    let start = *portals.get("AA").unwrap().first().unwrap(); 
+    let goal = *portals.get("ZZ").unwrap().first().unwrap();
+
+    let found = bfs(
+        start,
+        |&position| get_moves(&map, &transfer_table, position).map(|(p, _)| p),
+        |&position| position == goal,
+    );
+
+    found.path.map_or(u32::MAX, |path| path.cost as u32)
+}
+
+fn part2(input: &str) -> u32 {
+    let map = build_map(input);
+    let portals = get_portals(&map);
+    let transfer_table = get_transfer_table(&map, &portals);
+    let start = *portals.get("AA").unwrap().first().unwrap(); 
+    let goal = *portals.get("ZZ").unwrap().first().unwrap();
+
+    // The outer portals are walls on the outermost level
+    let found = bfs(
+        (start, 0),
+        |&(position, level)| {
+            get_moves(&map, &transfer_table, position)
+                .map(move |(p, difference)| (p, level + difference))
+                .filter(|&(_, level)| level >= 0)
+        },
+        |&state| state == (goal, 0),
+    );
+
+    /*for r in 0..map.height() {
+        let mut to_print = String::new();
+        for (c, col) in map.row(r).iter().enumerate() {
+            if let Some(destination) = transfer_table.get(&Vec2i::new(c as isize, r as isize)) {
+                match destination.level_difference {
+                    1 => to_print += "+",
+                    _ => to_print += "-"
+                }
+            }else{
+                match col {
+                    Tile::Empty => to_print += " ",
+                    Tile::Wall => to_print += "#",
+                    Tile::Path => to_print += ".",
+                    Tile::PortalHalf(c) => to_print += &c.to_string()
+                }
+            }
+        }
+        println!("{}", to_print);
+    }*/
+
+    found.path.map_or(u32::MAX, |path| path.cost as u32)
+}
+
+pub struct Day20;
+
+impl Solution for Day20 {
+    fn part1(input: &str) -> Answer {
+        part1(input).into()
+    }
+
+    fn part2(input: &str) -> Answer {
+        part2(input).into()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn get_input0() -> String {
+        r#"
+         A           
+         A           
+  #######.#########  
+  #######.........#  
+  #######.#######.#  
+  #######.#######.#  
+  #######.#######.#  
+  #####  B    ###.#  
+BC...##  C    ###.#  
+  ##.##       ###.#  
+  ##...DE  F  ###.#  
+  #####    G  ###.#  
+  #########.#####.#  
+DE..#######...###.#  
+  #.#########.###.#  
+FG..#########.....#  
+  ###########.#####  
+             Z       
+             Z     "#.to_owned()
+    }
+
+    fn get_input1() -> String {
+                  r#"
+                   A               
+                   A               
+  #################.#############  
+  #.#...#...................#.#.#  
+  #.#.#.###.###.###.#########.#.#  
+  #.#.#.......#...#.....#.#.#...#  
+  #.#########.###.#####.#.#.###.#  
+  #.............#.#.....#.......#  
+  ###.###########.###.#####.#.#.#  
+  #.....#        A   C    #.#.#.#  
+  #######        S   P    #####.#  
+  #.#...#                 #......VT
+  #.#.#.#                 #.#####  
+  #...#.#               YN....#.#  
+  #.###.#                 #####.#  
+DI....#.#                 #.....#  
+  #####.#                 #.###.#  
+ZZ......#               QG....#..AS
+  ###.###                 #######  
+JO..#.#.#                 #.....#  
+  #.#.#.#                 ###.#.#  
+  #...#..DI             BU....#..LF
+  #####.#                 #.#####  
+YN......#               VT..#....QG
+  #.###.#                 #.###.#  
+  #.#...#                 #.....#  
+  ###.###    J L     J    #.#.###  
+  #.....#    O F     P    #.#...#  
+  #.###.#####.#.#####.#####.###.#  
+  #...#.#.#...#.....#.....#.#...#  
+  #.#####.###.###.#.#.#########.#  
+  #...#.#.....#...#.#.#.#.....#.#  
+  #.###.#####.###.###.#.#.#######  
+  #.#.........#...#.............#  
+  #########.###.###.#############  
+           B   J   C               
+           U   P   P             "#.to_owned()
+    }
+
+    #[test]
+    fn part1_test0() {
+        let input = get_input0();
+
+        let steps = part1(&input);
+
+        assert_eq!(steps, 23);
+    }
+
+    #[test]
+    fn part1_test1() {
+        let input = get_input1();
+
+        let steps = part1(&input);
+
+        assert_eq!(steps, 58);
+    }
+
+    #[test]
+    fn part2_test0() {
+        let input = get_input0();
+
+        let steps = part2(&input);
+
+        assert_eq!(steps, 26);
+    }
+}
@@ -0,0 +1,40 @@
+use intcode::parse_program;
+use intcode::springscript::{compile, execute, solve, Attempt, Formula, SolveError, Speed};
+
+use crate::solution::*;
+
+// Learns a script from the hulls the springdroid falls into
+fn learn(program: &[i64], speed: Speed) -> Result<i64, SolveError> {
+    solve(program, speed).map(|solution| solution.damage)
+}
+
+// Jump if there is a hole in the next three tiles and ground to land on
+fn part1(program: &[i64]) -> Result<i64, SolveError> {
+    let formula = Formula::parse("!(A & B & C) & D").unwrap();
+    let script = compile(&formula, Speed::Walk).map_err(SolveError::Compile)?;
+
+    match execute(program, &script)? {
+        Attempt::Damage(damage) => Ok(damage),
+        Attempt::Fell(_) => learn(program, Speed::Walk),
+    }
+}
+
+fn part2(program: &[i64]) -> Result<i64, SolveError> {
+    learn(program, Speed::Run)
+}
+
+pub struct Day21;
+
+impl Solution for Day21 {
+    fn part1(input: &str) -> Answer {
+        let program = parse_program(input).expect("Invalid intcode program");
+
+        part1(&program).expect("The springdroid fell").into()
+    }
+
+    fn part2(input: &str) -> Answer {
+        let program = parse_program(input).expect("Invalid intcode program");
+
+        part2(&program).expect("The springdroid fell").into()
+    }
+}
@@ -1,7 +1,7 @@
 use regex::Regex;
 use lazy_static::lazy_static;
 
-use common::*;
+use crate::solution::*;
 
 #[derive(Debug, PartialEq, Copy, Clone)]
 enum Op {
@@ -11,6 +11,7 @@ enum Op {
 }
 
 impl Op {
+    #[cfg(test)]
     fn exec(&self, deck: &mut Vec<u64>) {
         match self {
             Op::DealNew => deck.reverse(),
@@ -18,17 +19,16 @@ impl Op {
                 assert!(*increment < deck.len());
 
                 let sz = deck.len();
-                let mut other = Vec::new();
-                other.resize(sz, 0);
+                let mut other = vec![0; sz];
 
-                for (index, value) in deck.into_iter().enumerate() {
+                for (index, value) in deck.iter_mut().enumerate() {
                     other[(index * increment) % sz] = *value;
                 }
 
                 *deck = other;
             },
             Op::Cut(offset) => {
-                assert!((offset.abs() as usize) < deck.len());
+                assert!(offset.unsigned_abs() < deck.len());
 
                 let sz = deck.len();
 
@@ -45,84 +45,84 @@ impl Op {
         }
     }
 
+    #[cfg(test)]
     fn forward(&self, index: usize, deck_size: usize) -> usize {
         match self {
             Op::DealNew => {
-                return (deck_size - index - 1).rem_euclid(deck_size);
+                (deck_size - index - 1).rem_euclid(deck_size)
             },
             Op::DealIncrement(increment) => {
                 assert!(*increment < deck_size);
 
-                return mod_mul(index as i64, *increment as i64, deck_size as u64) as usize;
+                mod_mul(index as i64, *increment as i64, deck_size as u64) as usize
             },
             Op::Cut(offset) => {
-                assert!((offset.abs() as usize) < deck_size);
+                assert!(offset.unsigned_abs() < deck_size);
 
-                return (index as isize - *offset).rem_euclid(deck_size as isize) as usize;
+                (index as isize - *offset).rem_euclid(deck_size as isize) as usize
             }
         }
     }
     
     fn forward_collect(&self, lc: &mut LinearCongruence) {
-        let lc_new;
-        match self {
+        let lc_new = match self {
             Op::DealNew => {
-                lc_new = LinearCongruence{a: -1, b: lc.m - 1, m: lc.m};
-            },
+                LinearCongruence{a: -1, b: lc.m - 1, m: lc.m}
+            }
             Op::DealIncrement(increment) => {
                 assert!((*increment as i64) < lc.m);
 
-                lc_new = LinearCongruence{a: *increment as i64, b: 0, m: lc.m};
-            },
+                LinearCongruence{a: *increment as i64, b: 0, m: lc.m}
+            }
             Op::Cut(offset) => {
                 assert!((offset.abs() as i64) < lc.m);
 
-                lc_new = LinearCongruence{a: 1, b: -*offset as i64, m: lc.m};
+                LinearCongruence{a: 1, b: -*offset as i64, m: lc.m}
             }
-        }
+        };
 
         *lc = lc_new.combine(lc);
     }
 
+    #[cfg(test)]
     fn reverse(&self, index: usize, deck_size: usize) -> usize {
         match self {
             Op::DealNew => {
-                return (deck_size - index - 1).rem_euclid(deck_size);
+                (deck_size - index - 1).rem_euclid(deck_size)
             },
             Op::DealIncrement(increment) => {
                 assert!(*increment < deck_size);
 
                 let y = modular_multiplicative_inverse(*increment as i64, deck_size as i64);
 
-                return mod_mul(index as i64, y as i64, deck_size as u64) as usize;
+                mod_mul(index as i64, y, deck_size as u64) as usize
             },
             Op::Cut(offset) => {
-                assert!((offset.abs() as usize) < deck_size);
+                assert!(offset.unsigned_abs() < deck_size);
 
-                return (index as isize + *offset).rem_euclid(deck_size as isize) as usize;
+                (index as isize + *offset).rem_euclid(deck_size as isize) as usize
             }
         }
     }
 
     fn reverse_collect(&self, lc: &mut LinearCongruence) {
-        let lc_new;
-        match self {
+        let lc_new = match self {
             Op::DealNew => {
-                lc_new = LinearCongruence{a: -1, b: lc.m - 1, m: lc.m};
-            },
+                LinearCongruence{a: -1, b: lc.m - 1, m: lc.m}
+            }
             Op::DealIncrement(increment) => {
                 assert!((*increment as i64) < lc.m);
 
                 let y = modular_multiplicative_inverse(*increment as i64, lc.m);
 
-                lc_new = LinearCongruence{a: y, b: 0, m: lc.m};
-            },
+                LinearCongruence{a: y, b: 0, m: lc.m}
+            }
             Op::Cut(offset) => {
                 assert!((offset.abs() as i64) < lc.m);
 
-                lc_new = LinearCongruence{a: 1, b: *offset as i64, m: lc.m};
+                LinearCongruence{a: 1, b: *offset as i64, m: lc.m}
             }
-        }
+        };
 
         *lc = lc_new.combine(lc);
     }
@@ -137,11 +137,11 @@ impl From<&str> for Op {
         }
 
         if let Some(capture) = CUT.captures(str) {
-            return Op::Cut(capture.get(1).unwrap().as_str().parse::<isize>().unwrap());
+            Op::Cut(capture.get(1).unwrap().as_str().parse::<isize>().unwrap())
         } else if let Some(capture) = DEAL_WITH_INCREMENT.captures(str) {
-            return Op::DealIncrement(capture.get(1).unwrap().as_str().parse::<usize>().unwrap());
+            Op::DealIncrement(capture.get(1).unwrap().as_str().parse::<usize>().unwrap())
         } else if DEAL_INTO_NEW.is_match(str) {
-            return Op::DealNew;
+            Op::DealNew
         }else {
             panic!("Unknown operation");
         }
@@ -184,7 +184,7 @@ fn mod_pow(a: u64, b: u64, m: u64) -> u64 {
 
     let m = m as u128;
     let mut res = 1u128;
-    let mut a = (a as u128).rem_euclid(m) as u128;
+    let mut a = (a as u128).rem_euclid(m);
     let mut b = b as u128;
 
     while b > 0 {
@@ -199,22 +199,14 @@ fn mod_pow(a: u64, b: u64, m: u64) -> u64 {
     res as u64
 }
 
-fn apply(operations: &Vec<Op>, deck: &mut Vec<u64>) {
+#[cfg(test)]
+fn apply(operations: &[Op], deck: &mut Vec<u64>) {
     for op in operations.iter() {
         op.exec(deck);
     }
 }
 
-fn forward(index: usize, operations: &Vec<Op>, deck_size: usize) -> usize {
-    let mut index = index;
-    for op in operations.iter() {
-        index = op.forward(index, deck_size);
-    }
-
-    index
-}
-
-fn forward_collect(operations: &Vec<Op>, deck_size: usize) -> LinearCongruence {
+fn forward_collect(operations: &[Op], deck_size: usize) -> LinearCongruence {
     let mut lc = LinearCongruence{a: 1, b: 0, m: deck_size as i64};
 
     for op in operations.iter() {
@@ -224,16 +216,7 @@ fn forward_collect(operations: &Vec<Op>, deck_size: usize) -> LinearCongruence {
     lc
 }
 
-fn reverse(index: usize, operations: &Vec<Op>, deck_size: usize) -> usize {
-    let mut index = index;
-    for op in operations.iter().rev() {
-        index = op.reverse(index, deck_size);
-    }
-
-    index
-}
-
-fn reverse_collect(operations: &Vec<Op>, deck_size: usize) -> LinearCongruence {
+fn reverse_collect(operations: &[Op], deck_size: usize) -> LinearCongruence {
     let mut lc = LinearCongruence{a: 1, b: 0, m: deck_size as i64};
 
     for op in operations.iter().rev() {
@@ -243,8 +226,8 @@ fn reverse_collect(operations: &Vec<Op>, deck_size: usize) -> LinearCongruence {
     lc
 }
 
-fn part1(input: &String) -> Result<usize,()> {
-    let operations = input.lines().into_iter().map(|line| Op::from(line)).collect::<Vec<_>>();
+fn part1(input: &str) -> Result<usize,()> {
+    let operations = input.lines().map(Op::from).collect::<Vec<_>>();
     let deck_size = 10007;
 
     let lc = forward_collect(&operations, deck_size);
@@ -291,7 +274,7 @@ fn modular_multiplicative_inverse(n: i64, s: i64) -> i64 {
         a0 += s as i128;
     }
 
-    return a0 as i64;
+    a0 as i64
 }
 
 #[derive(Debug)]
@@ -312,14 +295,14 @@ impl LinearCongruence {
 
         let m = self.m;
 
-        LinearCongruence{a: mod_mul(self.a as i64, other.a as i64, m as u64), b: (mod_mul(self.a as i64, other.b as i64, m as u64) + self.b).rem_euclid(m), m: m}
+        LinearCongruence{a: mod_mul(self.a, other.a, m as u64), b: (mod_mul(self.a, other.b, m as u64) + self.b).rem_euclid(m), m}
     }
 
     fn skip_forward(&self, times: u64) -> LinearCongruence {
         assert!(self.a > 0);
-        let apt = mod_pow(self.a as u64, times as u64, self.m as u64);
+        let apt = mod_pow(self.a as u64, times, self.m as u64);
         let a = apt as i64;
-        let b = mod_mul(mod_mul((apt - 1) as i64, modular_multiplicative_inverse(self.a - 1, self.m) as i64, self.m as u64), self.b, self.m as u64);
+        let b = mod_mul(mod_mul((apt - 1) as i64, modular_multiplicative_inverse(self.a - 1, self.m), self.m as u64), self.b, self.m as u64);
         let m = self.m;
 
         LinearCongruence{a, b, m}
@@ -331,15 +314,15 @@ impl LinearCongruence {
         let b_inv = mod_mul(-a_inv, self.b, self.m as u64);
         let a_inv_pt = mod_pow(a_inv as u64, times, self.m as u64);
         let a = a_inv_pt as i64;
-        let b = mod_mul(mod_mul((a_inv_pt - 1) as i64, modular_multiplicative_inverse(a_inv - 1, self.m) as i64, self.m as u64) as i64, b_inv as i64, self.m as u64) as i64;
+        let b = mod_mul(mod_mul((a_inv_pt - 1) as i64, modular_multiplicative_inverse(a_inv - 1, self.m), self.m as u64), b_inv, self.m as u64);
         let m = self.m;
 
         LinearCongruence{a, b, m}
     }
 }
 
-fn part2(input: &String) -> Result<u64,()> {
-    let operations = input.lines().into_iter().map(|line| Op::from(line)).collect::<Vec<_>>();
+fn part2(input: &str) -> Result<u64,()> {
+    let operations = input.lines().map(Op::from).collect::<Vec<_>>();
     let deck_size = 119_315_717_514_047;
     let times = 101_741_582_076_661;
 
@@ -350,8 +333,8 @@ fn part2(input: &String) -> Result<u64,()> {
     Ok(start_index as u64)
 }
 
-fn part2_v2(input: &String) -> Result<u64,()> {
-    let operations = input.lines().into_iter().map(|line| Op::from(line)).collect::<Vec<_>>();
+fn part2_v2(input: &str) -> Result<u64,()> {
+    let operations = input.lines().map(Op::from).collect::<Vec<_>>();
     let deck_size = 119_315_717_514_047;
     let times = 101_741_582_076_661;
 
@@ -362,7 +345,23 @@ fn part2_v2(input: &String) -> Result<u64,()> {
     Ok(start_index as u64)
 }
 
-task!(22.txt, part1, part2, part2_v2);
+pub struct Day22;
+
+impl Solution for Day22 {
+    fn part1(input: &str) -> Answer {
+        part1(input.trim()).unwrap().into()
+    }
+
+    fn part2(input: &str) -> Answer {
+        let input = input.trim().to_string();
+        let card = part2(&input).unwrap();
+
+        // Going forwards and skipping back has to agree with going backwards
+        debug_assert_eq!(part2_v2(&input), Ok(card));
+
+        card.into()
+    }
+}
 
 #[cfg(test)]
 mod tests {
@@ -566,8 +565,8 @@ mod tests {
         // Reference
         apply(&ops, &mut deck);
 
-        for i in 0..deck_size {
-            assert_eq!(lc.apply(deck[i] as i64) as usize, i);
+        for (i, &card) in deck.iter().enumerate() {
+            assert_eq!(lc.apply(card as i64) as usize, i);
         }
     }
 
@@ -582,8 +581,8 @@ mod tests {
         // Reference
         apply(&ops, &mut deck);
 
-        for i in 0..deck_size {
-            assert_eq!(lc.apply(deck[i] as i64) as usize, i);
+        for (i, &card) in deck.iter().enumerate() {
+            assert_eq!(lc.apply(card as i64) as usize, i);
         }
     }
 
@@ -598,8 +597,8 @@ mod tests {
         // Reference
         apply(&ops, &mut deck);
         
-        for i in 0..deck_size {
-            assert_eq!(lc.apply(deck[i] as i64) as usize, i);
+        for (i, &card) in deck.iter().enumerate() {
+            assert_eq!(lc.apply(card as i64) as usize, i);
         }
     }
 
@@ -614,8 +613,8 @@ mod tests {
         // Reference
         apply(&ops, &mut deck);
         
-        for i in 0..deck_size {
-            assert_eq!(lc.apply(deck[i] as i64) as usize, i);
+        for (i, &card) in deck.iter().enumerate() {
+            assert_eq!(lc.apply(card as i64) as usize, i);
         }
     }
 
@@ -752,8 +751,8 @@ mod tests {
         
         let lc = lc.skip_forward(repetitions);
 
-        for i in 0..deck_size {
-            assert_eq!(lc.apply(deck[i] as i64) as usize, i);
+        for (i, &card) in deck.iter().enumerate() {
+            assert_eq!(lc.apply(card as i64) as usize, i);
         }
     }
 
@@ -776,8 +775,8 @@ mod tests {
 
         let lc = lc.skip_forward(repetitions);
 
-        for i in 0..deck_size {
-            assert_eq!(lc.apply(deck[i] as i64) as usize, i);
+        for (i, &card) in deck.iter().enumerate() {
+            assert_eq!(lc.apply(card as i64) as usize, i);
         }
     }
 
@@ -803,8 +802,8 @@ mod tests {
 
         let lc = lc.skip_backward(repetitions);
 
-        for i in 0..deck_size {
-            assert_eq!(lc.apply(i as i64) as u64, deck[i]);
+        for (i, &card) in deck.iter().enumerate() {
+            assert_eq!(lc.apply(i as i64) as u64, card);
         }
     }
 
@@ -827,8 +826,8 @@ mod tests {
 
         let lc = lc.skip_backward(repetitions);
 
-        for i in 0..deck_size {
-            assert_eq!(lc.apply(deck[i] as i64) as u64, i as u64);
+        for (i, &card) in deck.iter().enumerate() {
+            assert_eq!(lc.apply(card as i64) as u64, i as u64);
         }
     }
 
@@ -848,8 +847,8 @@ mod tests {
         let deck = deck.into_iter().map(|x| lc_forward.apply(x as i64) as u64).collect::<Vec<_>>();
         let deck = deck.into_iter().map(|x| lc_reverse.apply(x as i64) as u64).collect::<Vec<_>>();
 
-        for i in 0..deck_size {
-            assert_eq!(deck[i], i as u64);
+        for (i, &card) in deck.iter().enumerate() {
+            assert_eq!(card, i as u64);
         }
     }
 }
@@ -0,0 +1,84 @@
+use std::num::NonZeroUsize;
+
+use intcode::*;
+
+use crate::solution::*;
+
+// Instructions each computer runs before the next one gets a turn
+const QUANTUM: u64 = 1000;
+
+const NAT: i64 = 255;
+
+fn initialize_network(program: &[i64]) -> Network {
+    let mut network = Network::new();
+
+    for address in 0..50 {
+        let computer = network.add_node(IntcodeVM::new(program));
+
+        network.send(computer, &[address]);
+        network.connect(computer, Link::Addressed);
+        network.set_packet_size(computer, NonZeroUsize::new(3).unwrap());
+        network.set_idle_input(computer, -1);
+    }
+
+    network
+}
+
+fn run_network(mut network: Network) -> Result<i64, String> {
+    match network.run(Schedule::RoundRobin { quantum: QUANTUM }) {
+        Ok(Outcome::Stopped(y)) => Ok(y),
+        Ok(outcome) => Err(format!("Network stopped without an answer: {:?}", outcome)),
+        Err(e) => Err(e.to_string()),
+    }
+}
+
+fn part1(program: &[i64]) -> Result<i64, String> {
+    let mut network = initialize_network(program);
+    network.intercept(NAT, |_: NodeId, packet: &[i64]| Control::Stop(packet[1]));
+
+    run_network(network)
+}
+
+fn part2(program: &[i64]) -> Result<i64, String> {
+    let mut network = initialize_network(program);
+    network.intercept(NAT, Nat::new(0));
+
+    run_network(network)
+}
+
+pub struct Day23;
+
+impl Solution for Day23 {
+    fn part1(input: &str) -> Answer {
+        let program = parse_program(input).expect("Invalid intcode program");
+
+        part1(&program).unwrap().into()
+    }
+
+    fn part2(input: &str) -> Answer {
+        let program = parse_program(input).expect("Invalid intcode program");
+
+        part2(&program).unwrap().into()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn part2_idle_without_nat_packet() {
+        // Reads its address, then polls for packets forever
+        let program = vec![3, 100, 3, 101, 1105, 1, 2];
+
+        let result = part2(&program);
+        let deadlock = Outcome::Deadlock {
+            blocked: (0..50).collect(),
+        };
+
+        assert_eq!(
+            result,
+            Err(format!("Network stopped without an answer: {:?}", deadlock))
+        );
+    }
+}
@@ -0,0 +1,391 @@
+use std::collections::{VecDeque, HashSet};
+
+use grid::*;
+
+use crate::solution::*;
+
+type Map = Grid<Tile>;
+type RecursiveMap = VecDeque<Map>;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+enum Tile {
+    Bug,
+    Empty
+}
+
+fn tile(c: char) -> Option<Tile> {
+    match c {
+        '#' => Some(Tile::Bug),
+        '.' => Some(Tile::Empty),
+        _ => None,
+    }
+}
+
+const CENTER: Vec2i = Vec2i { x: 2, y: 2 };
+
+fn get_neighbours(map: &Map, position: Vec2i, check_center: bool) -> i32 {
+    let mut neighbours = map
+        .neighbours4(position)
+        .filter(|&n| map[n] == Tile::Bug)
+        .count() as i32;
+
+    if !check_center && position.manhattan(CENTER) == 1 && map[CENTER] == Tile::Bug {
+        neighbours -= 1;
+    }
+
+    neighbours
+}
+
+fn advance(map: &Map) -> Map {
+    let mut new_map = map.clone();
+
+    for position in map.positions() {
+        let neighbours = get_neighbours(map, position, true);
+
+        if new_map[position] == Tile::Bug && neighbours != 1 {
+            new_map[position] = Tile::Empty;
+        } else if new_map[position] == Tile::Empty && (neighbours == 1 || neighbours == 2) {
+            new_map[position] = Tile::Bug;
+        }
+    }
+
+    new_map
+}
+
+fn is_on_outer_northern_edge(row: usize, _col: usize) -> bool {
+    if row == 0 {
+        return true;
+    }
+
+    false
+}
+
+fn check_outwards_north(map: &RecursiveMap, level: usize) -> i32 {
+    if let Tile::Bug = map[level - 1][Vec2i::new(2, 1)] {
+        return 1;
+    }
+
+    0
+}
+
+fn is_on_outer_eastern_edge(_row: usize, col: usize) -> bool {
+    if col == 4 {
+        return true;
+    }
+
+    false
+}
+
+fn check_outwards_east(map: &RecursiveMap, level: usize) -> i32 {
+    if let Tile::Bug = map[level - 1][Vec2i::new(3, 2)] {
+        return 1;
+    }
+
+    0
+}
+
+fn is_on_outer_southern_edge(row: usize, _col: usize) -> bool {
+    if row == 4 {
+        return true;
+    }
+
+    false
+}
+
+fn check_outwards_south(map: &RecursiveMap, level: usize) -> i32 {
+    if let Tile::Bug = map[level - 1][Vec2i::new(2, 3)] {
+        return 1;
+    }
+
+    0
+}
+
+fn is_on_outer_western_edge(_row: usize, col: usize) -> bool {
+    if col == 0 {
+        return true;
+    }
+
+    false
+}
+
+fn check_outwards_west(map: &RecursiveMap, level: usize) -> i32 {
+    if let Tile::Bug = map[level - 1][Vec2i::new(1, 2)] {
+        return 1;
+    }
+
+    0
+}
+
+fn is_on_inner_northern_edge(row: usize, col: usize) -> bool {
+    if row == 1 && col == 2 {
+        return true;
+    }
+
+    false
+}
+
+fn check_inwards_north(map: &RecursiveMap, level: usize) -> i32 {
+    let mut neighbours = 0;
+
+    let level = &map[level + 1];
+    level.row(0).iter().for_each(|t| if *t == Tile::Bug {
+        neighbours += 1;
+    });
+
+    neighbours
+}
+
+fn is_on_inner_eastern_edge(row: usize, col: usize) -> bool {
+    if row == 2 && col == 3 {
+        return true;
+    }
+
+    false
+}
+
+fn check_inwards_east(map: &RecursiveMap, level: usize) -> i32 {
+    let mut neighbours = 0;
+
+    let level = &map[level + 1];
+    for t in level.column(level.width() - 1) {
+        if Tile::Bug == *t {
+            neighbours += 1;
+        }
+    }
+
+    neighbours
+}
+
+fn is_on_inner_southern_edge(row: usize, col: usize) -> bool {
+    if row == 3 && col == 2 {
+        return true;
+    }
+
+    false
+}
+
+fn check_inwards_south(map: &RecursiveMap, level: usize) -> i32 {
+    let mut neighbours = 0;
+
+    let level = &map[level + 1];
+    level.row(level.height() - 1).iter().for_each(|t| if *t == Tile::Bug {
+        neighbours += 1;
+    });
+
+    neighbours
+}
+
+fn is_on_inner_western_edge(row: usize, col: usize) -> bool {
+    if row == 2 && col == 1 {
+        return true;
+    }
+
+    false
+}
+
+fn check_inwards_west(map: &RecursiveMap, level: usize) -> i32 {
+    let mut neighbours = 0;
+
+    let level = &map[level + 1];
+    for t in level.column(0) {
+        if Tile::Bug == *t {
+            neighbours += 1;
+        }
+    }
+
+    neighbours
+}
+
+fn advance_recursive(map: &RecursiveMap) -> RecursiveMap {
+    let mut new_map = map.clone();
+
+    for level in 0..map.len() {
+        for position in map[level].positions() {
+            let (r, c) = (position.y as usize, position.x as usize);
+
+            if position == CENTER {
+                continue;
+            }
+            let mut neighbours = get_neighbours(&map[level], position, false);
+
+            if is_on_outer_northern_edge(r, c) && level > 0 {
+                neighbours += check_outwards_north(map, level);
+            }
+            if is_on_outer_eastern_edge(r, c) && level > 0 {
+                neighbours += check_outwards_east(map, level);
+            }
+            if is_on_outer_southern_edge(r, c) && level > 0 {
+                neighbours += check_outwards_south(map, level);
+            }
+            if is_on_outer_western_edge(r, c) && level > 0 {
+                neighbours += check_outwards_west(map, level);
+            }
+
+            if is_on_inner_northern_edge(r, c) && level < map.len() - 1 {
+                neighbours += check_inwards_north(map, level);
+            }
+            if is_on_inner_eastern_edge(r, c) && level < map.len() - 1 {
+                neighbours += check_inwards_east(map, level);
+            }
+            if is_on_inner_southern_edge(r, c) && level < map.len() - 1 {
+                neighbours += check_inwards_south(map, level);
+            }
+            if is_on_inner_western_edge(r, c) && level < map.len() - 1 {
+                neighbours += check_inwards_west(map, level);
+            }
+
+            if new_map[level][position] == Tile::Bug && neighbours != 1 {
+                new_map[level][position] = Tile::Empty;
+            } else if new_map[level][position] == Tile::Empty && (neighbours == 1 || neighbours == 2) {
+                new_map[level][position] = Tile::Bug;
+            }
+        }
+    }
+
+    new_map
+}
+
+fn get_map(str: &str) -> Map {
+    Grid::parse(str.trim(), tile).expect("Invalid map")
+}
+
+fn part1(input: &str) -> Result<i64, ()> {
+    let mut map = get_map(input);
+
+    let mut seen_layouts = HashSet::new();
+
+    loop {
+        let next = advance(&map);
+        seen_layouts.insert(map);
+        map = next;
+
+        if seen_layouts.contains(&map) {
+            let mut diversity = 0;
+            for (position, t) in map.iter() {
+                if *t == Tile::Bug {
+                    diversity += 2i64.pow(position.y as u32 * 5 + position.x as u32);
+                }
+            }
+
+            return Ok(diversity);
+        }
+    }
+}
+
+fn get_bugs(level: &Map) -> i32 {
+    level.iter().filter(|(_, t)| **t == Tile::Bug).count() as i32
+}
+
+fn get_empty_level() -> Map {
+    Grid::new(5, 5, Tile::Empty)
+}
+
+fn expand(map: &mut RecursiveMap) {
+    let bugs = get_bugs(map.front().unwrap());
+
+    if bugs > 0 {
+        map.push_front(get_empty_level());
+    }
+
+    let bugs = get_bugs(map.back().unwrap());
+
+    if bugs > 0 {
+        map.push_back(get_empty_level());
+    }
+}
+
+fn _print(map: &RecursiveMap) {
+    for (level_idx, level) in map.iter().enumerate() {
+        println!("Level idx: {}", level_idx);
+        print!("{}", level.render(|t| match t {
+            Tile::Bug => '#',
+            Tile::Empty => '.',
+        }));
+    }
+}
+
+fn part2(input: &str) -> Result<i32, ()> {
+    let map = get_map(input);
+    let mut map = vec![map].into_iter().collect();
+
+    for _ in 0..200 {
+        expand(&mut map);
+        //print(&map);
+        //println!();
+
+        map = advance_recursive(&map);
+    }
+
+    let mut total_bugs = 0;
+    for level in map.iter() {
+        total_bugs += get_bugs(level);
+    }
+    
+    Ok(total_bugs)
+}
+
+pub struct Day24;
+
+impl Solution for Day24 {
+    fn part1(input: &str) -> Answer {
+        part1(input).unwrap().into()
+    }
+
+    fn part2(input: &str) -> Answer {
+        part2(input).unwrap().into()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn part1_example() {
+        let input = r#"
+....#
+#..#.
+#..##
+..#..
+#...."#.trim_start().to_string();
+
+        let map = get_map(&input);
+        let map = advance(&map);
+
+        assert_eq!(map.row(0), [Tile::Bug,Tile::Empty,Tile::Empty,Tile::Bug,Tile::Empty]);
+        assert_eq!(map.row(1), [Tile::Bug,Tile::Bug,Tile::Bug,Tile::Bug,Tile::Empty]);
+        assert_eq!(map.row(2), [Tile::Bug,Tile::Bug,Tile::Bug,Tile::Empty,Tile::Bug]);
+        assert_eq!(map.row(3), [Tile::Bug,Tile::Bug,Tile::Empty,Tile::Bug,Tile::Bug]);
+        assert_eq!(map.row(4), [Tile::Empty,Tile::Bug,Tile::Bug,Tile::Empty,Tile::Empty]);
+    }
+
+    #[test]
+    fn part2_example() {
+        let input = r#"
+....#
+#..#.
+#..##
+..#..
+#...."#.trim_start().to_string();
+
+
+        let map = get_map(&input);
+        let mut map = vec![map].into_iter().collect();
+
+        for _ in 0..10 {
+            expand(&mut map);
+            _print(&map);
+            println!();
+
+            map = advance_recursive(&map);
+        }
+
+        let mut total_bugs = 0;
+        for level in map.iter() {
+            total_bugs += get_bugs(level);
+        }
+
+        assert_eq!(total_bugs, 99);
+    }
+
+}
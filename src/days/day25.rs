@@ -0,0 +1,354 @@
+use std::collections::{HashMap, HashSet, VecDeque};
+
+use intcode::*;
+
+use crate::solution::*;
+
+// Instructions a command may take before the game counts as stuck in a loop
+const STEP_LIMIT: u64 = 1_000_000;
+
+const CHECKPOINT: &str = "Security Checkpoint";
+
+#[derive(Debug, Clone, PartialEq)]
+struct Room {
+    name: String,
+    doors: Vec<String>,
+    items: Vec<String>,
+}
+
+// Every room described in the output, in order
+fn parse_rooms(text: &str) -> Vec<Room> {
+    let mut rooms: Vec<Room> = Vec::new();
+    let mut list = None;
+
+    for line in text.lines() {
+        let line = line.trim();
+
+        if let Some(name) = line.strip_prefix("== ").and_then(|l| l.strip_suffix(" ==")) {
+            rooms.push(Room {
+                name: name.to_string(),
+                doors: Vec::new(),
+                items: Vec::new(),
+            });
+            list = None;
+        } else if line == "Doors here lead:" || line == "Items here:" {
+            list = Some(line);
+        } else if let (Some(entry), Some(room)) = (line.strip_prefix("- "), rooms.last_mut()) {
+            match list {
+                Some("Doors here lead:") => room.doors.push(entry.to_string()),
+                Some("Items here:") => room.items.push(entry.to_string()),
+                _ => (),
+            }
+        } else {
+            list = None;
+        }
+    }
+
+    rooms
+}
+
+enum Response {
+    Text(String),
+    Halted(String),
+    // The command didn't finish within the step limit
+    Stuck,
+}
+
+struct Game {
+    vm: IntcodeVM,
+}
+
+impl Game {
+    fn restore(snapshot: &Snapshot) -> Game {
+        Game {
+            vm: snapshot.restore().expect("Unable to restore snapshot"),
+        }
+    }
+
+    fn snapshot(&self) -> Snapshot {
+        Snapshot::capture(&self.vm, &VecDeque::new(), &VecDeque::new())
+    }
+
+    fn send(&mut self, command: Option<&str>) -> Response {
+        let mut input = VecDeque::new();
+
+        if let Some(command) = command {
+            input.extend(encode_ascii(command));
+            input.push_back(10);
+        }
+
+        let mut output = Vec::new();
+        let status = run_for(&mut self.vm, &mut input, &mut output, STEP_LIMIT);
+        let text = render_ascii(&output);
+
+        match status {
+            Ok(VMStatus::EmptyInputBuffer) => Response::Text(text),
+            Ok(VMStatus::Halted) => Response::Halted(text),
+            _ => Response::Stuck,
+        }
+    }
+
+    fn command(&mut self, command: &str) -> Result<String, String> {
+        match self.send(Some(command)) {
+            Response::Text(text) => Ok(text),
+            _ => Err(format!("'{}' ended the game", command)),
+        }
+    }
+}
+
+// The doors of every room and where they lead, None for doors not taken
+type ShipMap = HashMap<String, HashMap<String, Option<String>>>;
+
+struct Explored {
+    map: ShipMap,
+    // Room and item of every item that doesn't end or trap the game
+    items: Vec<(String, String)>,
+    // The door out of the checkpoint that weighs the droid
+    floor: Option<String>,
+}
+
+// Taking the item, or moving after taking it, must not end the game, loop forever or leave
+// the droid unable to move. Items in a room without doors can't be tried, so aren't safe.
+fn is_safe(snapshot: &Snapshot, room: &Room, item: &str) -> bool {
+    let door = match room.doors.first() {
+        Some(door) => door,
+        None => return false,
+    };
+
+    let mut game = Game::restore(snapshot);
+
+    if game.command(&format!("take {}", item)).is_err() {
+        return false;
+    }
+
+    match game.send(Some(door)) {
+        Response::Text(text) => parse_rooms(&text).iter().any(|r| r.name != room.name),
+        _ => false,
+    }
+}
+
+// Depth first through every door, going back in time with snapshots instead of walking
+fn explore(game: Game, room: Room, explored: &mut Explored) {
+    let snapshot = game.snapshot();
+
+    for item in room.items.iter() {
+        if is_safe(&snapshot, &room, item) {
+            explored.items.push((room.name.clone(), item.clone()));
+        }
+    }
+
+    explored.map.insert(
+        room.name.clone(),
+        room.doors.iter().map(|d| (d.clone(), None)).collect(),
+    );
+
+    for door in room.doors.iter() {
+        let mut game = Game::restore(&snapshot);
+
+        let rooms = match game.command(door) {
+            Ok(text) => parse_rooms(&text),
+            Err(_) => continue,
+        };
+
+        // Going the wrong way from the checkpoint weighs the droid and throws it back
+        let next = match rooms.as_slice() {
+            [next] => next.clone(),
+            _ if room.name == CHECKPOINT => {
+                explored.floor = Some(door.clone());
+                continue;
+            }
+            _ => continue,
+        };
+
+        explored
+            .map
+            .get_mut(&room.name)
+            .unwrap()
+            .insert(door.clone(), Some(next.name.clone()));
+
+        if !explored.map.contains_key(&next.name) {
+            explore(game, next, explored);
+        }
+    }
+}
+
+// The doors to go through from one room to another
+fn route(map: &ShipMap, from: &str, to: &str) -> Vec<String> {
+    let mut previous: HashMap<&str, (&str, &str)> = HashMap::new();
+    let mut seen = HashSet::new();
+    let mut queue = VecDeque::new();
+
+    seen.insert(from);
+    queue.push_back(from);
+
+    while let Some(room) = queue.pop_front() {
+        if room == to {
+            break;
+        }
+
+        for (door, next) in map[room].iter() {
+            if let Some(next) = next {
+                if seen.insert(next.as_str()) {
+                    previous.insert(next, (room, door));
+                    queue.push_back(next);
+                }
+            }
+        }
+    }
+
+    let mut doors = Vec::new();
+    let mut room = to;
+
+    while let Some((from, door)) = previous.get(room) {
+        doors.push(door.to_string());
+        room = from;
+    }
+
+    doors.reverse();
+    doors
+}
+
+// The item to take or drop at each step, as (index, take), so that every subset of n
+// items comes up once starting from holding them all. Each step only changes one item.
+fn gray_code_steps(n: usize) -> impl Iterator<Item = (usize, bool)> {
+    (1..1u64 << n).map(|i| {
+        let changed = i.trailing_zeros() as usize;
+        let dropped = (i ^ (i >> 1)) & (1 << changed) != 0;
+
+        (changed, !dropped)
+    })
+}
+
+fn password(text: &str) -> Option<i64> {
+    text.split_whitespace()
+        .skip_while(|&w| w != "typing")
+        .nth(1)
+        .and_then(|w| w.parse().ok())
+}
+
+fn part1(program: &[i64]) -> Result<i64, String> {
+    let mut game = Game {
+        vm: IntcodeVM::new(program),
+    };
+
+    let start = match game.send(None) {
+        Response::Text(text) => parse_rooms(&text).pop().ok_or("No starting room")?,
+        _ => return Err("The game didn't start".to_string()),
+    };
+
+    let mut explored = Explored {
+        map: HashMap::new(),
+        items: Vec::new(),
+        floor: None,
+    };
+
+    explore(
+        Game::restore(&game.snapshot()),
+        start.clone(),
+        &mut explored,
+    );
+
+    let floor = explored.floor.clone().ok_or("No pressure-sensitive floor found")?;
+
+    // Walk around picking everything up, then go to the checkpoint
+    let mut room = start.name.clone();
+    let stops = explored
+        .items
+        .iter()
+        .map(|(room, item)| (room.clone(), Some(item.clone())))
+        .chain(Some((CHECKPOINT.to_string(), None)));
+
+    for (stop, item) in stops {
+        for door in route(&explored.map, &room, &stop) {
+            game.command(&door)?;
+        }
+
+        if let Some(item) = item {
+            game.command(&format!("take {}", item))?;
+        }
+
+        room = stop;
+    }
+
+    let items = explored.items.iter().map(|(_, item)| item).collect::<Vec<_>>();
+    let mut steps = gray_code_steps(items.len());
+
+    loop {
+        match game.send(Some(&floor)) {
+            Response::Text(_) => (),
+            Response::Halted(text) => return password(&text).ok_or(text),
+            Response::Stuck => return Err("Stuck at the checkpoint".to_string()),
+        }
+
+        let (index, take) = steps.next().ok_or("No set of items gets through")?;
+        let command = if take { "take" } else { "drop" };
+
+        game.command(&format!("{} {}", command, items[index]))?;
+    }
+}
+
+pub struct Day25;
+
+impl Solution for Day25 {
+    fn part1(input: &str) -> Answer {
+        let program = parse_program(input).expect("Invalid intcode program");
+
+        part1(&program).expect("Unable to get through the checkpoint").into()
+    }
+
+    // The last star is given for finishing every other puzzle
+    fn part2(_input: &str) -> Answer {
+        Answer::None
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn rooms() {
+        let text = "
+
+== Hull Breach ==
+You got in through a hole in the floor here.
+
+Doors here lead:
+- north
+- south
+
+Items here:
+- mug
+
+Command?
+";
+
+        assert_eq!(
+            parse_rooms(text),
+            vec![Room {
+                name: "Hull Breach".to_string(),
+                doors: vec!["north".to_string(), "south".to_string()],
+                items: vec!["mug".to_string()],
+            }]
+        );
+        assert_eq!(
+            password("get in by typing 2424308736 on the keypad"),
+            Some(2424308736)
+        );
+    }
+
+    #[test]
+    fn gray_code() {
+        let mut held = vec![true; 3];
+        let mut seen = HashSet::new();
+        seen.insert(held.clone());
+
+        for (index, take) in gray_code_steps(3) {
+            assert_eq!(held[index], !take);
+            held[index] = take;
+            assert!(seen.insert(held.clone()));
+        }
+
+        assert_eq!(seen.len(), 8);
+    }
+}
@@ -0,0 +1,62 @@
+use crate::solution::Day;
+
+mod day01;
+mod day02;
+mod day03;
+mod day04;
+mod day05;
+mod day06;
+mod day07;
+mod day08;
+mod day09;
+mod day10;
+mod day11;
+mod day12;
+mod day13;
+mod day14;
+mod day15;
+mod day16;
+mod day17;
+mod day18;
+mod day19;
+mod day20;
+mod day21;
+mod day22;
+mod day23;
+mod day24;
+mod day25;
+
+// Every solved day, in calendar order
+pub fn all() -> Vec<Day> {
+    vec![
+        Day::new::<day01::Day01>(1, "The Tyranny of the Rocket Equation"),
+        Day::new::<day02::Day02>(2, "1202 Program Alarm"),
+        Day::new::<day03::Day03>(3, "Crossed Wires"),
+        Day::new::<day04::Day04>(4, "Secure Container"),
+        Day::new::<day05::Day05>(5, "Sunny with a Chance of Asteroids"),
+        Day::new::<day06::Day06>(6, "Universal Orbit Map"),
+        Day::new::<day07::Day07>(7, "Amplification Circuit"),
+        Day::new::<day08::Day08>(8, "Space Image Format"),
+        Day::new::<day09::Day09>(9, "Sensor Boost"),
+        Day::new::<day10::Day10>(10, "Monitoring Station"),
+        Day::new::<day11::Day11>(11, "Space Police"),
+        Day::new::<day12::Day12>(12, "The N-Body Problem"),
+        Day::new::<day13::Day13>(13, "Care Package"),
+        Day::new::<day14::Day14>(14, "Space Stoichiometry"),
+        Day::new::<day15::Day15>(15, "Oxygen System"),
+        Day::new::<day16::Day16>(16, "Flawed Frequency Transmission"),
+        Day::new::<day17::Day17>(17, "Set and Forget"),
+        Day::new::<day18::Day18>(18, "Many-Worlds Interpretation"),
+        Day::new::<day19::Day19>(19, "Tractor Beam"),
+        Day::new::<day20::Day20>(20, "Donut Maze"),
+        Day::new::<day21::Day21>(21, "Springdroid Adventure"),
+        Day::new::<day22::Day22>(22, "Slam Shuffle"),
+        Day::new::<day23::Day23>(23, "Category Six"),
+        Day::new::<day24::Day24>(24, "Planet of Discord"),
+        Day::new::<day25::Day25>(25, "Cryostasis"),
+    ]
+}
+
+pub fn get(number: u32) -> Option<Day> {
+    all().into_iter().find(|day| day.number == number)
+}
@@ -0,0 +1,8 @@
+pub mod answers;
+pub mod days;
+pub mod report;
+pub mod solution;
+
+pub use answers::*;
+pub use report::*;
+pub use solution::*;
@@ -0,0 +1,297 @@
+// Runs the puzzle solutions.
+// Usage: aoc run DAY [--part N] [--input PATH] [--check] [--format FORMAT]
+//        aoc run --all [--part N] [--check] [--format FORMAT]
+//        aoc list
+//
+// --input - reads the input from stdin. Without --input a part reads DAY_PART.txt if it
+// has an input of its own, and otherwise DAY.txt or DAY_1.txt, from $AOC_INPUT_DIR or
+// inputs/. Each part is timed separately, without the time it takes to read the input.
+//
+// --check compares the answers with the ones in answers.toml next to the inputs, and
+// fails if any of them is wrong.
+//
+// --format json prints a JSON object per part, and --format csv a CSV row per part after
+// a header, instead of the text for reading. See report.rs for the fields.
+
+use std::any::Any;
+use std::env;
+use std::panic;
+use std::process;
+use std::time::{Duration, Instant};
+
+use aoc::*;
+use common::*;
+
+const USAGE: &str = "Usage: aoc run DAY [--part N] [--input PATH] [--check] [--format FORMAT]
+       aoc run --all [--part N] [--check] [--format FORMAT]
+       aoc list";
+
+fn fail(message: String) -> ! {
+    eprintln!("{}", message);
+    process::exit(1);
+}
+
+// The parts run so far
+#[derive(Default)]
+struct Tally {
+    solved: usize,
+    failed: usize,
+    passed: usize,
+    wrong: usize,
+    missing: usize,
+    elapsed: Duration,
+}
+
+impl Tally {
+    fn record(&mut self, report: &Report) {
+        match report.answer {
+            Ok(_) => {
+                self.solved += 1;
+                self.elapsed += report.elapsed;
+            }
+            Err(_) => self.failed += 1,
+        }
+
+        match report.check {
+            None => (),
+            Some(Check::Pass) => self.passed += 1,
+            Some(Check::Fail { .. }) => self.wrong += 1,
+            Some(Check::Missing) => self.missing += 1,
+        }
+    }
+}
+
+// Prints a label with a text after it, or below it if the text has several lines
+fn print_labelled(label: &str, text: &str) {
+    if text.contains('\n') {
+        println!("{}", label);
+
+        for line in text.lines() {
+            println!("    {}", line);
+        }
+    } else {
+        println!("{} {}", label, text);
+    }
+}
+
+fn panic_message(panic: Box<dyn Any + Send>) -> String {
+    let message = match panic.downcast::<String>() {
+        Ok(message) => *message,
+        Err(panic) => match panic.downcast::<&str>() {
+            Ok(message) => message.to_string(),
+            Err(_) => return "Panicked".to_string(),
+        },
+    };
+
+    format!("Panicked: {}", message)
+}
+
+// Solves one part, checking the answer if there are answers to check against
+fn solve_part(day: &Day, part: u32, input: &str, answers: Option<&Answers>) -> Report {
+    let start = Instant::now();
+    let answer = panic::catch_unwind(|| day.solve(part, input));
+    let elapsed = start.elapsed();
+
+    let answer = match answer {
+        Ok(Some(answer)) => Ok(answer),
+        Ok(None) => Err("No such part".to_string()),
+        Err(panic) => Err(panic_message(panic)),
+    };
+
+    let check = match (&answer, answers) {
+        (Ok(answer), Some(answers)) => Some(answers.check(day.number, part, answer)),
+        _ => None,
+    };
+
+    Report {
+        day: day.number,
+        part,
+        answer,
+        elapsed,
+        check,
+    }
+}
+
+fn print_text(report: &Report) {
+    let answer = match &report.answer {
+        Ok(answer) => answer,
+        Err(e) => {
+            println!("  Part {}: {}", report.part, e);
+            return;
+        }
+    };
+
+    let status = match &report.check {
+        None => "",
+        Some(Check::Pass) => " [pass]",
+        Some(Check::Fail { .. }) => " [FAIL]",
+        Some(Check::Missing) => " [missing]",
+    };
+
+    print_labelled(
+        &format!("  Part {} ({:.2?}){}:", report.part, report.elapsed, status),
+        &answer.to_string(),
+    );
+
+    if let Some(Check::Fail { expected }) = &report.check {
+        print_labelled("    expected:", &expected.to_string());
+    }
+}
+
+// The parts often share their input, and stdin can only be read once, so the last input
+// read is kept for the next part
+fn read_input(input: Input, last_read: &mut Option<(Input, String)>) -> Result<String, InputError> {
+    if let Some((read, text)) = last_read {
+        if *read == input {
+            return Ok(text.clone());
+        }
+    }
+
+    let text = input.read()?;
+    *last_read = Some((input, text.clone()));
+
+    Ok(text)
+}
+
+// Only the text format has headings and totals, the others print a record per part
+fn run(
+    days: &[Day],
+    parts: &[u32],
+    input: Option<&str>,
+    answers: Option<&Answers>,
+    format: Format,
+) -> Tally {
+    let mut tally = Tally::default();
+
+    if format == Format::Csv {
+        println!("{}", CSV_HEADER);
+    }
+
+    for day in days {
+        if format == Format::Text {
+            println!("Day {}: {}", day.number, day.title);
+        }
+
+        let mut last_read = None;
+
+        for &part in parts {
+            let text = resolve_input(day.number, part, input)
+                .and_then(|input| read_input(input, &mut last_read));
+
+            let report = match text {
+                Ok(text) => solve_part(day, part, &text, answers),
+                Err(e) => Report {
+                    day: day.number,
+                    part,
+                    answer: Err(e.to_string()),
+                    elapsed: Duration::default(),
+                    check: None,
+                },
+            };
+
+            match format {
+                Format::Text => print_text(&report),
+                Format::Json => println!("{}", report.json()),
+                Format::Csv => println!("{}", report.csv()),
+            }
+
+            tally.record(&report);
+        }
+    }
+
+    if format != Format::Text {
+        return tally;
+    }
+
+    if days.len() > 1 {
+        println!("Total: {:.2?}", tally.elapsed);
+    }
+
+    if answers.is_some() {
+        println!(
+            "Checked {} parts: {} passed, {} failed, {} missing",
+            tally.solved + tally.failed,
+            tally.passed,
+            tally.wrong + tally.failed,
+            tally.missing
+        );
+    }
+
+    tally
+}
+
+fn list() {
+    for day in days::all() {
+        println!("{:>2}  {}", day.number, day.title);
+    }
+}
+
+fn main() {
+    let mut args = env::args().skip(1);
+
+    match args.next().as_deref() {
+        Some("list") => list(),
+        Some("run") => {
+            let mut all = false;
+            let mut check = false;
+            let mut day = None;
+            let mut part = None;
+            let mut input = None;
+            let mut format = None;
+
+            while let Some(arg) = args.next() {
+                match arg.as_str() {
+                    "--all" => all = true,
+                    "--check" => check = true,
+                    "--part" => part = args.next(),
+                    "--input" => input = args.next(),
+                    "--format" => format = args.next(),
+                    _ => day = Some(arg),
+                }
+            }
+
+            let days = match (all, day) {
+                (true, None) if input.is_none() => days::all(),
+                (false, Some(day)) => {
+                    let day = day
+                        .parse()
+                        .ok()
+                        .and_then(days::get)
+                        .unwrap_or_else(|| fail(format!("No solution for day {}", day)));
+
+                    vec![day]
+                }
+                _ => fail(USAGE.to_string()),
+            };
+
+            let parts = match part.as_deref() {
+                None => vec![1, 2],
+                Some("1") => vec![1],
+                Some("2") => vec![2],
+                Some(part) => fail(format!("No part {}, the parts are 1 and 2", part)),
+            };
+
+            let format = match format {
+                Some(format) => format.parse().unwrap_or_else(|e| fail(e)),
+                None => Format::Text,
+            };
+
+            let answers = if check {
+                let path = input_dir().join("answers.toml");
+                let answers = Answers::load(&path)
+                    .unwrap_or_else(|e| fail(format!("{}: {}", path.display(), e)));
+
+                Some(answers)
+            } else {
+                None
+            };
+
+            let tally = run(&days, &parts, input.as_deref(), answers.as_ref(), format);
+
+            if tally.failed > 0 || tally.wrong > 0 {
+                process::exit(1);
+            }
+        }
+        _ => fail(USAGE.to_string()),
+    }
+}
@@ -0,0 +1,222 @@
+// The result of running one part, and the machine-readable ways of printing it: JSON
+// Lines, one object per part, or CSV with a header. Every record has the same fields, so
+// a picture's pixels are always there in JSON, and null when the answer isn't a picture.
+// A JSON record, wrapped here but printed on one line:
+//
+//     {"day":1,"part":1,"answer":3271095,"picture":null,"elapsed_ms":0.021,
+//      "check":"pass","error":null}
+//
+//     day,part,answer,elapsed_ms,check,error
+//     1,1,3271095,0.021,pass,
+
+use std::fmt;
+use std::str::FromStr;
+use std::time::Duration;
+
+use crate::answers::Check;
+use crate::solution::Answer;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Format {
+    Text,
+    Json,
+    Csv,
+}
+
+impl FromStr for Format {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Format, String> {
+        match s {
+            "text" => Ok(Format::Text),
+            "json" => Ok(Format::Json),
+            "csv" => Ok(Format::Csv),
+            _ => Err(format!(
+                "Unknown format {}, the formats are text, json and csv",
+                s
+            )),
+        }
+    }
+}
+
+impl fmt::Display for Format {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Format::Text => write!(f, "text"),
+            Format::Json => write!(f, "json"),
+            Format::Csv => write!(f, "csv"),
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Report {
+    pub day: u32,
+    pub part: u32,
+    // Why there is no answer if the part couldn't be solved
+    pub answer: Result<Answer, String>,
+    pub elapsed: Duration,
+    // Only when the answers are checked
+    pub check: Option<Check>,
+}
+
+pub const CSV_HEADER: &str = "day,part,answer,elapsed_ms,check,error";
+
+fn json_string(s: &str) -> String {
+    let mut quoted = String::from("\"");
+
+    for c in s.chars() {
+        match c {
+            '"' => quoted.push_str("\\\""),
+            '\\' => quoted.push_str("\\\\"),
+            '\n' => quoted.push_str("\\n"),
+            '\r' => quoted.push_str("\\r"),
+            '\t' => quoted.push_str("\\t"),
+            c if (c as u32) < 0x20 => quoted.push_str(&format!("\\u{:04x}", c as u32)),
+            c => quoted.push(c),
+        }
+    }
+
+    quoted.push('"');
+    quoted
+}
+
+fn json_option(s: Option<&str>) -> String {
+    s.map_or_else(|| "null".to_string(), json_string)
+}
+
+// Quoted only if it has to be, with quotes doubled
+fn csv_field(s: &str) -> String {
+    if s.contains(&[',', '"', '\n', '\r'][..]) {
+        format!("\"{}\"", s.replace('"', "\"\""))
+    } else {
+        s.to_string()
+    }
+}
+
+fn check_name(check: &Check) -> &'static str {
+    match check {
+        Check::Pass => "pass",
+        Check::Fail { .. } => "fail",
+        Check::Missing => "missing",
+    }
+}
+
+impl Report {
+    fn elapsed_ms(&self) -> String {
+        format!("{:.3}", self.elapsed.as_secs_f64() * 1000.0)
+    }
+
+    fn error(&self) -> Option<&str> {
+        self.answer.as_ref().err().map(String::as_str)
+    }
+
+    // A number stays a number, and a picture that couldn't be read is null
+    fn json_answer(&self) -> String {
+        match &self.answer {
+            Ok(Answer::Number(n)) => n.to_string(),
+            Ok(Answer::Picture { text, .. }) => json_option(text.as_deref()),
+            Ok(Answer::None) | Err(_) => "null".to_string(),
+            Ok(answer) => json_string(&answer.to_string()),
+        }
+    }
+
+    pub fn json(&self) -> String {
+        let picture = self.answer.as_ref().ok().and_then(Answer::rendered);
+
+        format!(
+            concat!(
+                "{{\"day\":{},\"part\":{},\"answer\":{},\"picture\":{},",
+                "\"elapsed_ms\":{},\"check\":{},\"error\":{}}}"
+            ),
+            self.day,
+            self.part,
+            self.json_answer(),
+            json_option(picture.as_deref()),
+            self.elapsed_ms(),
+            json_option(self.check.as_ref().map(check_name)),
+            json_option(self.error())
+        )
+    }
+
+    // A part without a puzzle or without an answer has an empty answer
+    pub fn csv(&self) -> String {
+        let answer = match &self.answer {
+            Ok(Answer::None) | Err(_) => String::new(),
+            Ok(answer) => answer.to_string(),
+        };
+
+        format!(
+            "{},{},{},{},{},{}",
+            self.day,
+            self.part,
+            csv_field(&answer),
+            self.elapsed_ms(),
+            self.check.as_ref().map_or("", check_name),
+            csv_field(self.error().unwrap_or(""))
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use grid::Grid;
+
+    fn report(answer: Result<Answer, String>, check: Option<Check>) -> Report {
+        Report {
+            day: 8,
+            part: 2,
+            answer,
+            elapsed: Duration::from_micros(1500),
+            check,
+        }
+    }
+
+    #[test]
+    fn json() {
+        let number = report(Ok(Answer::Number(42)), Some(Check::Pass));
+        assert_eq!(
+            number.json(),
+            concat!(
+                r#"{"day":8,"part":2,"answer":42,"picture":null,"#,
+                r#""elapsed_ms":1.500,"check":"pass","error":null}"#
+            )
+        );
+
+        let text = report(Ok("a \"b\"\n".into()), None);
+        assert!(text.json().contains(r#""answer":"a \"b\"\n","#));
+
+        let pixels = Grid::parse("#.\n.#\n", |c| Some(c == '#')).unwrap();
+        let picture = report(Ok(Answer::picture(pixels)), None);
+        assert!(picture
+            .json()
+            .contains(r##""answer":null,"picture":"#.\n.#","##));
+
+        let failed = report(Err("overflow".to_string()), None);
+        assert!(failed.json().contains(r#""answer":null,"#));
+        assert!(failed
+            .json()
+            .ends_with(r#""check":null,"error":"overflow"}"#));
+    }
+
+    #[test]
+    fn csv() {
+        let number = report(Ok(Answer::Number(42)), Some(Check::Missing));
+        assert_eq!(number.csv(), "8,2,42,1.500,missing,");
+
+        let text = report(Ok("a,\"b\"".into()), None);
+        assert_eq!(text.csv(), "8,2,\"a,\"\"b\"\"\",1.500,,");
+
+        let failed = report(Err("No input, tried 8.txt".to_string()), None);
+        assert_eq!(failed.csv(), "8,2,,1.500,,\"No input, tried 8.txt\"");
+        assert_eq!(CSV_HEADER.split(',').count(), 6);
+    }
+
+    #[test]
+    fn formats() {
+        assert_eq!("json".parse(), Ok(Format::Json));
+        assert_eq!("csv".parse::<Format>().unwrap().to_string(), "csv");
+        assert!("xml".parse::<Format>().is_err());
+    }
+}
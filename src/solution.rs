@@ -0,0 +1,162 @@
+use std::fmt;
+
+use grid::Grid;
+
+// The answer to one part of a puzzle
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Answer {
+    Number(i64),
+    Text(String),
+    // Letters drawn with lit pixels, and the letters if they could be read
+    Picture {
+        pixels: Grid<bool>,
+        text: Option<String>,
+    },
+    // For a part without a puzzle, like the second half of day 25
+    None,
+}
+
+impl Answer {
+    // The letters are read from the pixels, if they are all in the font
+    pub fn picture(pixels: Grid<bool>) -> Answer {
+        let text = grid::read_letters(&pixels).ok();
+
+        Answer::Picture { pixels, text }
+    }
+
+    // The pixels of a picture, # where lit, without the last newline
+    pub fn rendered(&self) -> Option<String> {
+        match self {
+            Answer::Picture { pixels, .. } => {
+                let text = pixels.render(|&lit| if lit { '#' } else { '.' });
+
+                Some(text.trim_end().to_string())
+            }
+            _ => None,
+        }
+    }
+}
+
+// A picture shows its letters, or its pixels if they couldn't be read
+impl fmt::Display for Answer {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Answer::Number(n) => write!(f, "{}", n),
+            Answer::Text(s) => write!(f, "{}", s),
+            Answer::Picture {
+                text: Some(text), ..
+            } => write!(f, "{}", text),
+            Answer::Picture { .. } => write!(f, "{}", self.rendered().unwrap()),
+            Answer::None => write!(f, "-"),
+        }
+    }
+}
+
+macro_rules! impl_from_number {
+    ($($t:ty),*) => {
+        $(
+            impl From<$t> for Answer {
+                fn from(n: $t) -> Answer {
+                    Answer::Number(n as i64)
+                }
+            }
+        )*
+    };
+}
+
+impl_from_number!(i32, i64, isize, u32, u64, usize);
+
+impl From<String> for Answer {
+    fn from(s: String) -> Answer {
+        Answer::Text(s)
+    }
+}
+
+impl From<&str> for Answer {
+    fn from(s: &str) -> Answer {
+        Answer::Text(s.to_string())
+    }
+}
+
+// A day of the calendar, solving both parts from the puzzle input
+pub trait Solution {
+    fn part1(input: &str) -> Answer;
+    fn part2(input: &str) -> Answer;
+}
+
+// A registered solution. The parts are kept as plain functions so that every day fits
+// in the same list.
+#[derive(Clone, Copy)]
+pub struct Day {
+    pub number: u32,
+    pub title: &'static str,
+    parts: [fn(&str) -> Answer; 2],
+}
+
+impl Day {
+    pub fn new<S: Solution>(number: u32, title: &'static str) -> Day {
+        Day {
+            number,
+            title,
+            parts: [S::part1, S::part2],
+        }
+    }
+
+    // None if there is no such part
+    pub fn solve(&self, part: u32, input: &str) -> Option<Answer> {
+        let solve = self.parts.get((part as usize).checked_sub(1)?)?;
+
+        Some(solve(input))
+    }
+}
+
+impl fmt::Debug for Day {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "Day({}, {:?})", self.number, self.title)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    struct Echo;
+
+    impl Solution for Echo {
+        fn part1(input: &str) -> Answer {
+            input.len().into()
+        }
+
+        fn part2(input: &str) -> Answer {
+            input.into()
+        }
+    }
+
+    #[test]
+    fn solve_parts() {
+        let day = Day::new::<Echo>(1, "Echo");
+
+        assert_eq!(day.solve(1, "abc"), Some(Answer::Number(3)));
+        assert_eq!(day.solve(2, "abc"), Some(Answer::Text("abc".to_string())));
+        assert_eq!(day.solve(0, "abc"), None);
+        assert_eq!(day.solve(3, "abc"), None);
+        assert_eq!(Answer::None.to_string(), "-");
+    }
+
+    #[test]
+    fn pictures() {
+        let pixels = |text| Grid::parse(text, |c| Some(c == '#')).unwrap();
+
+        let unknown = Answer::picture(pixels("#.\n.#\n"));
+        assert_eq!(unknown.to_string(), "#.\n.#");
+        assert_eq!(unknown.rendered().as_deref(), Some("#.\n.#"));
+        assert_eq!(Answer::Number(1).rendered(), None);
+
+        let letter = Answer::picture(pixels("#...\n#...\n#...\n#...\n#...\n####\n"));
+        assert_eq!(letter.to_string(), "L");
+        assert_eq!(
+            letter.rendered().as_deref(),
+            Some("#...\n#...\n#...\n#...\n#...\n####")
+        );
+    }
+}
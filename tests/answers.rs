@@ -0,0 +1,65 @@
+// Runs every solution against its input and checks the answer with inputs/answers.toml,
+// or the answers.toml in $AOC_INPUT_DIR
+use aoc::*;
+use common::*;
+
+fn check_day(number: u32) {
+    let dir = input_dir();
+    let answers = Answers::load(&dir.join("answers.toml")).expect("Unable to load the answers");
+    let day = days::get(number).expect("No solution for the day");
+
+    for part in 1..=2 {
+        let input = find_input(&dir, number, part)
+            .and_then(|input| input.read())
+            .unwrap_or_else(|e| panic!("{}", e));
+        let answer = day.solve(part, &input).unwrap();
+
+        match answers.check(number, part, &answer) {
+            Check::Pass => (),
+            Check::Fail { expected } => panic!(
+                "Day {} part {}: expected\n{}\ngot\n{}",
+                number, part, expected, answer
+            ),
+            Check::Missing => panic!("Day {} part {}: no answer for {}", number, part, answer),
+        }
+    }
+}
+
+macro_rules! answer_tests {
+    ($($name:ident: $day:expr,)*) => {
+        $(
+            #[test]
+            fn $name() {
+                check_day($day);
+            }
+        )*
+    };
+}
+
+answer_tests! {
+    day01: 1,
+    day02: 2,
+    day03: 3,
+    day04: 4,
+    day05: 5,
+    day06: 6,
+    day07: 7,
+    day08: 8,
+    day09: 9,
+    day10: 10,
+    day11: 11,
+    day12: 12,
+    day13: 13,
+    day14: 14,
+    day15: 15,
+    day16: 16,
+    day17: 17,
+    day18: 18,
+    day19: 19,
+    day20: 20,
+    day21: 21,
+    day22: 22,
+    day23: 23,
+    day24: 24,
+    day25: 25,
+}